@@ -0,0 +1,128 @@
+use crate::features::plugin_store;
+use crate::hotkeys::HotkeyManager;
+use crate::plugins::validate::validate_plugin;
+use crate::plugins::PluginLoader;
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Parser)]
+#[command(name = "qol-tray", version, about = "Pluggable system tray daemon")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Print installed plugins
+    List,
+    /// Install a plugin from the configured plugin organization
+    Install { id: String },
+    /// Update an installed plugin
+    Update { id: String },
+    /// Print the hotkey configuration
+    Hotkeys,
+    /// Check a plugin directory's plugin.toml for errors
+    Validate {
+        path: PathBuf,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub fn run(command: Command) -> Result<()> {
+    match command {
+        Command::List => list_plugins(),
+        Command::Install { id } => {
+            block_on(plugin_store::install_plugin(&id))??;
+            println!("Installed {}", id);
+            Ok(())
+        }
+        Command::Update { id } => {
+            block_on(plugin_store::update_plugin(&id))??;
+            println!("Updated {}", id);
+            Ok(())
+        }
+        Command::Hotkeys => {
+            let config = HotkeyManager::new()?.load_config()?;
+            println!("{}", serde_json::to_string_pretty(&config)?);
+            Ok(())
+        }
+        Command::Validate { path, json } => validate(&path, json),
+    }
+}
+
+fn validate(path: &Path, json: bool) -> Result<()> {
+    let report = validate_plugin(path);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        for error in &report.errors {
+            println!("error: {}", error);
+        }
+        for warning in &report.warnings {
+            println!("warning: {}", warning);
+        }
+    }
+
+    if !report.is_valid() {
+        anyhow::bail!("{} has {} error(s)", path.display(), report.errors.len());
+    }
+    if !json {
+        println!("{} is valid", path.display());
+    }
+    Ok(())
+}
+
+fn list_plugins() -> Result<()> {
+    let mut plugins = PluginLoader::load_all()?;
+    plugins.sort_by(|a, b| a.id.cmp(&b.id));
+    for plugin in plugins {
+        println!("{}\t{}\t{}", plugin.id, plugin.manifest.plugin.name, plugin.manifest.plugin.version);
+    }
+    Ok(())
+}
+
+fn block_on<F: std::future::Future>(future: F) -> Result<F::Output> {
+    let rt = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    Ok(rt.block_on(future))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_subcommand_cases() {
+        let cases: [(&[&str], Option<Command>); 7] = [
+            (&["qol-tray"], None),
+            (&["qol-tray", "list"], Some(Command::List)),
+            (&["qol-tray", "install", "demo"], Some(Command::Install { id: "demo".to_string() })),
+            (&["qol-tray", "update", "demo"], Some(Command::Update { id: "demo".to_string() })),
+            (&["qol-tray", "hotkeys"], Some(Command::Hotkeys)),
+            (&["qol-tray", "validate", "demo"], Some(Command::Validate { path: "demo".into(), json: false })),
+            (&["qol-tray", "validate", "--json", "demo"], Some(Command::Validate { path: "demo".into(), json: true })),
+        ];
+
+        for (args, expected) in cases {
+            let cli = Cli::try_parse_from(args).unwrap();
+            assert_eq!(cli.command, expected, "args: {:?}", args);
+        }
+    }
+
+    #[test]
+    fn parse_rejects_invalid_args() {
+        let cases: [&[&str]; 4] = [
+            &["qol-tray", "install"],
+            &["qol-tray", "validate"],
+            &["qol-tray", "unknown"],
+            &["qol-tray", "list", "extra"],
+        ];
+
+        for args in cases {
+            assert!(Cli::try_parse_from(args).is_err(), "args: {:?}", args);
+        }
+    }
+}
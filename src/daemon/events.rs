@@ -1,25 +1,80 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
 use tokio::sync::broadcast;
 
 use super::DaemonEvent;
 
 const CHANNEL_CAPACITY: usize = 64;
 
+#[derive(Debug, Clone)]
+pub struct EventEnvelope {
+    pub id: u64,
+    pub event: DaemonEvent,
+}
+
+#[derive(Debug)]
+pub enum Replay {
+    Events(Vec<EventEnvelope>),
+    Gap { missed: u64, last_id: u64 },
+}
+
+#[derive(Default)]
+struct History {
+    last_id: u64,
+    recent: VecDeque<EventEnvelope>,
+}
+
+impl History {
+    fn push(&mut self, event: DaemonEvent) -> EventEnvelope {
+        self.last_id += 1;
+        let envelope = EventEnvelope { id: self.last_id, event };
+        if self.recent.len() == CHANNEL_CAPACITY {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(envelope.clone());
+        envelope
+    }
+
+    fn replay_after(&self, last_seen: u64) -> Replay {
+        if last_seen > self.last_id {
+            return Replay::Gap { missed: self.last_id, last_id: self.last_id };
+        }
+        let oldest = self.recent.front().map_or(self.last_id + 1, |e| e.id);
+        if last_seen + 1 < oldest {
+            return Replay::Gap { missed: oldest - last_seen - 1, last_id: self.last_id };
+        }
+        Replay::Events(self.recent.iter().filter(|e| e.id > last_seen).cloned().collect())
+    }
+}
+
 pub struct EventBus {
-    tx: broadcast::Sender<DaemonEvent>,
+    tx: broadcast::Sender<EventEnvelope>,
+    history: Mutex<History>,
 }
 
 impl EventBus {
     pub fn new() -> Self {
         let (tx, _) = broadcast::channel(CHANNEL_CAPACITY);
-        Self { tx }
+        Self {
+            tx,
+            history: Mutex::new(History::default()),
+        }
     }
 
     pub fn send(&self, event: DaemonEvent) {
-        let _ = self.tx.send(event);
+        let mut history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+        let envelope = history.push(event);
+        let _ = self.tx.send(envelope);
+    }
+
+    pub fn subscribe(&self) -> (u64, broadcast::Receiver<EventEnvelope>) {
+        let history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+        (history.last_id, self.tx.subscribe())
     }
 
-    pub fn subscribe(&self) -> broadcast::Receiver<DaemonEvent> {
-        self.tx.subscribe()
+    pub fn subscribe_after(&self, last_seen: u64) -> (Replay, broadcast::Receiver<EventEnvelope>) {
+        let history = self.history.lock().unwrap_or_else(|e| e.into_inner());
+        (history.replay_after(last_seen), self.tx.subscribe())
     }
 }
 
@@ -36,25 +91,25 @@ mod tests {
     #[tokio::test]
     async fn single_subscriber_receives_event() {
         let bus = EventBus::new();
-        let mut rx = bus.subscribe();
+        let (_, mut rx) = bus.subscribe();
 
         bus.send(DaemonEvent::PluginsChanged);
 
-        let event = rx.recv().await.unwrap();
+        let event = rx.recv().await.unwrap().event;
         assert!(matches!(event, DaemonEvent::PluginsChanged));
     }
 
     #[tokio::test]
     async fn multiple_subscribers_receive_same_event() {
         let bus = EventBus::new();
-        let mut rx1 = bus.subscribe();
-        let mut rx2 = bus.subscribe();
-        let mut rx3 = bus.subscribe();
+        let (_, mut rx1) = bus.subscribe();
+        let (_, mut rx2) = bus.subscribe();
+        let (_, mut rx3) = bus.subscribe();
 
         bus.send(DaemonEvent::PluginsChanged);
 
         for rx in [&mut rx1, &mut rx2, &mut rx3] {
-            let event = rx.recv().await.unwrap();
+            let event = rx.recv().await.unwrap().event;
             assert!(matches!(event, DaemonEvent::PluginsChanged));
         }
     }
@@ -65,13 +120,71 @@ mod tests {
 
         bus.send(DaemonEvent::PluginsChanged);
 
-        let mut rx = bus.subscribe();
+        let (_, mut rx) = bus.subscribe();
         bus.send(DaemonEvent::PluginsChanged);
 
-        let event = rx.recv().await.unwrap();
+        let event = rx.recv().await.unwrap().event;
         assert!(matches!(event, DaemonEvent::PluginsChanged));
     }
 
+    fn replay_ids(replay: Replay) -> Vec<u64> {
+        match replay {
+            Replay::Events(events) => events.iter().map(|e| e.id).collect(),
+            Replay::Gap { missed, .. } => panic!("unexpected gap of {}", missed),
+        }
+    }
+
+    #[tokio::test]
+    async fn events_get_monotonic_ids() {
+        let bus = EventBus::new();
+        let (last_id, mut rx) = bus.subscribe();
+        assert_eq!(last_id, 0);
+
+        bus.send(DaemonEvent::PluginsChanged);
+        bus.send(DaemonEvent::HotkeysChanged);
+
+        assert_eq!(rx.recv().await.unwrap().id, 1);
+        assert_eq!(rx.recv().await.unwrap().id, 2);
+        assert_eq!(bus.subscribe().0, 2);
+    }
+
+    #[test]
+    fn subscribe_after_replays_events_since_id() {
+        let bus = EventBus::new();
+        for _ in 0..5 {
+            bus.send(DaemonEvent::PluginsChanged);
+        }
+
+        let cases = [(0, vec![1, 2, 3, 4, 5]), (3, vec![4, 5]), (5, vec![])];
+
+        for (last_seen, expected) in cases {
+            let (replay, _) = bus.subscribe_after(last_seen);
+            assert_eq!(replay_ids(replay), expected, "last_seen: {}", last_seen);
+        }
+    }
+
+    #[test]
+    fn subscribe_after_reports_gaps_beyond_buffer() {
+        let bus = EventBus::new();
+        for _ in 0..CHANNEL_CAPACITY + 10 {
+            bus.send(DaemonEvent::PluginsChanged);
+        }
+
+        let cases = [(0, Some(10)), (5, Some(5)), (9, Some(1)), (10, None), (500, Some(74))];
+
+        for (last_seen, expected_gap) in cases {
+            let (replay, _) = bus.subscribe_after(last_seen);
+            match (replay, expected_gap) {
+                (Replay::Gap { missed, last_id }, Some(expected)) => {
+                    assert_eq!(missed, expected, "last_seen: {}", last_seen);
+                    assert_eq!(last_id, CHANNEL_CAPACITY as u64 + 10);
+                }
+                (Replay::Events(events), None) => assert_eq!(events.len(), CHANNEL_CAPACITY, "last_seen: {}", last_seen),
+                (replay, expected) => panic!("last_seen {}: got {:?}, expected gap {:?}", last_seen, replay, expected),
+            }
+        }
+    }
+
     #[test]
     fn send_without_subscribers_does_not_panic() {
         let bus = EventBus::new();
@@ -79,21 +192,21 @@ mod tests {
     }
 }
 
-#[cfg(all(test, feature = "dev"))]
+#[cfg(test)]
 mod dev_tests {
     use super::*;
 
     #[tokio::test]
     async fn subscribers_receive_events_in_order() {
         let bus = EventBus::new();
-        let mut rx = bus.subscribe();
+        let (_, mut rx) = bus.subscribe();
 
         bus.send(DaemonEvent::DiscoveryStarted);
-        bus.send(DaemonEvent::DiscoveryComplete { plugins: vec![] });
+        bus.send(DaemonEvent::DiscoveryComplete { plugins: vec![], errors: vec![] });
         bus.send(DaemonEvent::PluginsChanged);
 
-        assert!(matches!(rx.recv().await.unwrap(), DaemonEvent::DiscoveryStarted));
-        assert!(matches!(rx.recv().await.unwrap(), DaemonEvent::DiscoveryComplete { .. }));
-        assert!(matches!(rx.recv().await.unwrap(), DaemonEvent::PluginsChanged));
+        assert!(matches!(rx.recv().await.unwrap().event, DaemonEvent::DiscoveryStarted));
+        assert!(matches!(rx.recv().await.unwrap().event, DaemonEvent::DiscoveryComplete { .. }));
+        assert!(matches!(rx.recv().await.unwrap().event, DaemonEvent::PluginsChanged));
     }
 }
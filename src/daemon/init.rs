@@ -1,12 +1,13 @@
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 
+use super::state::{DiscoveryCache, RootStamp};
 use super::EventBus;
-#[cfg(feature = "dev")]
-use super::{DaemonEvent, DaemonState, DiscoveredPluginInfo, DiscoveryStatus};
+use super::{DaemonEvent, DaemonState, DiscoveredPluginInfo, DiscoveryErrorInfo};
 
 #[derive(Clone)]
 pub struct Daemon {
-    #[cfg(feature = "dev")]
     pub state: Arc<DaemonState>,
     pub events: Arc<EventBus>,
 }
@@ -14,53 +15,83 @@ pub struct Daemon {
 impl Daemon {
     pub fn new() -> Self {
         Self {
-            #[cfg(feature = "dev")]
             state: Arc::new(DaemonState::new()),
             events: Arc::new(EventBus::new()),
         }
     }
 
-    #[cfg(feature = "dev")]
-    pub fn start_discovery(&self, plugins_dir: std::path::PathBuf) {
+    pub fn start_discovery(&self, plugins_dir: PathBuf, force: bool) {
         let state = Arc::clone(&self.state);
         let events = Arc::clone(&self.events);
 
-        {
-            let guard = state.discovery.read().unwrap();
-            if guard.status == DiscoveryStatus::Discovering {
-                return;
-            }
-        }
+        let generation = state.discovery.write().unwrap().begin();
 
         std::thread::spawn(move || {
-            {
-                let mut guard = state.discovery.write().unwrap();
-                guard.status = DiscoveryStatus::Discovering;
-            }
             events.send(DaemonEvent::DiscoveryStarted);
 
             let config = crate::dev::DevConfig::load().unwrap_or_default();
+            let roots = discovery_roots(&config, &plugins_dir);
+
+            let cached = if force {
+                None
+            } else {
+                state.discovery.read().unwrap().cached_result(&roots, Instant::now())
+            };
+            if let Some((plugins, errors)) = cached {
+                log::debug!("Reusing cached discovery results");
+                if state.discovery.write().unwrap().finish(generation, plugins.clone(), errors.clone()) {
+                    events.send(DaemonEvent::DiscoveryComplete { plugins, errors });
+                }
+                return;
+            }
+
             let discovered = crate::dev::discover_plugins(&config, &plugins_dir);
 
             let plugins: Vec<DiscoveredPluginInfo> = discovered
+                .plugins
                 .into_iter()
                 .map(|p| DiscoveredPluginInfo {
                     id: p.id,
                     name: p.name,
                     path: p.path,
+                    installed_not_linked: p.installed_not_linked,
                 })
                 .collect();
 
-            {
-                let mut guard = state.discovery.write().unwrap();
-                guard.status = DiscoveryStatus::Complete;
-                guard.plugins = plugins.clone();
+            let errors: Vec<DiscoveryErrorInfo> = discovered
+                .errors
+                .into_iter()
+                .map(|e| {
+                    log::warn!("Discovery skipped {}: {}", e.path, e.reason);
+                    DiscoveryErrorInfo {
+                        path: e.path,
+                        reason: e.reason,
+                    }
+                })
+                .collect();
+
+            let mut discovery = state.discovery.write().unwrap();
+            let current = discovery.finish(generation, plugins.clone(), errors.clone());
+            if current {
+                discovery.cache = Some(DiscoveryCache { roots, cached_at: Instant::now() });
+            }
+            drop(discovery);
+            if current {
+                events.send(DaemonEvent::DiscoveryComplete { plugins, errors });
             }
-            events.send(DaemonEvent::DiscoveryComplete { plugins });
         });
     }
 }
 
+fn discovery_roots(config: &crate::dev::DevConfig, plugins_dir: &std::path::Path) -> Vec<RootStamp> {
+    config
+        .effective_search_paths()
+        .into_iter()
+        .chain(std::iter::once(plugins_dir.to_path_buf()))
+        .map(RootStamp::read)
+        .collect()
+}
+
 impl Default for Daemon {
     fn default() -> Self {
         Self::new()
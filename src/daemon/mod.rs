@@ -1,31 +1,48 @@
 mod events;
 mod init;
-#[cfg(feature = "dev")]
 mod state;
 
-pub use events::EventBus;
+pub use events::{EventBus, EventEnvelope, Replay};
 pub use init::Daemon;
-#[cfg(feature = "dev")]
 pub use state::{DaemonState, DiscoveryStatus};
 
 use serde::Serialize;
 
-#[cfg(feature = "dev")]
 #[derive(Debug, Clone, Serialize)]
 pub struct DiscoveredPluginInfo {
     pub id: String,
     pub name: String,
     pub path: String,
+    pub installed_not_linked: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoveryErrorInfo {
+    pub path: String,
+    pub reason: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
+#[allow(clippy::enum_variant_names)]
 pub enum DaemonEvent {
     PluginsChanged,
-    #[cfg(feature = "dev")]
+    HotkeysChanged,
+    PluginConfigChanged { id: String },
+    DaemonRestarted { id: String, pid: u32 },
+    InstallProgress { id: String, stage: String, percent: u8 },
     DiscoveryStarted,
-    #[cfg(feature = "dev")]
-    DiscoveryComplete { plugins: Vec<DiscoveredPluginInfo> },
+    DiscoveryComplete {
+        plugins: Vec<DiscoveredPluginInfo>,
+        errors: Vec<DiscoveryErrorInfo>,
+    },
+    Resync { missed: u64 },
+    Snapshot {
+        plugins: Vec<String>,
+        discovery: DiscoveryStatus,
+        discovered: Vec<DiscoveredPluginInfo>,
+        discovery_errors: Vec<DiscoveryErrorInfo>,
+    },
 }
 
 #[cfg(test)]
@@ -33,15 +50,54 @@ mod tests {
     use super::*;
 
     #[test]
-    fn plugins_changed_serializes_with_type_only() {
-        let event = DaemonEvent::PluginsChanged;
+    fn unit_events_serialize_with_type_only() {
+        let cases = [
+            (DaemonEvent::PluginsChanged, "plugins_changed"),
+            (DaemonEvent::HotkeysChanged, "hotkeys_changed"),
+        ];
+
+        for (event, expected_type) in cases {
+            let json = serde_json::to_value(&event).unwrap();
+            assert_eq!(json["type"], expected_type);
+            assert_eq!(json.as_object().unwrap().len(), 1, "should only have type field");
+        }
+    }
+
+    #[test]
+    fn plugin_config_changed_serializes_plugin_id() {
+        let event = DaemonEvent::PluginConfigChanged {
+            id: "plugin-a".into(),
+        };
         let json = serde_json::to_value(&event).unwrap();
-        assert_eq!(json["type"], "plugins_changed");
-        assert_eq!(json.as_object().unwrap().len(), 1);
+        assert_eq!(json, serde_json::json!({"type": "plugin_config_changed", "id": "plugin-a"}));
+    }
+
+    #[test]
+    fn daemon_restarted_serializes_plugin_id_and_pid() {
+        let event = DaemonEvent::DaemonRestarted {
+            id: "plugin-a".into(),
+            pid: 4242,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json, serde_json::json!({"type": "daemon_restarted", "id": "plugin-a", "pid": 4242}));
+    }
+
+    #[test]
+    fn install_progress_serializes_stage_and_percent() {
+        let event = DaemonEvent::InstallProgress {
+            id: "plugin-a".into(),
+            stage: "Receiving objects".into(),
+            percent: 45,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"type": "install_progress", "id": "plugin-a", "stage": "Receiving objects", "percent": 45})
+        );
     }
 }
 
-#[cfg(all(test, feature = "dev"))]
+#[cfg(test)]
 mod dev_tests {
     use super::*;
 
@@ -67,6 +123,7 @@ mod dev_tests {
                     id: "plugin-a".into(),
                     name: "Plugin A".into(),
                     path: "/path/a".into(),
+                    installed_not_linked: false,
                 }],
                 1,
             ),
@@ -76,11 +133,13 @@ mod dev_tests {
                         id: "plugin-a".into(),
                         name: "Plugin A".into(),
                         path: "/path/a".into(),
+                    installed_not_linked: false,
                     },
                     DiscoveredPluginInfo {
                         id: "plugin-b".into(),
                         name: "Plugin B".into(),
                         path: "/path/b".into(),
+                        installed_not_linked: true,
                     },
                 ],
                 2,
@@ -90,6 +149,7 @@ mod dev_tests {
         for (plugins, expected_count) in cases {
             let event = DaemonEvent::DiscoveryComplete {
                 plugins: plugins.clone(),
+                errors: vec![],
             };
             let json = serde_json::to_value(&event).unwrap();
 
@@ -100,10 +160,33 @@ mod dev_tests {
                 assert_eq!(json["plugins"][i]["id"], plugin.id);
                 assert_eq!(json["plugins"][i]["name"], plugin.name);
                 assert_eq!(json["plugins"][i]["path"], plugin.path);
+                assert_eq!(json["plugins"][i]["installed_not_linked"], plugin.installed_not_linked);
             }
+            assert_eq!(json["errors"], serde_json::json!([]));
         }
     }
 
+    #[test]
+    fn discovery_complete_serializes_errors() {
+        let event = DaemonEvent::DiscoveryComplete {
+            plugins: vec![],
+            errors: vec![DiscoveryErrorInfo {
+                path: "/src/broken".into(),
+                reason: "Invalid plugin.toml: expected `]`".into(),
+            }],
+        };
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "discovery_complete",
+                "plugins": [],
+                "errors": [{"path": "/src/broken", "reason": "Invalid plugin.toml: expected `]`"}]
+            })
+        );
+    }
+
     #[test]
     fn plugin_info_fields_serialize_correctly() {
         let cases: Vec<(&str, &str, &str)> = vec![
@@ -120,6 +203,7 @@ mod dev_tests {
                 id: id.into(),
                 name: name.into(),
                 path: path.into(),
+                installed_not_linked: false,
             };
             let json = serde_json::to_value(&info).unwrap();
 
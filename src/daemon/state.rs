@@ -1,18 +1,86 @@
+use serde::Serialize;
+use std::path::PathBuf;
 use std::sync::RwLock;
+use std::time::{Duration, Instant, SystemTime};
 
-use super::DiscoveredPluginInfo;
+use super::{DiscoveredPluginInfo, DiscoveryErrorInfo};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DiscoveryStatus {
     Idle,
     Discovering,
     Complete,
 }
 
+pub const DISCOVERY_CACHE_TTL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RootStamp {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+}
+
+impl RootStamp {
+    pub fn read(path: PathBuf) -> Self {
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        Self { path, modified }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DiscoveryCache {
+    pub roots: Vec<RootStamp>,
+    pub cached_at: Instant,
+}
+
+impl DiscoveryCache {
+    pub fn is_valid(&self, roots: &[RootStamp], now: Instant) -> bool {
+        self.roots == roots && now.saturating_duration_since(self.cached_at) < DISCOVERY_CACHE_TTL
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct DiscoveryState {
     pub status: DiscoveryStatus,
     pub plugins: Vec<DiscoveredPluginInfo>,
+    pub errors: Vec<DiscoveryErrorInfo>,
+    pub generation: u64,
+    pub cache: Option<DiscoveryCache>,
+}
+
+impl DiscoveryState {
+    pub fn begin(&mut self) -> u64 {
+        self.generation += 1;
+        self.status = DiscoveryStatus::Discovering;
+        self.generation
+    }
+
+    pub fn finish(
+        &mut self,
+        generation: u64,
+        plugins: Vec<DiscoveredPluginInfo>,
+        errors: Vec<DiscoveryErrorInfo>,
+    ) -> bool {
+        if generation != self.generation {
+            return false;
+        }
+        self.status = DiscoveryStatus::Complete;
+        self.plugins = plugins;
+        self.errors = errors;
+        true
+    }
+
+    pub fn cached_result(
+        &self,
+        roots: &[RootStamp],
+        now: Instant,
+    ) -> Option<(Vec<DiscoveredPluginInfo>, Vec<DiscoveryErrorInfo>)> {
+        let cache = self.cache.as_ref()?;
+        cache
+            .is_valid(roots, now)
+            .then(|| (self.plugins.clone(), self.errors.clone()))
+    }
 }
 
 impl Default for DiscoveryState {
@@ -20,6 +88,9 @@ impl Default for DiscoveryState {
         Self {
             status: DiscoveryStatus::Idle,
             plugins: vec![],
+            errors: vec![],
+            generation: 0,
+            cache: None,
         }
     }
 }
@@ -48,6 +119,95 @@ mod tests {
     use std::sync::Arc;
     use std::thread;
 
+    fn plugin(id: &str) -> DiscoveredPluginInfo {
+        DiscoveredPluginInfo {
+            id: id.into(),
+            name: id.into(),
+            path: format!("/{}", id),
+            installed_not_linked: false,
+        }
+    }
+
+    #[test]
+    fn stale_discovery_results_are_ignored() {
+        let mut state = DiscoveryState::default();
+
+        let older = state.begin();
+        let newer = state.begin();
+
+        assert!(state.finish(newer, vec![plugin("fresh")], vec![]));
+        assert!(!state.finish(older, vec![plugin("stale")], vec![]));
+
+        assert_eq!(state.status, DiscoveryStatus::Complete);
+        assert_eq!(state.plugins.len(), 1);
+        assert_eq!(state.plugins[0].id, "fresh");
+    }
+
+    #[test]
+    fn finishing_older_run_keeps_newer_run_in_progress() {
+        let mut state = DiscoveryState::default();
+
+        let older = state.begin();
+        let _newer = state.begin();
+
+        assert!(!state.finish(older, vec![plugin("stale")], vec![]));
+        assert_eq!(state.status, DiscoveryStatus::Discovering);
+        assert!(state.plugins.is_empty());
+    }
+
+    #[test]
+    fn discovery_cache_validity_cases() {
+        let cached_at = Instant::now();
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+        let t1 = t0 + Duration::from_secs(5);
+        let stamp = |path: &str, modified| RootStamp { path: PathBuf::from(path), modified };
+        let cache = DiscoveryCache {
+            roots: vec![stamp("/code", Some(t0)), stamp("/plugins", Some(t0))],
+            cached_at,
+        };
+
+        let cases = [
+            (vec![stamp("/code", Some(t0)), stamp("/plugins", Some(t0))], Duration::ZERO, true),
+            (vec![stamp("/code", Some(t0)), stamp("/plugins", Some(t0))], Duration::from_secs(30), true),
+            (vec![stamp("/code", Some(t1)), stamp("/plugins", Some(t0))], Duration::ZERO, false),
+            (vec![stamp("/code", Some(t0)), stamp("/plugins", Some(t1))], Duration::ZERO, false),
+            (vec![stamp("/code", None), stamp("/plugins", Some(t0))], Duration::ZERO, false),
+            (vec![stamp("/code", Some(t0))], Duration::ZERO, false),
+            (vec![stamp("/code", Some(t0)), stamp("/plugins", Some(t0)), stamp("/src", Some(t0))], Duration::ZERO, false),
+            (vec![stamp("/code", Some(t0)), stamp("/plugins", Some(t0))], DISCOVERY_CACHE_TTL, false),
+        ];
+
+        for (roots, elapsed, expected) in cases {
+            assert_eq!(cache.is_valid(&roots, cached_at + elapsed), expected, "roots: {:?} elapsed: {:?}", roots, elapsed);
+        }
+    }
+
+    #[test]
+    fn cached_result_reuses_last_plugins_only_when_valid() {
+        let mut state = DiscoveryState::default();
+        let roots = vec![RootStamp { path: PathBuf::from("/code"), modified: Some(SystemTime::UNIX_EPOCH) }];
+        let now = Instant::now();
+        assert!(state.cached_result(&roots, now).is_none());
+
+        let generation = state.begin();
+        state.finish(generation, vec![plugin("cached")], vec![]);
+        state.cache = Some(DiscoveryCache { roots: roots.clone(), cached_at: now });
+
+        let (plugins, _) = state.cached_result(&roots, now).unwrap();
+        assert_eq!(plugins[0].id, "cached");
+
+        let changed = vec![RootStamp { path: PathBuf::from("/code"), modified: None }];
+        assert!(state.cached_result(&changed, now).is_none());
+    }
+
+    #[test]
+    fn root_stamp_reads_directory_mtime() {
+        let tmp = tempfile::TempDir::new().unwrap();
+
+        assert!(RootStamp::read(tmp.path().to_path_buf()).modified.is_some());
+        assert!(RootStamp::read(tmp.path().join("missing")).modified.is_none());
+    }
+
     #[test]
     fn discovery_status_equality() {
         let cases = [
@@ -122,6 +282,7 @@ mod tests {
                 id: "test".into(),
                 name: "Test".into(),
                 path: "/test".into(),
+                installed_not_linked: false,
             });
         });
 
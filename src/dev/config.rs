@@ -1,11 +1,13 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DevConfig {
     #[serde(default)]
     pub search_paths: Vec<PathBuf>,
+    #[serde(default)]
+    pub auto_link_discovered: bool,
 }
 
 impl DevConfig {
@@ -23,7 +25,15 @@ impl DevConfig {
         let mut paths = Vec::new();
 
         if !self.search_paths.is_empty() {
-            paths.extend(self.search_paths.clone());
+            let home = dirs::home_dir();
+            for configured in &self.search_paths {
+                let path = expand_home(configured, home.as_deref());
+                if path.is_dir() {
+                    paths.push(path);
+                } else {
+                    log::warn!("Skipping dev search path {:?}: not a directory", path);
+                }
+            }
         } else {
             if let Some(home) = dirs::home_dir() {
                 for name in Self::common_dev_dirs() {
@@ -71,3 +81,65 @@ impl DevConfig {
         ]
     }
 }
+
+fn expand_home(path: &Path, home: Option<&Path>) -> PathBuf {
+    let Some(home) = home else {
+        return path.to_path_buf();
+    };
+    let Some(text) = path.to_str() else {
+        return path.to_path_buf();
+    };
+
+    for prefix in ["~", "$HOME"] {
+        if text == prefix {
+            return home.to_path_buf();
+        }
+        if let Some(rest) = text.strip_prefix(prefix).and_then(|r| r.strip_prefix('/')) {
+            return home.join(rest);
+        }
+    }
+    path.to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_home_cases() {
+        let home = Path::new("/home/user");
+        let cases = [
+            ("~", "/home/user"),
+            ("~/code", "/home/user/code"),
+            ("~/code/plugins", "/home/user/code/plugins"),
+            ("$HOME", "/home/user"),
+            ("$HOME/code", "/home/user/code"),
+            ("/opt/code", "/opt/code"),
+            ("relative/code", "relative/code"),
+            ("~other/code", "~other/code"),
+            ("$HOMEDIR/code", "$HOMEDIR/code"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(expand_home(Path::new(input), Some(home)), PathBuf::from(expected), "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn expand_home_without_home_keeps_path() {
+        assert_eq!(expand_home(Path::new("~/code"), None), PathBuf::from("~/code"));
+    }
+
+    #[test]
+    fn effective_search_paths_skips_missing_directories() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let existing = tmp.path().join("code");
+        std::fs::create_dir_all(&existing).unwrap();
+        let config = DevConfig {
+            search_paths: vec![existing.clone(), tmp.path().join("missing")],
+            ..Default::default()
+        };
+
+        assert_eq!(config.effective_search_paths(), vec![existing.canonicalize().unwrap()]);
+    }
+}
@@ -14,9 +14,32 @@ pub struct DiscoveredPlugin {
     pub installed_not_linked: bool,
 }
 
-pub fn discover_plugins(config: &DevConfig, plugins_dir: &Path) -> Vec<DiscoveredPlugin> {
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoveryError {
+    pub path: String,
+    pub reason: String,
+}
+
+impl DiscoveryError {
+    fn new(path: &Path, reason: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string_lossy().to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Discovery {
+    pub plugins: Vec<DiscoveredPlugin>,
+    pub errors: Vec<DiscoveryError>,
+}
+
+pub fn discover_plugins(config: &DevConfig, plugins_dir: &Path) -> Discovery {
     let search_paths = config.effective_search_paths();
-    let plugin_dirs = find_plugin_dirs(&search_paths);
+    let mut errors = Vec::new();
+    let plugin_dirs = find_plugin_dirs(&search_paths, &mut errors);
+    let linked_targets = collect_link_targets(plugins_dir);
 
     let mut seen_paths = HashSet::new();
     let mut discovered = Vec::new();
@@ -27,86 +50,140 @@ pub fn discover_plugins(config: &DevConfig, plugins_dir: &Path) -> Vec<Discovere
             Err(_) => dir.to_path_buf(),
         };
 
+        let linked_elsewhere = linked_targets.contains(&abs_path);
         if !seen_paths.insert(abs_path) {
             continue;
         }
 
-        if let Some(mut p) = try_parse_plugin_dir(&dir) {
-            let (linked, installed) = check_install_status(plugins_dir, &p.id, &p.path);
-            p.already_linked = linked;
-            p.installed_not_linked = installed;
-            if !p.already_linked {
-                discovered.push(p);
+        match try_parse_plugin_dir(&dir) {
+            Ok(Some(mut p)) => {
+                let (linked, installed) = check_install_status(plugins_dir, &p.id, &p.path);
+                p.already_linked = linked || linked_elsewhere;
+                p.installed_not_linked = installed;
+                if !p.already_linked {
+                    discovered.push(p);
+                }
             }
+            Ok(None) => {}
+            Err(reason) => errors.push(DiscoveryError::new(&dir, reason)),
         }
     }
 
     discovered.sort_by(|a, b| a.name.cmp(&b.name));
-    discovered
+    Discovery {
+        plugins: discovered,
+        errors,
+    }
 }
 
-fn find_plugin_dirs(search_paths: &[PathBuf]) -> Vec<PathBuf> {
-    let mut plugins = Vec::new();
+fn collect_link_targets(plugins_dir: &Path) -> HashSet<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(plugins_dir) else {
+        return HashSet::new();
+    };
 
-    for search_path in search_paths {
-        if !search_path.exists() {
-            continue;
-        }
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_symlink()))
+        .filter_map(|entry| entry.path().canonicalize().ok())
+        .collect()
+}
 
-        let mut it = WalkDir::new(search_path)
-            .max_depth(5)
-            .follow_links(false)
+fn find_plugin_dirs(search_paths: &[PathBuf], errors: &mut Vec<DiscoveryError>) -> Vec<PathBuf> {
+    let walks: Vec<(Vec<PathBuf>, Vec<DiscoveryError>)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = search_paths
+            .iter()
+            .map(|search_path| scope.spawn(move || walk_search_path(search_path)))
+            .collect();
+        handles
             .into_iter()
-            .filter_entry(|e| {
-                if e.depth() == 0 {
-                    return true;
-                }
-                let name = e.file_name().to_string_lossy();
-                !name.starts_with('.')
-                    && name != "node_modules"
-                    && name != "target"
-                    && name != "vendor"
-            });
-
-        while let Some(entry) = it.next() {
-            let Ok(entry) = entry else { continue };
-            let path = entry.path();
-
-            if path.is_dir() && path.join("plugin.toml").exists() {
-                plugins.push(path.to_path_buf());
-                it.skip_current_dir();
+            .zip(search_paths)
+            .map(|(handle, search_path)| {
+                handle.join().unwrap_or_else(|_| {
+                    (Vec::new(), vec![DiscoveryError::new(search_path, "Directory walk panicked")])
+                })
+            })
+            .collect()
+    });
+
+    let mut plugins = Vec::new();
+    for (found, walk_errors) in walks {
+        plugins.extend(found);
+        errors.extend(walk_errors);
+    }
+    plugins
+}
+
+fn walk_search_path(search_path: &Path) -> (Vec<PathBuf>, Vec<DiscoveryError>) {
+    let mut plugins = Vec::new();
+    let mut errors = Vec::new();
+
+    if !search_path.exists() {
+        errors.push(DiscoveryError::new(search_path, "Search path does not exist"));
+        return (plugins, errors);
+    }
+
+    let mut it = WalkDir::new(search_path)
+        .max_depth(5)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| {
+            if e.depth() == 0 {
+                return true;
+            }
+            let name = e.file_name().to_string_lossy();
+            !name.starts_with('.')
+                && name != "node_modules"
+                && name != "target"
+                && name != "vendor"
+        });
+
+    while let Some(entry) = it.next() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                let path = e.path().unwrap_or(search_path);
+                errors.push(DiscoveryError::new(path, e.to_string()));
+                continue;
             }
+        };
+        let path = entry.path();
+
+        if path.is_dir() && path.join("plugin.toml").exists() {
+            plugins.push(path.to_path_buf());
+            it.skip_current_dir();
         }
     }
 
-    plugins
+    (plugins, errors)
 }
 
-fn try_parse_plugin_dir(path: &Path) -> Option<DiscoveredPlugin> {
+fn try_parse_plugin_dir(path: &Path) -> Result<Option<DiscoveredPlugin>, String> {
     if !path.is_dir() {
-        return None;
+        return Ok(None);
     }
 
     let plugin_toml = path.join("plugin.toml");
     if !plugin_toml.exists() {
-        return None;
+        return Ok(None);
     }
 
-    let id = path.file_name()?.to_string_lossy().to_string();
+    let Some(id) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+        return Ok(None);
+    };
 
     if id == "plugin-template" {
-        return None;
+        return Ok(None);
     }
 
-    let name = read_plugin_name(&plugin_toml).unwrap_or_else(|| id.clone());
+    let name = read_plugin_name(&plugin_toml)?;
 
-    Some(DiscoveredPlugin {
+    Ok(Some(DiscoveredPlugin {
         id,
         name,
         path: path.to_string_lossy().to_string(),
         already_linked: false,
         installed_not_linked: false,
-    })
+    }))
 }
 
 #[derive(Deserialize)]
@@ -119,18 +196,17 @@ struct MinimalPluginInfo {
     name: String,
 }
 
-fn read_plugin_name(toml_path: &Path) -> Option<String> {
-    let content = std::fs::read_to_string(toml_path).ok()?;
+fn read_plugin_name(toml_path: &Path) -> Result<String, String> {
+    let content = std::fs::read_to_string(toml_path)
+        .map_err(|e| format!("Failed to read plugin.toml: {}", e))?;
 
     if let Ok(manifest) = toml::from_str::<crate::plugins::PluginManifest>(&content) {
-        return Some(manifest.plugin.name);
-    }
-
-    if let Ok(minimal) = toml::from_str::<MinimalManifest>(&content) {
-        return Some(minimal.plugin.name);
+        return Ok(manifest.plugin.name);
     }
 
-    None
+    toml::from_str::<MinimalManifest>(&content)
+        .map(|minimal| minimal.plugin.name)
+        .map_err(|e| format!("Invalid plugin.toml: {}", e.message()))
 }
 
 fn check_install_status(plugins_dir: &Path, id: &str, target: &str) -> (bool, bool) {
@@ -184,7 +260,7 @@ items = []
         fs::create_dir(&plugin_dir).unwrap();
         create_plugin_toml(&plugin_dir);
 
-        let found = find_plugin_dirs(&[tmp.path().to_path_buf()]);
+        let found = find_plugin_dirs(&[tmp.path().to_path_buf()], &mut Vec::new());
         assert_eq!(found.len(), 1);
         assert_eq!(found[0], plugin_dir);
     }
@@ -197,7 +273,7 @@ items = []
         fs::create_dir_all(&plugin_dir).unwrap();
         create_plugin_toml(&plugin_dir);
 
-        let found = find_plugin_dirs(&[tmp.path().to_path_buf()]);
+        let found = find_plugin_dirs(&[tmp.path().to_path_buf()], &mut Vec::new());
         assert_eq!(found.len(), 1);
         assert_eq!(found[0], plugin_dir);
     }
@@ -212,7 +288,7 @@ items = []
         create_plugin_toml(&p1);
         create_plugin_toml(&p2);
 
-        let found = find_plugin_dirs(&[tmp.path().to_path_buf()]);
+        let found = find_plugin_dirs(&[tmp.path().to_path_buf()], &mut Vec::new());
         assert_eq!(found.len(), 2);
     }
 
@@ -223,7 +299,7 @@ items = []
         fs::create_dir_all(&hidden).unwrap();
         create_plugin_toml(&hidden);
 
-        let found = find_plugin_dirs(&[tmp.path().to_path_buf()]);
+        let found = find_plugin_dirs(&[tmp.path().to_path_buf()], &mut Vec::new());
         assert_eq!(found.len(), 0);
     }
 
@@ -234,7 +310,7 @@ items = []
         fs::create_dir_all(&nm).unwrap();
         create_plugin_toml(&nm);
 
-        let found = find_plugin_dirs(&[tmp.path().to_path_buf()]);
+        let found = find_plugin_dirs(&[tmp.path().to_path_buf()], &mut Vec::new());
         assert_eq!(found.len(), 0);
     }
 
@@ -245,7 +321,7 @@ items = []
         fs::create_dir_all(&target).unwrap();
         create_plugin_toml(&target);
 
-        let found = find_plugin_dirs(&[tmp.path().to_path_buf()]);
+        let found = find_plugin_dirs(&[tmp.path().to_path_buf()], &mut Vec::new());
         assert_eq!(found.len(), 0);
     }
 
@@ -256,7 +332,7 @@ items = []
         fs::create_dir_all(&deep).unwrap();
         create_plugin_toml(&deep);
 
-        let found = find_plugin_dirs(&[tmp.path().to_path_buf()]);
+        let found = find_plugin_dirs(&[tmp.path().to_path_buf()], &mut Vec::new());
         assert_eq!(found.len(), 0);
     }
 
@@ -267,7 +343,7 @@ items = []
         fs::create_dir_all(&plugin_dir).unwrap();
         create_plugin_toml(&plugin_dir);
 
-        let found = find_plugin_dirs(&[tmp.path().to_path_buf()]);
+        let found = find_plugin_dirs(&[tmp.path().to_path_buf()], &mut Vec::new());
         assert_eq!(found.len(), 1, "Should find plugin at depth 3");
         assert_eq!(found[0], plugin_dir);
     }
@@ -279,10 +355,61 @@ items = []
         fs::create_dir_all(&plugin_dir).unwrap();
         create_plugin_toml(&plugin_dir);
 
-        let found = find_plugin_dirs(&[tmp.path().to_path_buf()]);
+        let found = find_plugin_dirs(&[tmp.path().to_path_buf()], &mut Vec::new());
         assert_eq!(found.len(), 1, "Should find plugin at depth 5");
     }
 
+    #[test]
+    fn parallel_walk_matches_sequential_walk() {
+        let tmp = TempDir::new().unwrap();
+        let roots: Vec<PathBuf> = ["code", "work", "src", "empty"]
+            .iter()
+            .map(|name| tmp.path().join(name))
+            .collect();
+        let plugins = [
+            "code/plugin-a",
+            "code/group/plugin-b",
+            "code/plugin-a/nested-plugin",
+            "code/node_modules/ignored",
+            "work/a/b/c/plugin-c",
+            "work/.hidden/plugin-d",
+            "work/vendor/plugin-e",
+            "src/plugin-f",
+            "src/target/plugin-g",
+        ];
+        for root in &roots {
+            fs::create_dir_all(root).unwrap();
+        }
+        for plugin in plugins {
+            let dir = tmp.path().join(plugin);
+            fs::create_dir_all(&dir).unwrap();
+            create_plugin_toml(&dir);
+        }
+        let mut search_paths = roots.clone();
+        search_paths.push(tmp.path().join("missing"));
+
+        let mut sequential_errors = Vec::new();
+        let mut sequential = Vec::new();
+        for root in &search_paths {
+            let (found, errors) = walk_search_path(root);
+            sequential.extend(found);
+            sequential_errors.extend(errors);
+        }
+        let mut parallel_errors = Vec::new();
+        let parallel = find_plugin_dirs(&search_paths, &mut parallel_errors);
+
+        assert_eq!(parallel, sequential);
+        assert_eq!(
+            parallel_errors.iter().map(|e| &e.path).collect::<Vec<_>>(),
+            sequential_errors.iter().map(|e| &e.path).collect::<Vec<_>>()
+        );
+        let expected: HashSet<PathBuf> = ["code/plugin-a", "code/group/plugin-b", "work/a/b/c/plugin-c", "src/plugin-f"]
+            .iter()
+            .map(|p| tmp.path().join(p))
+            .collect();
+        assert_eq!(parallel.into_iter().collect::<HashSet<_>>(), expected);
+    }
+
     #[test]
     fn search_paths_can_overlap_and_are_deduplicated() {
         let tmp = TempDir::new().unwrap();
@@ -295,9 +422,10 @@ items = []
                 tmp.path().to_path_buf(),
                 tmp.path().join("sub"),
             ],
+            ..Default::default()
         };
 
-        let discovered = discover_plugins(&config, tmp.path());
+        let discovered = discover_plugins(&config, tmp.path()).plugins;
         assert_eq!(discovered.len(), 1, "Should deduplicate plugin found from multiple search roots");
     }
 
@@ -318,10 +446,123 @@ version = "0.1.0"
 
         let config = DevConfig {
             search_paths: vec![tmp.path().to_path_buf()],
+            ..Default::default()
         };
 
-        let discovered = discover_plugins(&config, tmp.path());
+        let discovered = discover_plugins(&config, tmp.path()).plugins;
         assert_eq!(discovered.len(), 1, "Should find it even if TOML is minimal");
         assert_eq!(discovered[0].name, "Minimal");
     }
+
+    #[test]
+    fn preserves_installed_not_linked_for_installed_copy() {
+        let tmp = TempDir::new().unwrap();
+        let source = tmp.path().join("src");
+        let installed_source = source.join("installed-plugin");
+        let fresh_source = source.join("fresh-plugin");
+        fs::create_dir_all(&installed_source).unwrap();
+        fs::create_dir_all(&fresh_source).unwrap();
+        create_plugin_toml(&installed_source);
+        create_plugin_toml(&fresh_source);
+
+        let plugins_dir = tmp.path().join("plugins");
+        fs::create_dir_all(plugins_dir.join("installed-plugin")).unwrap();
+
+        let config = DevConfig {
+            search_paths: vec![source],
+            ..Default::default()
+        };
+
+        let discovered = discover_plugins(&config, &plugins_dir).plugins;
+        let mut flags: Vec<(&str, bool)> = discovered
+            .iter()
+            .map(|p| (p.id.as_str(), p.installed_not_linked))
+            .collect();
+        flags.sort();
+        assert_eq!(flags, vec![("fresh-plugin", false), ("installed-plugin", true)]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn omits_plugins_already_linked_to_source() {
+        let tmp = TempDir::new().unwrap();
+        let source = tmp.path().join("src");
+        let plugin_source = source.join("linked-plugin");
+        fs::create_dir_all(&plugin_source).unwrap();
+        create_plugin_toml(&plugin_source);
+
+        let plugins_dir = tmp.path().join("plugins");
+        fs::create_dir_all(&plugins_dir).unwrap();
+        std::os::unix::fs::symlink(&plugin_source, plugins_dir.join("linked-plugin")).unwrap();
+
+        let config = DevConfig {
+            search_paths: vec![source],
+            ..Default::default()
+        };
+
+        assert!(discover_plugins(&config, &plugins_dir).plugins.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hides_plugins_linked_under_a_different_id() {
+        let tmp = TempDir::new().unwrap();
+        let source = tmp.path().join("src");
+        let plugin_source = source.join("my-plugin");
+        let other_source = source.join("other-plugin");
+        fs::create_dir_all(&plugin_source).unwrap();
+        fs::create_dir_all(&other_source).unwrap();
+        create_plugin_toml(&plugin_source);
+        create_plugin_toml(&other_source);
+
+        let plugins_dir = tmp.path().join("plugins");
+        fs::create_dir_all(&plugins_dir).unwrap();
+        std::os::unix::fs::symlink(&plugin_source, plugins_dir.join("renamed-plugin")).unwrap();
+
+        let config = DevConfig {
+            search_paths: vec![source],
+            ..Default::default()
+        };
+
+        let ids: Vec<String> = discover_plugins(&config, &plugins_dir)
+            .plugins
+            .into_iter()
+            .map(|p| p.id)
+            .collect();
+        assert_eq!(ids, vec!["other-plugin"]);
+    }
+
+    #[test]
+    fn reports_malformed_manifest_as_error() {
+        let tmp = TempDir::new().unwrap();
+        let good = tmp.path().join("good-plugin");
+        let broken = tmp.path().join("broken-plugin");
+        fs::create_dir_all(&good).unwrap();
+        fs::create_dir_all(&broken).unwrap();
+        create_plugin_toml(&good);
+        fs::write(broken.join("plugin.toml"), "[plugin\nname = ").unwrap();
+
+        let config = DevConfig {
+            search_paths: vec![tmp.path().to_path_buf()],
+            ..Default::default()
+        };
+
+        let discovery = discover_plugins(&config, &tmp.path().join("plugins"));
+        let ids: Vec<&str> = discovery.plugins.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["good-plugin"]);
+        assert_eq!(discovery.errors.len(), 1);
+        assert_eq!(discovery.errors[0].path, broken.to_string_lossy());
+        assert!(discovery.errors[0].reason.starts_with("Invalid plugin.toml"), "reason: {}", discovery.errors[0].reason);
+    }
+
+    #[test]
+    fn reports_missing_search_path_as_error() {
+        let tmp = TempDir::new().unwrap();
+        let missing = tmp.path().join("missing");
+
+        let mut errors = Vec::new();
+        assert!(find_plugin_dirs(std::slice::from_ref(&missing), &mut errors).is_empty());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, missing.to_string_lossy());
+    }
 }
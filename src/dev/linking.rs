@@ -1,6 +1,56 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
 use std::path::Path;
 
+use super::discovery::DiscoveredPlugin;
+
+#[derive(Debug)]
+pub enum LinkError {
+    AlreadyLinked,
+    SourceMissing,
+    NoManifest,
+    InvalidId,
+    NotInstalled,
+    IdMismatch { source_id: String, expected: String },
+    NotLinked,
+    NotSymlink,
+    Io { context: &'static str, source: std::io::Error },
+}
+
+impl LinkError {
+    fn io(context: &'static str) -> impl FnOnce(std::io::Error) -> Self {
+        move |source| Self::Io { context, source }
+    }
+}
+
+impl fmt::Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlreadyLinked => f.write_str("Already linked"),
+            Self::SourceMissing => f.write_str("Source path does not exist"),
+            Self::NoManifest => f.write_str("No plugin.toml found in source"),
+            Self::InvalidId => f.write_str("Invalid plugin ID"),
+            Self::NotInstalled => f.write_str("Plugin not installed"),
+            Self::IdMismatch { source_id, expected } => {
+                write!(f, "Plugin id mismatch: source is {:?}, expected {:?}", source_id, expected)
+            }
+            Self::NotLinked => f.write_str("Plugin not found"),
+            Self::NotSymlink => f.write_str("Not a symlink - use uninstall instead"),
+            Self::Io { context, source } => write!(f, "{}: {}", context, source),
+        }
+    }
+}
+
+impl std::error::Error for LinkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct LinkedPlugin {
     pub id: String,
@@ -14,13 +64,19 @@ pub struct LinkRequest {
     pub path: String,
 }
 
-pub fn list_linked_plugins(plugins_dir: &Path) -> Result<Vec<LinkedPlugin>, String> {
+#[derive(Debug, Clone, Deserialize)]
+pub struct LinkExistingRequest {
+    pub id: String,
+    pub path: String,
+}
+
+pub fn list_linked_plugins(plugins_dir: &Path) -> Result<Vec<LinkedPlugin>, LinkError> {
     if !plugins_dir.exists() {
         return Ok(vec![]);
     }
 
     let entries = std::fs::read_dir(plugins_dir)
-        .map_err(|e| format!("Failed to read plugins dir: {}", e))?;
+        .map_err(LinkError::io("Failed to read plugins dir"))?;
 
     let mut plugins = Vec::new();
     for entry in entries.filter_map(|e| e.ok()) {
@@ -64,60 +120,110 @@ pub fn list_linked_plugins(plugins_dir: &Path) -> Result<Vec<LinkedPlugin>, Stri
     Ok(plugins)
 }
 
-pub fn create_link(source: &Path, plugins_dir: &Path) -> Result<String, String> {
+pub fn create_link(source: &Path, plugins_dir: &Path) -> Result<String, LinkError> {
     if !source.exists() {
-        return Err("Source path does not exist".to_string());
+        return Err(LinkError::SourceMissing);
     }
 
     if !source.join("plugin.toml").exists() {
-        return Err("No plugin.toml found in source".to_string());
+        return Err(LinkError::NoManifest);
     }
 
     let plugin_id = source
         .file_name()
-        .ok_or("Invalid path")?
+        .ok_or(LinkError::InvalidId)?
         .to_string_lossy()
         .to_string();
 
     let link_path = plugins_dir.join(&plugin_id);
 
     backup_existing_if_not_symlink(&link_path)?;
-    create_symlink(source, &link_path)
-        .map_err(|e| format!("Failed to create symlink: {}", e))?;
+    create_symlink(source, &link_path).map_err(LinkError::io("Failed to create symlink"))?;
 
     log::info!("Created plugin link: {} -> {:?}", plugin_id, source);
     Ok(plugin_id)
 }
 
-pub fn remove_link(id: &str, plugins_dir: &Path) -> Result<(), String> {
+pub fn auto_link(plugins: &[DiscoveredPlugin], plugins_dir: &Path) -> Vec<String> {
+    link_each(plugins, |source| create_link(source, plugins_dir))
+}
+
+fn link_each<F>(plugins: &[DiscoveredPlugin], mut link: F) -> Vec<String>
+where
+    F: FnMut(&Path) -> Result<String, LinkError>,
+{
+    let mut seen = HashSet::new();
+    let mut linked = Vec::new();
+
+    for plugin in plugins {
+        if plugin.already_linked || !seen.insert(plugin.id.as_str()) {
+            continue;
+        }
+        match link(Path::new(&plugin.path)) {
+            Ok(id) => {
+                log::info!("Auto-linked discovered plugin {} from {}", id, plugin.path);
+                linked.push(id);
+            }
+            Err(e) => log::warn!("Failed to auto-link {} from {}: {}", plugin.id, plugin.path, e),
+        }
+    }
+
+    linked
+}
+
+pub fn link_existing(id: &str, source: &Path, plugins_dir: &Path) -> Result<(), LinkError> {
+    if !crate::paths::is_safe_path_component(id) {
+        return Err(LinkError::InvalidId);
+    }
+
+    let Ok(metadata) = std::fs::symlink_metadata(plugins_dir.join(id)) else {
+        return Err(LinkError::NotInstalled);
+    };
+    if metadata.file_type().is_symlink() {
+        return Err(LinkError::AlreadyLinked);
+    }
+
+    let source_id = source.file_name().map(|name| name.to_string_lossy().to_string());
+    if source_id.as_deref() != Some(id) {
+        return Err(LinkError::IdMismatch {
+            source_id: source_id.unwrap_or_default(),
+            expected: id.to_string(),
+        });
+    }
+
+    create_link(source, plugins_dir).map(|_| ())
+}
+
+pub fn remove_link(id: &str, plugins_dir: &Path) -> Result<(), LinkError> {
     let link_path = plugins_dir.join(id);
 
     remove_symlink(&link_path)?;
 
-    if let Err(e) = restore_from_backup(&link_path) {
-        log::warn!("No backup to restore for {}: {}", id, e);
+    match restore_from_backup(&link_path) {
+        Ok(true) => {}
+        Ok(false) => log::warn!("No backup to restore for {}", id),
+        Err(e) => log::warn!("Failed to restore backup for {}: {}", id, e),
     }
 
     log::info!("Unlinked plugin: {}", id);
     Ok(())
 }
 
-fn backup_existing_if_not_symlink(path: &Path) -> Result<(), String> {
+fn backup_existing_if_not_symlink(path: &Path) -> Result<(), LinkError> {
     let Ok(metadata) = std::fs::symlink_metadata(path) else {
         return Ok(());
     };
 
     if metadata.file_type().is_symlink() {
-        return Err("Already linked".to_string());
+        return Err(LinkError::AlreadyLinked);
     }
 
     let backup_path = path.with_extension("backup");
     if backup_path.exists() {
-        std::fs::remove_dir_all(&backup_path)
-            .map_err(|e| format!("Failed to remove old backup: {}", e))?;
+        std::fs::remove_dir_all(&backup_path).map_err(LinkError::io("Failed to remove old backup"))?;
     }
 
-    std::fs::rename(path, &backup_path).map_err(|e| format!("Failed to backup existing: {}", e))
+    std::fs::rename(path, &backup_path).map_err(LinkError::io("Failed to backup existing"))
 }
 
 fn create_symlink(source: &Path, link: &Path) -> std::io::Result<()> {
@@ -131,23 +237,177 @@ fn create_symlink(source: &Path, link: &Path) -> std::io::Result<()> {
     }
 }
 
-fn remove_symlink(path: &Path) -> Result<(), String> {
+fn remove_symlink(path: &Path) -> Result<(), LinkError> {
     let Ok(metadata) = std::fs::symlink_metadata(path) else {
-        return Err("Plugin not found".to_string());
+        return Err(LinkError::NotLinked);
     };
 
     if !metadata.file_type().is_symlink() {
-        return Err("Not a symlink - use uninstall instead".to_string());
+        return Err(LinkError::NotSymlink);
     }
 
-    std::fs::remove_file(path).map_err(|e| format!("Failed to remove link: {}", e))
+    std::fs::remove_file(path).map_err(LinkError::io("Failed to remove link"))
 }
 
-fn restore_from_backup(path: &Path) -> Result<(), String> {
+fn restore_from_backup(path: &Path) -> Result<bool, LinkError> {
     let backup_path = path.with_extension("backup");
     if !backup_path.exists() {
-        return Err("No backup exists".to_string());
+        return Ok(false);
+    }
+
+    std::fs::rename(&backup_path, path).map_err(LinkError::io("Failed to restore backup"))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn create_plugin(dir: &Path, marker: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join("plugin.toml"), "[plugin]\nname = \"Test\"\n").unwrap();
+        fs::write(dir.join("marker"), marker).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn link_existing_backs_up_install_and_links_source() {
+        let tmp = TempDir::new().unwrap();
+        let plugins_dir = tmp.path().join("plugins");
+        let source = tmp.path().join("src").join("my-plugin");
+        create_plugin(&plugins_dir.join("my-plugin"), "installed");
+        create_plugin(&source, "checkout");
+
+        link_existing("my-plugin", &source, &plugins_dir).unwrap();
+
+        let link = plugins_dir.join("my-plugin");
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_to_string(link.join("marker")).unwrap(), "checkout");
+        assert_eq!(fs::read_to_string(plugins_dir.join("my-plugin.backup").join("marker")).unwrap(), "installed");
+    }
+
+    #[test]
+    fn link_existing_rejects_invalid_requests() {
+        let tmp = TempDir::new().unwrap();
+        let plugins_dir = tmp.path().join("plugins");
+        create_plugin(&plugins_dir.join("my-plugin"), "installed");
+        create_plugin(&tmp.path().join("src").join("other-plugin"), "checkout");
+        create_plugin(&tmp.path().join("src").join("not-installed"), "checkout");
+        fs::create_dir_all(tmp.path().join("src").join("empty").join("my-plugin")).unwrap();
+
+        let cases = [
+            ("my-plugin", tmp.path().join("src").join("other-plugin"), "Plugin id mismatch"),
+            ("not-installed", tmp.path().join("src").join("not-installed"), "Plugin not installed"),
+            ("../my-plugin", tmp.path().join("src").join("other-plugin"), "Invalid plugin ID"),
+            ("my-plugin", tmp.path().join("src").join("empty").join("my-plugin"), "No plugin.toml"),
+        ];
+
+        for (id, source, expected) in cases {
+            let err = link_existing(id, &source, &plugins_dir).unwrap_err().to_string();
+            assert!(err.starts_with(expected), "id: {} err: {}", id, err);
+            assert_eq!(
+                fs::read_to_string(plugins_dir.join("my-plugin").join("marker")).unwrap(),
+                "installed",
+                "install must be untouched for id: {}",
+                id
+            );
+        }
     }
 
-    std::fs::rename(&backup_path, path).map_err(|e| format!("Failed to restore backup: {}", e))
+    #[cfg(unix)]
+    #[test]
+    fn create_link_reports_typed_errors() {
+        let tmp = TempDir::new().unwrap();
+        let plugins_dir = tmp.path().join("plugins");
+        fs::create_dir_all(&plugins_dir).unwrap();
+        let source = tmp.path().join("src").join("my-plugin");
+        create_plugin(&source, "checkout");
+        let empty = tmp.path().join("src").join("empty");
+        fs::create_dir_all(&empty).unwrap();
+
+        assert!(matches!(create_link(&tmp.path().join("missing"), &plugins_dir), Err(LinkError::SourceMissing)));
+        assert!(matches!(create_link(&empty, &plugins_dir), Err(LinkError::NoManifest)));
+
+        create_link(&source, &plugins_dir).unwrap();
+        assert!(matches!(create_link(&source, &plugins_dir), Err(LinkError::AlreadyLinked)));
+    }
+
+    fn discovered(id: &str, path: &str, already_linked: bool) -> DiscoveredPlugin {
+        DiscoveredPlugin {
+            id: id.into(),
+            name: id.into(),
+            path: path.into(),
+            already_linked,
+            installed_not_linked: false,
+        }
+    }
+
+    #[test]
+    fn link_each_links_every_unlinked_plugin_once() {
+        let plugins = [
+            discovered("plugin-a", "/src/plugin-a", false),
+            discovered("plugin-b", "/src/plugin-b", true),
+            discovered("plugin-c", "/src/plugin-c", false),
+            discovered("plugin-a", "/other/plugin-a", false),
+            discovered("broken", "/src/broken", false),
+        ];
+        let mut calls = Vec::new();
+
+        let linked = link_each(&plugins, |source| {
+            calls.push(source.to_path_buf());
+            let id = source.file_name().unwrap().to_string_lossy().to_string();
+            if id == "broken" {
+                return Err(LinkError::NoManifest);
+            }
+            Ok(id)
+        });
+
+        assert_eq!(
+            calls,
+            vec![
+                Path::new("/src/plugin-a").to_path_buf(),
+                Path::new("/src/plugin-c").to_path_buf(),
+                Path::new("/src/broken").to_path_buf(),
+            ]
+        );
+        assert_eq!(linked, vec!["plugin-a", "plugin-c"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn auto_link_backs_up_installed_copies() {
+        let tmp = TempDir::new().unwrap();
+        let plugins_dir = tmp.path().join("plugins");
+        let installed = tmp.path().join("src").join("installed");
+        let fresh = tmp.path().join("src").join("fresh");
+        create_plugin(&plugins_dir.join("installed"), "store");
+        create_plugin(&installed, "checkout");
+        create_plugin(&fresh, "checkout");
+        let plugins = [
+            discovered("installed", &installed.to_string_lossy(), false),
+            discovered("fresh", &fresh.to_string_lossy(), false),
+        ];
+
+        let linked = auto_link(&plugins, &plugins_dir);
+
+        assert_eq!(linked, vec!["installed", "fresh"]);
+        for id in ["installed", "fresh"] {
+            let link = plugins_dir.join(id);
+            assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink(), "id: {}", id);
+            assert_eq!(fs::read_to_string(link.join("marker")).unwrap(), "checkout");
+        }
+        assert_eq!(fs::read_to_string(plugins_dir.join("installed.backup").join("marker")).unwrap(), "store");
+    }
+
+    #[test]
+    fn remove_link_reports_typed_errors() {
+        let tmp = TempDir::new().unwrap();
+        let plugins_dir = tmp.path().join("plugins");
+        create_plugin(&plugins_dir.join("installed"), "installed");
+
+        assert!(matches!(remove_link("missing", &plugins_dir), Err(LinkError::NotLinked)));
+        assert!(matches!(remove_link("installed", &plugins_dir), Err(LinkError::NotSymlink)));
+    }
 }
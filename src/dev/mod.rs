@@ -4,4 +4,45 @@ mod linking;
 
 pub use config::DevConfig;
 pub use discovery::discover_plugins;
-pub use linking::{create_link, list_linked_plugins, remove_link, LinkedPlugin, LinkRequest};
+pub use linking::{auto_link, create_link, link_existing, list_linked_plugins, remove_link, LinkError, LinkExistingRequest, LinkedPlugin, LinkRequest};
+
+const DEV_TOOLS_ENV: &str = "QOL_DEV_TOOLS";
+
+pub fn tools_enabled() -> bool {
+    let configured = crate::settings::AppSettings::load()
+        .map(|s| s.dev_tools_enabled)
+        .unwrap_or(false);
+    cfg!(feature = "dev") || resolve_tools_enabled(std::env::var(DEV_TOOLS_ENV).ok().as_deref(), configured)
+}
+
+fn resolve_tools_enabled(env_value: Option<&str>, configured: bool) -> bool {
+    match env_value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+        Some("1" | "true" | "yes" | "on") => true,
+        Some("0" | "false" | "no" | "off") => false,
+        _ => configured,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_tools_enabled_cases() {
+        let cases = [
+            (None, false, false),
+            (None, true, true),
+            (Some("1"), false, true),
+            (Some(" TRUE "), false, true),
+            (Some("on"), false, true),
+            (Some("0"), true, false),
+            (Some("false"), true, false),
+            (Some(""), true, true),
+            (Some("maybe"), false, false),
+        ];
+
+        for (env_value, configured, expected) in cases {
+            assert_eq!(resolve_tools_enabled(env_value, configured), expected, "env={:?} configured={}", env_value, configured);
+        }
+    }
+}
@@ -1,12 +1,21 @@
 pub mod plugin_store;
 pub mod task_runner;
 
+use crate::menu::router::EventRoute;
 use crate::plugins::MenuItem as PluginMenuItem;
 use anyhow::Result;
 
 pub trait MenuProvider: Send + Sync {
     fn menu_items(&self) -> Vec<PluginMenuItem>;
     fn handle_event(&self, event_id: &str) -> Result<()>;
+
+    fn plugin_id(&self) -> Option<&str> {
+        None
+    }
+
+    fn routes(&self, _feature_id: &str) -> Result<Vec<EventRoute>> {
+        Ok(Vec::new())
+    }
 }
 
 pub struct FeatureRegistry {
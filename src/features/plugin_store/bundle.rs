@@ -0,0 +1,244 @@
+use crate::features::task_runner::{self, TaskRunnerConfig};
+use crate::hotkeys::{self, HotkeyConfig};
+use crate::migration::load_json_or_default;
+use crate::paths::{self, is_safe_path_component};
+use crate::plugins::config::PluginConfigs;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub version: u32,
+    #[serde(default)]
+    pub hotkeys: HotkeyConfig,
+    #[serde(default)]
+    pub plugin_configs: PluginConfigs,
+    #[serde(default)]
+    pub task_runner: TaskRunnerConfig,
+    #[serde(default)]
+    pub plugins: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_token: Option<String>,
+}
+
+pub struct BundlePaths {
+    pub hotkeys: PathBuf,
+    pub plugin_configs: PathBuf,
+    pub task_runner: PathBuf,
+}
+
+impl BundlePaths {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            hotkeys: paths::hotkeys_path()?,
+            plugin_configs: paths::plugin_configs_path()?,
+            task_runner: paths::task_runner_config_path()?,
+        })
+    }
+}
+
+pub fn export(paths: &BundlePaths, plugins_dir: &Path) -> Result<ConfigBundle> {
+    Ok(ConfigBundle {
+        version: BUNDLE_VERSION,
+        hotkeys: load_json_or_default(&paths.hotkeys, hotkeys::MIGRATIONS)?,
+        plugin_configs: load_json_or_default(&paths.plugin_configs, &[])?,
+        task_runner: load_json_or_default(&paths.task_runner, task_runner::MIGRATIONS)?,
+        plugins: installed_plugin_ids(plugins_dir),
+        github_token: None,
+    })
+}
+
+pub fn import(bundle: &ConfigBundle, paths: &BundlePaths, plugins_dir: &Path) -> Result<Vec<String>> {
+    if bundle.version > BUNDLE_VERSION {
+        anyhow::bail!("Unsupported bundle version: {}", bundle.version);
+    }
+
+    write_json(&paths.hotkeys, &bundle.hotkeys)?;
+    import_plugin_configs(&bundle.plugin_configs, &paths.plugin_configs, plugins_dir)?;
+    write_json(&paths.task_runner, &bundle.task_runner)?;
+
+    Ok(missing_plugins(&bundle.plugins, plugins_dir))
+}
+
+fn import_plugin_configs(imported: &PluginConfigs, backup_path: &Path, plugins_dir: &Path) -> Result<()> {
+    let mut backups: PluginConfigs = load_json_or_default(backup_path, &[])?;
+    for (id, config) in &imported.configs {
+        if !is_safe_path_component(id) {
+            log::warn!("Skipping config for invalid plugin ID: {}", id);
+            continue;
+        }
+        let plugin_dir = plugins_dir.join(id);
+        if plugin_dir.is_dir() {
+            write_json(&plugin_dir.join("config.json"), config)?;
+        }
+        backups.record(id, config.clone());
+    }
+    write_json(backup_path, &backups)
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    paths::atomic_write(path, serde_json::to_string_pretty(value)?)
+}
+
+fn installed_plugin_ids(plugins_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(plugins_dir) else {
+        return Vec::new();
+    };
+
+    let mut ids: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|entry| entry.path().join("plugin.toml").exists())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    ids.sort();
+    ids
+}
+
+fn missing_plugins(plugins: &[String], plugins_dir: &Path) -> Vec<String> {
+    let mut missing: Vec<String> = plugins
+        .iter()
+        .filter(|id| is_safe_path_component(id))
+        .filter(|id| std::fs::symlink_metadata(plugins_dir.join(id)).is_err())
+        .cloned()
+        .collect();
+    missing.sort();
+    missing.dedup();
+    missing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn bundle_paths(dir: &Path) -> BundlePaths {
+        BundlePaths {
+            hotkeys: dir.join("hotkeys.json"),
+            plugin_configs: dir.join("plugin-configs.json"),
+            task_runner: dir.join("task-runner.json"),
+        }
+    }
+
+    fn add_plugin(plugins_dir: &Path, id: &str) {
+        let dir = plugins_dir.join(id);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("plugin.toml"), "[plugin]").unwrap();
+    }
+
+    #[test]
+    fn bundle_round_trips_through_json() {
+        let source = TempDir::new().unwrap();
+        let source_plugins = TempDir::new().unwrap();
+        let paths = bundle_paths(source.path());
+        fs::write(
+            &paths.hotkeys,
+            json!({"hotkeys": [{"id": "h1", "key": "Super+A", "plugin_id": "p", "action": "run", "enabled": true}]})
+                .to_string(),
+        )
+        .unwrap();
+        fs::write(&paths.plugin_configs, json!({"plugin-a": {"theme": "dark"}}).to_string()).unwrap();
+        fs::write(
+            &paths.task_runner,
+            json!({"actions": {"build": {"name": "Build", "command": "make"}}}).to_string(),
+        )
+        .unwrap();
+        add_plugin(source_plugins.path(), "plugin-b");
+        add_plugin(source_plugins.path(), "plugin-a");
+        fs::create_dir_all(source_plugins.path().join("not-a-plugin")).unwrap();
+
+        let exported = export(&paths, source_plugins.path()).unwrap();
+        let json = serde_json::to_string(&exported).unwrap();
+        let imported: ConfigBundle = serde_json::from_str(&json).unwrap();
+
+        let target = TempDir::new().unwrap();
+        let target_paths = bundle_paths(&target.path().join("nested"));
+        import(&imported, &target_paths, target.path()).unwrap();
+
+        assert_eq!(imported.plugins, vec!["plugin-a", "plugin-b"]);
+        assert!(!json.contains("github_token"));
+        let hotkeys: HotkeyConfig = load_json_or_default(&target_paths.hotkeys, &[]).unwrap();
+        assert_eq!(hotkeys.hotkeys[0].key, "Super+A");
+        let configs: PluginConfigs = load_json_or_default(&target_paths.plugin_configs, &[]).unwrap();
+        assert_eq!(configs.configs["plugin-a"], json!({"theme": "dark"}));
+        let tasks: TaskRunnerConfig = load_json_or_default(&target_paths.task_runner, &[]).unwrap();
+        assert_eq!(tasks.actions["build"].command, "make");
+    }
+
+    #[test]
+    fn import_reports_only_missing_plugins() {
+        let config_dir = TempDir::new().unwrap();
+        let plugins_dir = TempDir::new().unwrap();
+        add_plugin(plugins_dir.path(), "plugin-installed");
+        let bundle: ConfigBundle = serde_json::from_value(json!({
+            "version": 1,
+            "plugins": ["plugin-missing", "plugin-installed", "../escape", "plugin-missing"]
+        }))
+        .unwrap();
+
+        let missing = import(&bundle, &bundle_paths(config_dir.path()), plugins_dir.path()).unwrap();
+
+        assert_eq!(missing, vec!["plugin-missing"]);
+    }
+
+    #[test]
+    fn import_writes_live_configs_and_merges_backups() {
+        let config_dir = TempDir::new().unwrap();
+        let plugins_dir = TempDir::new().unwrap();
+        let paths = bundle_paths(config_dir.path());
+        add_plugin(plugins_dir.path(), "plugin-installed");
+        fs::write(
+            plugins_dir.path().join("plugin-installed").join("config.json"),
+            json!({"theme": "light"}).to_string(),
+        )
+        .unwrap();
+        fs::write(
+            &paths.plugin_configs,
+            json!({"plugin-kept": {"a": 1}, "plugin-installed": {"theme": "light"}}).to_string(),
+        )
+        .unwrap();
+        let bundle: ConfigBundle = serde_json::from_value(json!({
+            "version": 1,
+            "plugin_configs": {
+                "plugin-installed": {"theme": "dark"},
+                "plugin-missing": {"b": 2},
+                "../escape": {"c": 3}
+            }
+        }))
+        .unwrap();
+
+        import(&bundle, &paths, plugins_dir.path()).unwrap();
+
+        let live: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(plugins_dir.path().join("plugin-installed").join("config.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(live, json!({"theme": "dark"}));
+        assert!(!plugins_dir.path().join("plugin-missing").exists());
+        let backups: PluginConfigs = load_json_or_default(&paths.plugin_configs, &[]).unwrap();
+        assert_eq!(backups.configs["plugin-kept"], json!({"a": 1}));
+        assert_eq!(backups.configs["plugin-installed"], json!({"theme": "dark"}));
+        assert_eq!(backups.configs["plugin-missing"], json!({"b": 2}));
+        assert!(!backups.configs.contains_key("../escape"));
+        assert!(backups.updated_at.contains_key("plugin-missing"));
+    }
+
+    #[test]
+    fn import_rejects_newer_bundle_version() {
+        let config_dir = TempDir::new().unwrap();
+        let bundle: ConfigBundle = serde_json::from_value(json!({"version": 99})).unwrap();
+
+        let result = import(&bundle, &bundle_paths(config_dir.path()), config_dir.path());
+
+        assert!(result.is_err());
+        assert!(!config_dir.path().join("hotkeys.json").exists());
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+pub const MAX_COVER_SIZE: usize = 5 * 1024 * 1024;
+const MAX_CACHE_BYTES: u64 = 50 * 1024 * 1024;
+const COVER_FILE: &str = "cover.png";
+
+pub fn cache_dir() -> Option<PathBuf> {
+    crate::paths::cover_cache_dir().ok()
+}
+
+pub fn cached_cover_path(cache_dir: &Path, plugin_id: &str) -> PathBuf {
+    cache_dir.join(format!("{}.png", plugin_id))
+}
+
+pub fn cover_candidates(plugins_dir: &Path, cache_dir: Option<&Path>, plugin_id: &str) -> Vec<PathBuf> {
+    let mut candidates = vec![plugins_dir.join(plugin_id).join(COVER_FILE)];
+    if let Some(cache_dir) = cache_dir {
+        candidates.push(cached_cover_path(cache_dir, plugin_id));
+    }
+    candidates
+}
+
+pub fn resolve_cover(plugins_dir: &Path, cache_dir: Option<&Path>, plugin_id: &str) -> Option<PathBuf> {
+    cover_candidates(plugins_dir, cache_dir, plugin_id)
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+pub fn store_cover(cache_dir: &Path, plugin_id: &str, data: &[u8]) -> Result<()> {
+    if data.len() > MAX_COVER_SIZE {
+        anyhow::bail!("Cover for {} exceeds {} bytes", plugin_id, MAX_COVER_SIZE);
+    }
+    std::fs::create_dir_all(cache_dir)?;
+    crate::paths::atomic_write(&cached_cover_path(cache_dir, plugin_id), data)
+}
+
+pub fn prune_cache(cache_dir: &Path) {
+    prune_to(cache_dir, MAX_CACHE_BYTES);
+}
+
+fn prune_to(cache_dir: &Path, max_bytes: u64) {
+    let Ok(entries) = std::fs::read_dir(cache_dir) else {
+        return;
+    };
+
+    let mut files: Vec<(SystemTime, u64, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata.is_file().then(|| {
+                let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                (modified, metadata.len(), entry.path())
+            })
+        })
+        .collect();
+    files.sort();
+
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    for (_, len, path) in files {
+        if total <= max_bytes {
+            break;
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => total -= len,
+            Err(e) => log::warn!("Failed to prune cached cover {:?}: {}", path, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn cached_cover_path_uses_plugin_id() {
+        let cache = Path::new("/config/cover-cache");
+        assert_eq!(cached_cover_path(cache, "plugin-demo"), PathBuf::from("/config/cover-cache/plugin-demo.png"));
+    }
+
+    #[test]
+    fn resolve_cover_prefers_installed_then_cache() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let plugins_dir = tmp.path().join("plugins");
+        let cache_dir = tmp.path().join("cover-cache");
+        std::fs::create_dir_all(plugins_dir.join("installed")).unwrap();
+        std::fs::create_dir_all(plugins_dir.join("both")).unwrap();
+        std::fs::write(plugins_dir.join("installed").join(COVER_FILE), "local").unwrap();
+        std::fs::write(plugins_dir.join("both").join(COVER_FILE), "local").unwrap();
+        store_cover(&cache_dir, "both", b"cached").unwrap();
+        store_cover(&cache_dir, "store-only", b"cached").unwrap();
+
+        let cases = [
+            ("installed", Some(plugins_dir.join("installed").join(COVER_FILE))),
+            ("both", Some(plugins_dir.join("both").join(COVER_FILE))),
+            ("store-only", Some(cached_cover_path(&cache_dir, "store-only"))),
+            ("missing", None),
+        ];
+
+        for (id, expected) in cases {
+            assert_eq!(resolve_cover(&plugins_dir, Some(&cache_dir), id), expected, "id: {}", id);
+        }
+        assert_eq!(resolve_cover(&plugins_dir, None, "store-only"), None);
+    }
+
+    #[test]
+    fn store_cover_rejects_oversized_images() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let data = vec![0u8; MAX_COVER_SIZE + 1];
+
+        assert!(store_cover(tmp.path(), "big", &data).is_err());
+        assert!(!cached_cover_path(tmp.path(), "big").exists());
+    }
+
+    #[test]
+    fn prune_removes_oldest_covers_until_under_limit() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let now = SystemTime::now();
+        for (i, id) in ["oldest", "middle", "newest"].iter().enumerate() {
+            store_cover(tmp.path(), id, &[0u8; 100]).unwrap();
+            let file = std::fs::File::options().write(true).open(cached_cover_path(tmp.path(), id)).unwrap();
+            file.set_modified(now - Duration::from_secs(300 - i as u64 * 100)).unwrap();
+        }
+
+        prune_to(tmp.path(), 200);
+
+        assert!(!cached_cover_path(tmp.path(), "oldest").exists());
+        assert!(cached_cover_path(tmp.path(), "middle").exists());
+        assert!(cached_cover_path(tmp.path(), "newest").exists());
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct Debouncer {
+    delay: Duration,
+    latest: Arc<AtomicU64>,
+}
+
+impl Debouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            latest: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    pub fn schedule<F>(&self, action: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let ticket = self.latest.fetch_add(1, Ordering::SeqCst) + 1;
+        let latest = Arc::clone(&self.latest);
+        let delay = self.delay;
+
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            if latest.load(Ordering::SeqCst) == ticket {
+                tokio::task::spawn_blocking(action);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    const DELAY: Duration = Duration::from_millis(50);
+
+    fn counting_action(count: &Arc<AtomicUsize>) -> impl FnOnce() + Send + 'static {
+        let count = Arc::clone(count);
+        move || {
+            count.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn requests_within_window_collapse_into_one() {
+        let debouncer = Debouncer::new(DELAY);
+        let count = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..5 {
+            debouncer.schedule(counting_action(&count));
+            tokio::time::sleep(DELAY / 5).await;
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+
+        tokio::time::sleep(DELAY * 3).await;
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn requests_after_quiet_period_run_again() {
+        let debouncer = Debouncer::new(DELAY);
+        let count = Arc::new(AtomicUsize::new(0));
+
+        debouncer.schedule(counting_action(&count));
+        tokio::time::sleep(DELAY * 3).await;
+        debouncer.schedule(counting_action(&count));
+        tokio::time::sleep(DELAY * 3).await;
+
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn blocking_actions_run_off_the_runtime() {
+        let debouncer = Debouncer::new(DELAY);
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+        debouncer.schedule(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            let _ = done_tx.send(());
+        });
+
+        let started = std::time::Instant::now();
+        tokio::time::sleep(DELAY * 3).await;
+        assert!(started.elapsed() < Duration::from_millis(250), "runtime was blocked for {:?}", started.elapsed());
+        done_rx.await.unwrap();
+    }
+}
@@ -0,0 +1,418 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+use super::installer::{GitError, LocalChanges, UpdateReverted};
+use crate::plugins::schema::FieldError;
+use crate::dev::LinkError;
+use crate::plugins::PluginError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    InvalidId,
+    InvalidJson,
+    InvalidConfig,
+    AlreadyInstalled,
+    NotInstalled,
+    NotFound,
+    AlreadyLinked,
+    NotLinked,
+    InvalidPath,
+    InvalidManifest,
+    DaemonStartFailed,
+    NoDaemon,
+    PayloadTooLarge,
+    GitFailed,
+    Cancelled,
+    OperationInProgress,
+    UpdateReverted,
+    LocalChanges,
+    Offline,
+    Internal,
+}
+
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: ErrorCode,
+    message: String,
+    fields: Vec<FieldError>,
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    error: ErrorDetail<'a>,
+}
+
+#[derive(Serialize)]
+struct ErrorDetail<'a> {
+    code: ErrorCode,
+    message: &'a str,
+    #[serde(skip_serializing_if = "<[FieldError]>::is_empty")]
+    fields: &'a [FieldError],
+}
+
+#[derive(Serialize)]
+struct ActionFailure<'a> {
+    success: bool,
+    message: &'a str,
+    error: ErrorDetail<'a>,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn invalid_id() -> Self {
+        Self::new(StatusCode::BAD_REQUEST, ErrorCode::InvalidId, "Invalid plugin ID")
+    }
+
+    pub fn invalid_json() -> Self {
+        Self::new(StatusCode::BAD_REQUEST, ErrorCode::InvalidJson, "Invalid JSON")
+    }
+
+    pub fn invalid_config(fields: Vec<FieldError>) -> Self {
+        Self {
+            fields,
+            ..Self::new(StatusCode::BAD_REQUEST, ErrorCode::InvalidConfig, "Config does not match schema")
+        }
+    }
+
+    pub fn already_installed() -> Self {
+        Self::new(StatusCode::CONFLICT, ErrorCode::AlreadyInstalled, "Plugin already installed")
+    }
+
+    pub fn not_installed() -> Self {
+        Self::new(StatusCode::NOT_FOUND, ErrorCode::NotInstalled, "Plugin not installed")
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, ErrorCode::NotFound, message)
+    }
+
+    pub fn payload_too_large(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::PAYLOAD_TOO_LARGE, ErrorCode::PayloadTooLarge, message)
+    }
+
+    pub fn git_failed(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::GitFailed, message)
+    }
+
+    pub fn cancelled() -> Self {
+        Self::new(StatusCode::CONFLICT, ErrorCode::Cancelled, "Installation cancelled")
+    }
+
+    pub fn operation_in_progress() -> Self {
+        Self::new(
+            StatusCode::CONFLICT,
+            ErrorCode::OperationInProgress,
+            "Another operation is in progress for this plugin",
+        )
+    }
+
+    pub fn offline() -> Self {
+        Self::new(StatusCode::SERVICE_UNAVAILABLE, ErrorCode::Offline, "Offline mode is enabled")
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::Internal, message)
+    }
+
+    pub fn from_installer(error: &anyhow::Error, message: impl Into<String>) -> Self {
+        if let Some(plugin_error) = error.downcast_ref::<PluginError>() {
+            return Self::from(plugin_error);
+        }
+        if let Some(reverted) = error.downcast_ref::<UpdateReverted>() {
+            return Self::new(StatusCode::UNPROCESSABLE_ENTITY, ErrorCode::UpdateReverted, reverted.to_string());
+        }
+        if let Some(changes) = error.downcast_ref::<LocalChanges>() {
+            return Self::new(StatusCode::CONFLICT, ErrorCode::LocalChanges, changes.to_string());
+        }
+        if error.downcast_ref::<GitError>().is_some() {
+            return Self::git_failed(message);
+        }
+        Self::internal(message)
+    }
+}
+
+impl From<&PluginError> for ApiError {
+    fn from(error: &PluginError) -> Self {
+        let (status, code) = match error {
+            PluginError::NotFound(_) => (StatusCode::NOT_FOUND, ErrorCode::NotFound),
+            PluginError::AlreadyInstalled(_) => (StatusCode::CONFLICT, ErrorCode::AlreadyInstalled),
+            PluginError::InvalidPath(_) => (StatusCode::BAD_REQUEST, ErrorCode::InvalidPath),
+            PluginError::ManifestInvalid(_) => (StatusCode::UNPROCESSABLE_ENTITY, ErrorCode::InvalidManifest),
+            PluginError::DaemonStartFailed(_) => (StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::DaemonStartFailed),
+            PluginError::NoDaemon(_) => (StatusCode::CONFLICT, ErrorCode::NoDaemon),
+            PluginError::Io { .. } => (StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::Internal),
+        };
+        Self::new(status, code, error.to_string())
+    }
+}
+
+impl From<PluginError> for ApiError {
+    fn from(error: PluginError) -> Self {
+        Self::from(&error)
+    }
+}
+
+impl From<LinkError> for ApiError {
+    fn from(error: LinkError) -> Self {
+        let (status, code) = match &error {
+            LinkError::AlreadyLinked => (StatusCode::CONFLICT, ErrorCode::AlreadyLinked),
+            LinkError::SourceMissing | LinkError::NoManifest | LinkError::IdMismatch { .. } => {
+                (StatusCode::BAD_REQUEST, ErrorCode::InvalidPath)
+            }
+            LinkError::InvalidId => (StatusCode::BAD_REQUEST, ErrorCode::InvalidId),
+            LinkError::NotInstalled => (StatusCode::NOT_FOUND, ErrorCode::NotInstalled),
+            LinkError::NotLinked | LinkError::NotSymlink => (StatusCode::BAD_REQUEST, ErrorCode::NotLinked),
+            LinkError::Io { .. } => (StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::Internal),
+        };
+        Self::new(status, code, error.to_string())
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl ApiError {
+    fn detail(&self) -> ErrorDetail<'_> {
+        ErrorDetail {
+            code: self.code,
+            message: &self.message,
+            fields: &self.fields,
+        }
+    }
+
+    pub fn into_install_failure(self) -> Self {
+        match self.code {
+            ErrorCode::InvalidId | ErrorCode::OperationInProgress | ErrorCode::Offline | ErrorCode::Cancelled => self,
+            _ => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                ..self
+            },
+        }
+    }
+
+    pub fn into_action_failure(self) -> Response {
+        let status = match self.code {
+            ErrorCode::OperationInProgress | ErrorCode::Offline => self.status,
+            _ => StatusCode::OK,
+        };
+        let body = ActionFailure {
+            success: false,
+            message: &self.message,
+            error: self.detail(),
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorBody { error: self.detail() };
+        (self.status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn response_json(error: ApiError) -> (StatusCode, serde_json::Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn api_error_serializes_code_and_status() {
+        let cases = [
+            (ApiError::invalid_id(), StatusCode::BAD_REQUEST, "invalid_id"),
+            (ApiError::already_installed(), StatusCode::CONFLICT, "already_installed"),
+            (ApiError::operation_in_progress(), StatusCode::CONFLICT, "operation_in_progress"),
+            (ApiError::not_found("Config not found"), StatusCode::NOT_FOUND, "not_found"),
+            (ApiError::git_failed("Installation failed"), StatusCode::INTERNAL_SERVER_ERROR, "git_failed"),
+            (ApiError::payload_too_large("Config too large"), StatusCode::PAYLOAD_TOO_LARGE, "payload_too_large"),
+        ];
+
+        for (error, expected_status, expected_code) in cases {
+            let expected_message = error.message.clone();
+            let (status, json) = response_json(error).await;
+
+            assert_eq!(status, expected_status, "code: {}", expected_code);
+            assert_eq!(json["error"]["code"], expected_code);
+            assert_eq!(json["error"]["message"], expected_message);
+            assert_eq!(json.as_object().unwrap().len(), 1, "only the error field should be present");
+        }
+    }
+
+    #[tokio::test]
+    async fn action_failures_keep_ok_status_with_structured_error() {
+        let cases = [
+            (ApiError::invalid_id(), StatusCode::OK, "invalid_id"),
+            (ApiError::offline(), StatusCode::SERVICE_UNAVAILABLE, "offline"),
+            (ApiError::git_failed("Update failed"), StatusCode::OK, "git_failed"),
+            (ApiError::operation_in_progress(), StatusCode::CONFLICT, "operation_in_progress"),
+        ];
+
+        for (error, expected_status, expected_code) in cases {
+            let expected_message = error.message.clone();
+            let response = error.into_action_failure();
+            let status = response.status();
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+            assert_eq!(status, expected_status, "code: {}", expected_code);
+            assert_eq!(json["success"], false);
+            assert_eq!(json["message"], expected_message);
+            assert_eq!(json["error"]["code"], expected_code);
+            assert_eq!(json["error"]["message"], expected_message);
+        }
+    }
+
+    #[test]
+    fn install_failures_keep_server_error_status_with_specific_code() {
+        let cases = [
+            (ApiError::invalid_id(), StatusCode::BAD_REQUEST, ErrorCode::InvalidId),
+            (ApiError::already_installed(), StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::AlreadyInstalled),
+            (ApiError::from(PluginError::ManifestInvalid("bad".into())), StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::InvalidManifest),
+            (ApiError::git_failed("Installation failed"), StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::GitFailed),
+            (ApiError::operation_in_progress(), StatusCode::CONFLICT, ErrorCode::OperationInProgress),
+            (ApiError::offline(), StatusCode::SERVICE_UNAVAILABLE, ErrorCode::Offline),
+        ];
+
+        for (error, expected_status, expected_code) in cases {
+            let error = error.into_install_failure();
+
+            assert_eq!(error.status, expected_status, "code: {:?}", expected_code);
+            assert_eq!(error.code, expected_code);
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_config_lists_offending_fields() {
+        let error = ApiError::invalid_config(vec![FieldError {
+            field: "enabled".to_string(),
+            message: "Missing required field".to_string(),
+        }]);
+
+        let (status, json) = response_json(error).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(json["error"]["code"], "invalid_config");
+        assert_eq!(json["error"]["fields"][0]["field"], "enabled");
+        assert_eq!(json["error"]["fields"][0]["message"], "Missing required field");
+    }
+
+    #[test]
+    fn from_installer_classifies_git_errors() {
+        let git_error = anyhow::Error::new(GitError::new("Git clone failed"));
+        let io_error = anyhow::anyhow!("Permission denied");
+
+        assert_eq!(ApiError::from_installer(&git_error, "Installation failed").code, ErrorCode::GitFailed);
+        assert_eq!(ApiError::from_installer(&io_error, "Installation failed").code, ErrorCode::Internal);
+    }
+
+    #[test]
+    fn plugin_errors_map_to_status_and_code() {
+        let io = || std::io::Error::other("disk full");
+        let cases = vec![
+            (PluginError::NotFound("gone".into()), StatusCode::NOT_FOUND, ErrorCode::NotFound),
+            (PluginError::AlreadyInstalled("dup".into()), StatusCode::CONFLICT, ErrorCode::AlreadyInstalled),
+            (PluginError::InvalidPath("bad".into()), StatusCode::BAD_REQUEST, ErrorCode::InvalidPath),
+            (PluginError::ManifestInvalid("bad".into()), StatusCode::UNPROCESSABLE_ENTITY, ErrorCode::InvalidManifest),
+            (PluginError::DaemonStartFailed("crash".into()), StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::DaemonStartFailed),
+            (PluginError::NoDaemon("none".into()), StatusCode::CONFLICT, ErrorCode::NoDaemon),
+            (PluginError::io("Failed to write")(io()), StatusCode::INTERNAL_SERVER_ERROR, ErrorCode::Internal),
+        ];
+
+        for (plugin_error, expected_status, expected_code) in cases {
+            let message = plugin_error.to_string();
+            let error = ApiError::from(plugin_error);
+            assert_eq!(error.status, expected_status, "message: {}", message);
+            assert_eq!(error.code, expected_code, "message: {}", message);
+            assert_eq!(error.message, message);
+        }
+    }
+
+    #[test]
+    fn link_errors_map_to_status_and_code() {
+        let io = std::io::Error::other("denied");
+        let cases = vec![
+            (LinkError::AlreadyLinked, StatusCode::CONFLICT, ErrorCode::AlreadyLinked),
+            (LinkError::SourceMissing, StatusCode::BAD_REQUEST, ErrorCode::InvalidPath),
+            (LinkError::NoManifest, StatusCode::BAD_REQUEST, ErrorCode::InvalidPath),
+            (
+                LinkError::IdMismatch { source_id: "a".into(), expected: "b".into() },
+                StatusCode::BAD_REQUEST,
+                ErrorCode::InvalidPath,
+            ),
+            (LinkError::InvalidId, StatusCode::BAD_REQUEST, ErrorCode::InvalidId),
+            (LinkError::NotInstalled, StatusCode::NOT_FOUND, ErrorCode::NotInstalled),
+            (LinkError::NotLinked, StatusCode::BAD_REQUEST, ErrorCode::NotLinked),
+            (LinkError::NotSymlink, StatusCode::BAD_REQUEST, ErrorCode::NotLinked),
+            (
+                LinkError::Io { context: "Failed to create symlink", source: io },
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorCode::Internal,
+            ),
+        ];
+
+        for (link_error, expected_status, expected_code) in cases {
+            let message = link_error.to_string();
+            let error = ApiError::from(link_error);
+            assert_eq!(error.status, expected_status, "message: {}", message);
+            assert_eq!(error.code, expected_code, "message: {}", message);
+            assert_eq!(error.message, message);
+        }
+    }
+
+    #[test]
+    fn from_installer_classifies_plugin_errors() {
+        let error = anyhow::Error::new(PluginError::AlreadyInstalled("Plugin already installed: a".into()));
+
+        let error = ApiError::from_installer(&error, "Installation failed");
+
+        assert_eq!(error.code, ErrorCode::AlreadyInstalled);
+        assert_eq!(error.status, StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn from_installer_reports_reverted_updates() {
+        let reverted = anyhow::Error::new(UpdateReverted::new("Failed to parse plugin.toml"));
+
+        let error = ApiError::from_installer(&reverted, "Update failed");
+
+        assert_eq!(error.code, ErrorCode::UpdateReverted);
+        assert_eq!(error.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(error.message.starts_with("Update reverted"));
+    }
+
+    #[test]
+    fn from_installer_reports_local_changes() {
+        let changes = anyhow::Error::new(LocalChanges::new(vec!["run.sh".into()]));
+
+        let error = ApiError::from_installer(&changes, "Update failed");
+
+        assert_eq!(error.code, ErrorCode::LocalChanges);
+        assert_eq!(error.status, StatusCode::CONFLICT);
+        assert!(error.message.ends_with("run.sh"));
+    }
+}
@@ -1,11 +1,14 @@
+use super::covers;
 use crate::paths;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const PLUGIN_PREFIX: &str = "plugin-";
-const CACHE_TTL_SECS: u64 = 3600;
+const DEFAULT_CACHE_TTL_SECS: u64 = 3600;
+const CACHE_TTL_ENV: &str = "QOL_PLUGIN_CACHE_TTL";
 
 fn token_path() -> Option<PathBuf> {
     paths::github_token_path().ok()
@@ -30,6 +33,10 @@ pub struct CachedPlugin {
     pub repo_url: String,
     #[serde(default)]
     pub platforms: Option<Vec<String>>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub homepage: Option<String>,
 }
 
 impl From<PluginMetadata> for CachedPlugin {
@@ -41,6 +48,8 @@ impl From<PluginMetadata> for CachedPlugin {
             version: m.version,
             repo_url: m.repo_url,
             platforms: m.platforms,
+            author: m.author,
+            homepage: m.homepage,
         }
     }
 }
@@ -54,6 +63,8 @@ impl From<CachedPlugin> for PluginMetadata {
             version: c.version,
             repo_url: c.repo_url,
             platforms: c.platforms,
+            author: c.author,
+            homepage: c.homepage,
         }
     }
 }
@@ -83,7 +94,7 @@ pub fn write_cache(plugins: &[PluginMetadata]) -> Result<()> {
         plugins: plugins.iter().cloned().map(CachedPlugin::from).collect(),
     };
     let content = serde_json::to_string(&cache)?;
-    std::fs::write(&path, content)?;
+    paths::atomic_write(&path, content)?;
     log::info!("Plugin cache written to {:?}", path);
     Ok(())
 }
@@ -99,18 +110,155 @@ pub fn update_cached_version(plugin_id: &str, version: &str) {
 
     plugin.version = version.to_string();
     let Ok(content) = serde_json::to_string(&cache) else { return };
-    let _ = std::fs::write(path, content);
+    let _ = paths::atomic_write(&path, content);
     log::info!("Updated cache version for {}: {}", plugin_id, version);
 }
 
-fn get_valid_cache() -> Option<Vec<PluginMetadata>> {
-    let cache = read_cache()?;
-    let age = current_timestamp() - cache.timestamp;
-    
-    if age >= CACHE_TTL_SECS {
+pub fn reconcile_cached_versions(installed: &HashMap<String, String>) -> Result<Vec<String>> {
+    let Some(mut cache) = read_cache() else {
+        return Ok(vec![]);
+    };
+    let Some(path) = cache_path() else {
+        anyhow::bail!("Could not determine cache path");
+    };
+
+    let reconciled = reconcile_versions(&mut cache, installed);
+    if !reconciled.is_empty() {
+        paths::atomic_write(&path, serde_json::to_string(&cache)?)?;
+        log::info!("Reconciled cached versions for: {}", reconciled.join(", "));
+    }
+    Ok(reconciled)
+}
+
+fn reconcile_versions(cache: &mut PluginCache, installed: &HashMap<String, String>) -> Vec<String> {
+    use crate::version::Version;
+
+    let mut reconciled = Vec::new();
+    for plugin in cache.plugins.iter_mut() {
+        let Some(installed_version) = installed.get(&plugin.id) else { continue };
+        if Version::parse(installed_version).is_newer_than(&Version::parse(&plugin.version)) {
+            plugin.version = installed_version.clone();
+            reconciled.push(plugin.id.clone());
+        }
+    }
+    reconciled.sort();
+    reconciled
+}
+
+pub fn configured_plugin_org() -> String {
+    let settings = crate::settings::AppSettings::load().unwrap_or_else(|e| {
+        log::warn!("Failed to load settings for plugin org: {}", e);
+        crate::settings::AppSettings::default()
+    });
+    resolve_plugin_org(settings.plugin_org)
+}
+
+fn resolve_plugin_org(configured: String) -> String {
+    if is_valid_org(&configured) {
+        return configured;
+    }
+    log::warn!("Ignoring invalid plugin_org {:?}", configured);
+    crate::settings::AppSettings::default().plugin_org
+}
+
+pub fn is_valid_org(org: &str) -> bool {
+    (1..=39).contains(&org.len())
+        && org.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && !org.starts_with('-')
+        && !org.ends_with('-')
+        && !org.contains("--")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitHubEndpoints {
+    api_base: String,
+    raw_base: String,
+    web_base: String,
+}
+
+impl GitHubEndpoints {
+    pub fn configured() -> Self {
+        let settings = crate::settings::AppSettings::load().unwrap_or_else(|e| {
+            log::warn!("Failed to load settings for GitHub endpoints: {}", e);
+            crate::settings::AppSettings::default()
+        });
+        Self::resolve(&settings.github_api_base, &settings.github_raw_base, &settings.github_web_base)
+    }
+
+    pub(super) fn resolve(api_base: &str, raw_base: &str, web_base: &str) -> Self {
+        let defaults = crate::settings::AppSettings::default();
+        Self {
+            api_base: resolve_base("github_api_base", api_base, &defaults.github_api_base),
+            raw_base: resolve_base("github_raw_base", raw_base, &defaults.github_raw_base),
+            web_base: resolve_base("github_web_base", web_base, &defaults.github_web_base),
+        }
+    }
+
+    pub fn org_repos_url(&self, org: &str) -> String {
+        format!("{}/orgs/{}/repos", self.api_base, org)
+    }
+
+    pub fn manifest_url(&self, org: &str, repo: &str, branch: &str) -> String {
+        self.raw_file_url(org, repo, branch, "plugin.toml")
+    }
+
+    pub fn raw_file_url(&self, org: &str, repo: &str, branch: &str, file: &str) -> String {
+        format!("{}/{}/{}/{}/{}", self.raw_base, org, repo, branch, file)
+    }
+
+    pub fn latest_release_url(&self, repo: &str) -> String {
+        format!("{}/repos/{}/releases/latest", self.api_base, repo)
+    }
+
+    pub fn clone_url(&self, org: &str, plugin_id: &str) -> String {
+        format!("{}/{}/{}.git", self.web_base, org, plugin_id)
+    }
+}
+
+impl Default for GitHubEndpoints {
+    fn default() -> Self {
+        let defaults = crate::settings::AppSettings::default();
+        Self::resolve(&defaults.github_api_base, &defaults.github_raw_base, &defaults.github_web_base)
+    }
+}
+
+fn resolve_base(key: &str, configured: &str, default: &str) -> String {
+    if is_https_url(configured) {
+        return configured.trim_end_matches('/').to_string();
+    }
+    log::warn!("Ignoring {} {:?}: must be an https URL", key, configured);
+    default.to_string()
+}
+
+fn is_https_url(value: &str) -> bool {
+    reqwest::Url::parse(value).is_ok_and(|url| url.scheme() == "https" && url.host_str().is_some())
+}
+
+fn cache_ttl_secs() -> u64 {
+    let configured = crate::settings::AppSettings::load()
+        .ok()
+        .and_then(|s| s.plugin_cache_ttl_secs);
+    resolve_cache_ttl(std::env::var(CACHE_TTL_ENV).ok().as_deref(), configured)
+}
+
+fn resolve_cache_ttl(env_value: Option<&str>, configured: Option<u64>) -> u64 {
+    env_value
+        .and_then(|v| v.trim().parse().ok())
+        .or(configured)
+        .unwrap_or(DEFAULT_CACHE_TTL_SECS)
+}
+
+fn get_valid_cache(ttl_secs: u64) -> Option<Vec<PluginMetadata>> {
+    fresh_cache_plugins(read_cache()?, current_timestamp(), ttl_secs)
+}
+
+fn fresh_cache_plugins(cache: PluginCache, now: u64, ttl_secs: u64) -> Option<Vec<PluginMetadata>> {
+    let age = now.saturating_sub(cache.timestamp);
+
+    if age >= ttl_secs {
         return None;
     }
-    
+
     log::info!("Using cached plugin data ({} seconds old)", age);
     Some(cache.plugins.into_iter().map(PluginMetadata::from).collect())
 }
@@ -124,8 +272,11 @@ struct GitHubRepo {
 
 pub struct GitHubClient {
     org: String,
+    endpoints: GitHubEndpoints,
+    cover_cache: Option<PathBuf>,
     client: reqwest::Client,
     token: Option<String>,
+    offline: bool,
 }
 
 pub fn get_stored_token() -> Option<String> {
@@ -155,7 +306,7 @@ pub fn store_token(token: &str) -> Result<()> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
-    std::fs::write(&path, token.trim())?;
+    paths::atomic_write_private(&path, token.trim())?;
     log::info!("Stored GitHub token to {:?}", path);
     Ok(())
 }
@@ -175,15 +326,26 @@ impl GitHubClient {
         let token = get_stored_token();
         Self {
             org: org.into(),
-            client: reqwest::Client::new(),
+            endpoints: GitHubEndpoints::configured(),
+            cover_cache: None,
+            client: crate::http::client().clone(),
             token,
+            offline: crate::http::offline(),
         }
     }
 
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn with_cover_cache(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.cover_cache = cache_dir;
+        self
+    }
+
     fn build_request(&self, url: &str) -> reqwest::RequestBuilder {
-        let mut req = self.client
-            .get(url)
-            .header("User-Agent", "qol-tray");
+        let mut req = self.client.get(url);
         
         if let Some(token) = &self.token {
             req = req.header("Authorization", format!("Bearer {}", token));
@@ -193,7 +355,7 @@ impl GitHubClient {
     }
 
     pub async fn list_plugins(&self) -> Result<Vec<PluginMetadata>> {
-        let url = format!("https://api.github.com/orgs/{}/repos", self.org);
+        let url = self.endpoints.org_repos_url(&self.org);
 
         let response = self.build_request(&url)
             .send()
@@ -211,35 +373,67 @@ impl GitHubClient {
         let mut plugins = Vec::new();
 
         for repo in plugin_repos {
-            if let Ok(manifest) = self.fetch_plugin_manifest(&repo.name).await {
+            if let Ok((manifest, branch)) = self.fetch_plugin_manifest(&repo.name).await {
+                if let Some(cache_dir) = &self.cover_cache {
+                    self.cache_cover(cache_dir, &repo.name, branch).await;
+                }
                 plugins.push(build_plugin_metadata(repo, manifest));
             }
         }
 
+        if let Some(cache_dir) = &self.cover_cache {
+            covers::prune_cache(cache_dir);
+        }
+
         Ok(plugins)
     }
 
-    async fn fetch_plugin_manifest(&self, repo_name: &str) -> Result<crate::plugins::PluginManifest> {
+    async fn fetch_plugin_manifest(
+        &self,
+        repo_name: &str,
+    ) -> Result<(crate::plugins::PluginManifest, &'static str)> {
         for branch in ["main", "master"] {
-            let url = format!(
-                "https://raw.githubusercontent.com/{}/{}/{}/plugin.toml",
-                self.org, repo_name, branch
-            );
+            let url = self.endpoints.manifest_url(&self.org, repo_name, branch);
 
             let response = self.build_request(&url).send().await?;
             if response.status().is_success() {
                 let content = response.text().await?;
                 let manifest: crate::plugins::PluginManifest = toml::from_str(&content)?;
-                return Ok(manifest);
+                return Ok((manifest, branch));
             }
         }
 
         anyhow::bail!("plugin.toml not found on main or master branch")
     }
 
+    async fn cache_cover(&self, cache_dir: &std::path::Path, repo_name: &str, branch: &str) {
+        let url = self.endpoints.raw_file_url(&self.org, repo_name, branch, "cover.png");
+        let response = match self.build_request(&url).send().await {
+            Ok(response) if response.status().is_success() => response,
+            Ok(_) => return,
+            Err(e) => {
+                log::debug!("Failed to fetch cover for {}: {}", repo_name, e);
+                return;
+            }
+        };
+        let result = match response.bytes().await {
+            Ok(bytes) => covers::store_cover(cache_dir, repo_name, &bytes),
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = result {
+            log::warn!("Failed to cache cover for {}: {}", repo_name, e);
+        }
+    }
+
     pub async fn list_plugins_cached(&self, force_refresh: bool) -> Result<Vec<PluginMetadata>> {
+        if self.offline {
+            log::info!("Offline mode, serving plugin list from cache only");
+            let cached = read_cache().map(|cache| cache.plugins.into_iter().map(PluginMetadata::from).collect());
+            return Ok(cached.unwrap_or_default());
+        }
+
         if !force_refresh {
-            if let Some(plugins) = get_valid_cache() {
+            if let Some(plugins) = get_valid_cache(cache_ttl_secs()) {
                 return Ok(plugins);
             }
         }
@@ -271,6 +465,8 @@ fn build_plugin_metadata(repo: &GitHubRepo, manifest: crate::plugins::PluginMani
         version: manifest.plugin.version,
         repo_url: repo.html_url.clone(),
         platforms: manifest.plugin.platforms,
+        author: manifest.plugin.author,
+        homepage: manifest.plugin.homepage,
     }
 }
 
@@ -282,6 +478,8 @@ pub struct PluginMetadata {
     pub version: String,
     pub repo_url: String,
     pub platforms: Option<Vec<String>>,
+    pub author: Option<String>,
+    pub homepage: Option<String>,
 }
 
 impl PluginMetadata {
@@ -309,7 +507,9 @@ mod tests {
                 description: "Test plugin".to_string(),
                 version: version.to_string(),
                 author: None,
+                homepage: None,
                 platforms: None,
+                run: None,
             },
             menu: MenuConfig {
                 label: "Test".to_string(),
@@ -318,6 +518,160 @@ mod tests {
             },
             daemon: None,
             dependencies: None,
+            ui: None,
+            config_schema: None,
+            task_actions: vec![],
+        }
+    }
+
+    fn make_cache(timestamp: u64) -> PluginCache {
+        PluginCache {
+            timestamp,
+            plugins: vec![CachedPlugin {
+                id: "plugin-demo".to_string(),
+                name: "Demo".to_string(),
+                description: "Demo plugin".to_string(),
+                version: "1.0.0".to_string(),
+                repo_url: "https://github.com/qol-tools/plugin-demo".to_string(),
+                platforms: None,
+                author: None,
+                homepage: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn reconcile_versions_bumps_cache_behind_installed() {
+        let mut cache = make_cache(1000);
+        cache.plugins.push(CachedPlugin {
+            id: "plugin-other".to_string(),
+            version: "2.0.0".to_string(),
+            ..cache.plugins[0].clone()
+        });
+        let installed = HashMap::from([
+            ("plugin-demo".to_string(), "1.2.0".to_string()),
+            ("plugin-other".to_string(), "1.5.0".to_string()),
+            ("plugin-local".to_string(), "9.9.9".to_string()),
+        ]);
+
+        let reconciled = reconcile_versions(&mut cache, &installed);
+
+        assert_eq!(reconciled, vec!["plugin-demo".to_string()]);
+        assert_eq!(cache.plugins[0].version, "1.2.0");
+        assert_eq!(cache.plugins[1].version, "2.0.0");
+        assert_eq!(cache.plugins.len(), 2);
+    }
+
+    #[test]
+    fn fresh_cache_plugins_respects_ttl() {
+        let cases = [
+            (1000, 1000, 60, true),
+            (1000, 1059, 60, true),
+            (1000, 1060, 60, false),
+            (1000, 5000, 7200, true),
+            (1000, 1000, 0, false),
+            (2000, 1000, 60, true),
+        ];
+
+        for (timestamp, now, ttl, expected_fresh) in cases {
+            let result = fresh_cache_plugins(make_cache(timestamp), now, ttl);
+            assert_eq!(result.is_some(), expected_fresh, "timestamp={} now={} ttl={}", timestamp, now, ttl);
+        }
+    }
+
+    #[test]
+    fn is_valid_org_cases() {
+        let max_len = "a".repeat(39);
+        let too_long = "a".repeat(40);
+        let cases = [
+            ("qol-tools", true),
+            ("Acme42", true),
+            ("a", true),
+            ("", false),
+            ("-acme", false),
+            ("acme-", false),
+            ("ac--me", false),
+            ("acme/evil", false),
+            ("acme.org", false),
+            (max_len.as_str(), true),
+            (too_long.as_str(), false),
+        ];
+
+        for (org, expected) in cases {
+            assert_eq!(is_valid_org(org), expected, "org: {:?}", org);
+        }
+    }
+
+    #[test]
+    fn configured_org_flows_into_list_and_clone_urls() {
+        let endpoints = GitHubEndpoints::default();
+        let cases = [
+            ("acme-internal", "acme-internal"),
+            ("qol-tools", "qol-tools"),
+            ("../evil", "qol-tools"),
+        ];
+
+        for (configured, expected) in cases {
+            let org = resolve_plugin_org(configured.to_string());
+            assert_eq!(endpoints.org_repos_url(&org), format!("https://api.github.com/orgs/{}/repos", expected));
+            assert_eq!(
+                endpoints.clone_url(&org, "plugin-demo"),
+                format!("https://github.com/{}/plugin-demo.git", expected)
+            );
+        }
+    }
+
+    #[test]
+    fn configured_bases_build_repos_manifest_and_release_urls() {
+        let endpoints = GitHubEndpoints::resolve(
+            "https://ghe.example.com/api/v3/",
+            "https://ghe.example.com/raw",
+            "https://ghe.example.com",
+        );
+
+        assert_eq!(endpoints.org_repos_url("acme"), "https://ghe.example.com/api/v3/orgs/acme/repos");
+        assert_eq!(
+            endpoints.manifest_url("acme", "plugin-demo", "main"),
+            "https://ghe.example.com/raw/acme/plugin-demo/main/plugin.toml"
+        );
+        assert_eq!(
+            endpoints.raw_file_url("acme", "plugin-demo", "master", "cover.png"),
+            "https://ghe.example.com/raw/acme/plugin-demo/master/cover.png"
+        );
+        assert_eq!(
+            endpoints.latest_release_url("acme/tool"),
+            "https://ghe.example.com/api/v3/repos/acme/tool/releases/latest"
+        );
+        assert_eq!(endpoints.clone_url("acme", "plugin-demo"), "https://ghe.example.com/acme/plugin-demo.git");
+    }
+
+    #[test]
+    fn non_https_bases_fall_back_to_defaults() {
+        let cases = [
+            "http://ghe.example.com/api/v3",
+            "ftp://ghe.example.com",
+            "ghe.example.com",
+            "",
+        ];
+
+        for base in cases {
+            assert_eq!(GitHubEndpoints::resolve(base, base, base), GitHubEndpoints::default(), "base: {:?}", base);
+        }
+    }
+
+    #[test]
+    fn resolve_cache_ttl_cases() {
+        let cases = [
+            (None, None, DEFAULT_CACHE_TTL_SECS),
+            (None, Some(600), 600),
+            (Some("120"), Some(600), 120),
+            (Some(" 30 "), None, 30),
+            (Some("soon"), Some(600), 600),
+            (Some(""), None, DEFAULT_CACHE_TTL_SECS),
+        ];
+
+        for (env_value, configured, expected) in cases {
+            assert_eq!(resolve_cache_ttl(env_value, configured), expected, "env={:?} configured={:?}", env_value, configured);
         }
     }
 
@@ -398,6 +752,8 @@ mod tests {
             version: "1.0.0".to_string(),
             repo_url: "https://example.com".to_string(),
             platforms: platforms.map(|p| p.into_iter().map(String::from).collect()),
+            author: None,
+            homepage: None,
         }
     }
 
@@ -429,6 +785,8 @@ mod tests {
             version: "1.2.3".to_string(),
             repo_url: "https://github.com/test/plugin-test".to_string(),
             platforms: Some(vec!["linux".to_string()]),
+            author: Some("Jane".to_string()),
+            homepage: Some("https://example.com/plugin-test".to_string()),
         };
 
         let cached: CachedPlugin = metadata.clone().into();
@@ -440,5 +798,64 @@ mod tests {
         assert_eq!(back.version, metadata.version);
         assert_eq!(back.repo_url, metadata.repo_url);
         assert_eq!(back.platforms, metadata.platforms);
+        assert_eq!(back.author, metadata.author);
+        assert_eq!(back.homepage, metadata.homepage);
+    }
+
+    #[test]
+    fn cache_json_roundtrips_author_and_homepage() {
+        let cases = [
+            (None, None),
+            (Some("Jane"), None),
+            (Some("Jane"), Some("https://example.com/demo")),
+        ];
+
+        for (author, homepage) in cases {
+            let mut cache = make_cache(1000);
+            cache.plugins[0].author = author.map(String::from);
+            cache.plugins[0].homepage = homepage.map(String::from);
+
+            let json = serde_json::to_string(&cache).unwrap();
+            let back: PluginCache = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(back.plugins[0].author.as_deref(), author);
+            assert_eq!(back.plugins[0].homepage.as_deref(), homepage);
+        }
+    }
+
+    #[test]
+    fn cache_without_author_and_homepage_still_parses() {
+        let json = r#"{"timestamp":1000,"plugins":[{"id":"plugin-demo","name":"Demo","description":"Demo plugin","version":"1.0.0","repo_url":"https://github.com/qol-tools/plugin-demo"}]}"#;
+
+        let cache: PluginCache = serde_json::from_str(json).unwrap();
+
+        assert!(cache.plugins[0].author.is_none());
+        assert!(cache.plugins[0].homepage.is_none());
+    }
+
+    #[test]
+    fn build_plugin_metadata_carries_author_and_homepage() {
+        let mut manifest = make_manifest("Demo", "1.0.0");
+        manifest.plugin.author = Some("Jane".to_string());
+        manifest.plugin.homepage = Some("https://example.com/demo".to_string());
+
+        let metadata = build_plugin_metadata(&make_repo("plugin-demo"), manifest);
+
+        assert_eq!(metadata.author.as_deref(), Some("Jane"));
+        assert_eq!(metadata.homepage.as_deref(), Some("https://example.com/demo"));
+    }
+
+    #[tokio::test]
+    async fn offline_listing_never_hits_the_network() {
+        let unreachable = GitHubEndpoints::resolve("https://127.0.0.1:1", "https://127.0.0.1:1", "https://127.0.0.1:1");
+        let client = |offline: bool| GitHubClient {
+            endpoints: unreachable.clone(),
+            ..GitHubClient::new("test-org").with_offline(offline)
+        };
+
+        assert!(client(false).list_plugins_cached(true).await.is_err());
+        for force_refresh in [false, true] {
+            assert!(client(true).list_plugins_cached(force_refresh).await.is_ok(), "force_refresh={}", force_refresh);
+        }
     }
 }
@@ -0,0 +1,78 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+pub struct PluginLocks {
+    in_flight: Arc<Mutex<HashSet<String>>>,
+}
+
+pub struct PluginLockGuard {
+    in_flight: Arc<Mutex<HashSet<String>>>,
+    id: String,
+}
+
+impl PluginLocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn try_acquire(&self, id: &str) -> Option<PluginLockGuard> {
+        let mut in_flight = self.in_flight.lock().ok()?;
+        if !in_flight.insert(id.to_string()) {
+            return None;
+        }
+        Some(PluginLockGuard {
+            in_flight: Arc::clone(&self.in_flight),
+            id: id.to_string(),
+        })
+    }
+}
+
+impl Drop for PluginLockGuard {
+    fn drop(&mut self) {
+        match self.in_flight.lock() {
+            Ok(mut in_flight) => {
+                in_flight.remove(&self.id);
+            }
+            Err(e) => log::error!("Plugin lock mutex poisoned: {}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_acquisition_blocked_until_released() {
+        let locks = PluginLocks::new();
+
+        let guard = locks.try_acquire("plugin-a");
+        assert!(guard.is_some());
+        assert!(locks.try_acquire("plugin-a").is_none());
+
+        drop(guard);
+        assert!(locks.try_acquire("plugin-a").is_some());
+    }
+
+    #[test]
+    fn different_ids_do_not_block_each_other() {
+        let locks = PluginLocks::new();
+
+        let _a = locks.try_acquire("plugin-a").unwrap();
+        let _b = locks.try_acquire("plugin-b").unwrap();
+
+        assert!(locks.try_acquire("plugin-a").is_none());
+        assert!(locks.try_acquire("plugin-b").is_none());
+    }
+
+    #[test]
+    fn clones_share_lock_state() {
+        let locks = PluginLocks::new();
+        let clone = locks.clone();
+
+        let _guard = locks.try_acquire("plugin-a").unwrap();
+
+        assert!(clone.try_acquire("plugin-a").is_none());
+    }
+}
@@ -1,15 +1,28 @@
+mod bundle;
+mod covers;
+mod debounce;
+mod error;
 mod server;
 mod github;
 mod installer;
+mod locks;
 mod plugin_ui;
+mod watcher;
 
 use crate::daemon::Daemon;
 use crate::features::MenuProvider;
-use crate::plugins::{MenuItem as PluginMenuItem, PluginManager};
+use crate::instance::InstanceLock;
+use crate::menu::router::{EventHandler, EventPattern, EventRoute, HandlerResult};
+use crate::paths::is_safe_path_component;
+use crate::plugins::{ActionType, MenuItem as PluginMenuItem, PluginLoader, PluginManager};
 use anyhow::Result;
+use github::PluginMetadata;
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 
 const SERVER_PORT: u16 = 42700;
+const BROWSE_LIMIT: usize = 10;
+const INSTALL_EVENT: &str = "::install::";
 
 pub struct PluginStore;
 
@@ -29,23 +42,259 @@ impl PluginStore {
     }
 }
 
+pub async fn install_plugin(id: &str) -> Result<()> {
+    use installer::{remove_partial_install, PluginInstaller};
+
+    let _instance = claim_plugins_for_cli(id)?;
+    let installer = PluginInstaller::new(PluginLoader::ensure_plugin_dir()?);
+    let target_dir = installer.target_dir(id);
+    if target_dir.exists() {
+        anyhow::bail!("Plugin already installed: {}", id);
+    }
+
+    let org = github::configured_plugin_org();
+    let result = installer.install(&installer.repo_url(&org, id), id).await;
+    if result.is_err() {
+        remove_partial_install(&target_dir).await;
+    }
+    result
+}
+
+pub async fn update_plugin(id: &str) -> Result<()> {
+    let _instance = claim_plugins_for_cli(id)?;
+    let installer = installer::PluginInstaller::new(PluginLoader::default_plugin_dir()?);
+    if !installer.target_dir(id).exists() {
+        anyhow::bail!("Plugin not installed: {}", id);
+    }
+    installer.update(id, false).await
+}
+
+fn claim_plugins_for_cli(id: &str) -> Result<InstanceLock> {
+    if !is_safe_path_component(id) {
+        anyhow::bail!("Invalid plugin ID: {}", id);
+    }
+    if crate::http::offline() {
+        anyhow::bail!("Offline mode is enabled");
+    }
+    match InstanceLock::acquire(&crate::paths::instance_lock_path()?)? {
+        Some(lock) => Ok(lock),
+        None => anyhow::bail!("QoL Tray is running; manage plugins from its plugin store or quit it first"),
+    }
+}
+
+pub fn open_store() -> Result<()> {
+    crate::paths::open_url(&format!("http://127.0.0.1:{}", SERVER_PORT))
+}
+
+pub fn toggle_plugin_config(plugin_id: &str, key: &str, default: bool) -> Result<bool> {
+    server::toggle_config_from_menu(plugin_id, key, default)
+}
+
+pub fn open_plugin_settings(plugin_id: &str) -> Result<()> {
+    if !is_safe_path_component(plugin_id) {
+        anyhow::bail!("Invalid plugin ID: {}", plugin_id);
+    }
+    let has_ui = PluginLoader::default_plugin_dir()?
+        .join(plugin_id)
+        .join("ui")
+        .join("index.html")
+        .exists();
+    if !has_ui {
+        log::info!("Plugin {} has no UI, opening store", plugin_id);
+        return open_store();
+    }
+    crate::paths::open_url(&plugin_ui_url(plugin_id, SERVER_PORT))
+}
+
+fn plugin_ui_url(plugin_id: &str, port: u16) -> String {
+    format!("http://127.0.0.1:{}/plugins/{}/", port, plugin_id)
+}
+
+impl Default for PluginStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn run_action(id: String, label: String) -> PluginMenuItem {
+    PluginMenuItem::Action {
+        id,
+        label,
+        action: ActionType::Run,
+        config_key: None,
+        enabled_when: None,
+        icon: None,
+        command: None,
+    }
+}
+
+fn browse_menu_items(
+    cached: Option<Vec<PluginMetadata>>,
+    installed: &HashSet<String>,
+    limit: usize,
+) -> Vec<PluginMenuItem> {
+    let items: Vec<PluginMenuItem> = cached
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|plugin| !installed.contains(&plugin.id) && plugin.supports_current_platform())
+        .filter(|plugin| is_safe_path_component(&plugin.id))
+        .take(limit)
+        .map(|plugin| run_action(format!("install::{}", plugin.id), plugin.name))
+        .collect();
+
+    if items.is_empty() {
+        return vec![run_action("open_store".to_string(), "Open Plugin Store".to_string())];
+    }
+    items
+}
+
+fn cached_store_plugins() -> Option<Vec<PluginMetadata>> {
+    github::read_cache().map(|cache| cache.plugins.into_iter().map(PluginMetadata::from).collect())
+}
+
+fn installed_plugin_ids() -> HashSet<String> {
+    PluginLoader::default_plugin_dir()
+        .and_then(|dir| Ok(std::fs::read_dir(dir)?))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn install_route(feature_id: &str) -> Result<EventRoute> {
+    Ok(EventRoute {
+        pattern: EventPattern::glob(&format!("{}{}*", feature_id, INSTALL_EVENT))?,
+        handler: EventHandler::Async(Box::new(|event_id| {
+            let id = event_id.split_once(INSTALL_EVENT).map(|(_, id)| id.to_string()).unwrap_or_default();
+            Box::pin(install_from_menu(id))
+        })),
+    })
+}
+
+async fn install_from_menu(id: String) -> Result<HandlerResult> {
+    log::info!("Installing {} from tray menu", id);
+    match server::install_from_menu(id.clone()).await {
+        Ok(()) => {
+            log::info!("Installed {} from tray menu", id);
+            crate::menu::request_rebuild();
+        }
+        Err(e) => log::error!("Failed to install {} from tray menu: {}", id, e),
+    }
+    Ok(HandlerResult::Continue)
+}
+
 impl MenuProvider for PluginStore {
     fn menu_items(&self) -> Vec<PluginMenuItem> {
         vec![
-            PluginMenuItem::Action {
-                id: "plugin_store".to_string(),
-                label: "🔌 Plugin Store".to_string(),
-                action: crate::plugins::ActionType::Run,
-                config_key: None,
+            run_action("plugin_store".to_string(), "🔌 Plugin Store".to_string()),
+            PluginMenuItem::Submenu {
+                id: "browse_plugins".to_string(),
+                label: "Browse plugins…".to_string(),
+                items: browse_menu_items(cached_store_plugins(), &installed_plugin_ids(), BROWSE_LIMIT),
             },
         ]
     }
 
     fn handle_event(&self, event_id: &str) -> Result<()> {
         log::info!("PluginStore received event: {}", event_id);
-        if event_id.ends_with("::plugin_store") {
-            crate::paths::open_url(&format!("http://127.0.0.1:{}", SERVER_PORT))?;
+        if event_id.ends_with("::plugin_store") || event_id.ends_with("::open_store") {
+            open_store()?;
         }
         Ok(())
     }
+
+    fn routes(&self, feature_id: &str) -> Result<Vec<EventRoute>> {
+        Ok(vec![install_route(feature_id)?])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(id: &str, platforms: Option<Vec<&str>>) -> PluginMetadata {
+        PluginMetadata {
+            id: id.to_string(),
+            name: format!("{} name", id),
+            description: String::new(),
+            version: "1.0.0".to_string(),
+            repo_url: String::new(),
+            platforms: platforms.map(|p| p.into_iter().map(str::to_string).collect()),
+            author: None,
+            homepage: None,
+        }
+    }
+
+    fn item_ids(items: &[PluginMenuItem]) -> Vec<String> {
+        items
+            .iter()
+            .map(|item| match item {
+                PluginMenuItem::Action { id, .. } => id.clone(),
+                other => panic!("unexpected menu item: {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn browse_menu_items_cases() {
+        let installed: HashSet<String> = ["installed".to_string()].into();
+        let cached = vec![
+            metadata("alpha", None),
+            metadata("installed", None),
+            metadata("other-os", Some(vec!["beos"])),
+            metadata("beta", Some(vec![std::env::consts::OS])),
+            metadata("gamma", None),
+        ];
+
+        let cases = [
+            (Some(cached.clone()), 10, vec!["install::alpha", "install::beta", "install::gamma"]),
+            (Some(cached.clone()), 2, vec!["install::alpha", "install::beta"]),
+            (Some(vec![metadata("installed", None)]), 10, vec!["open_store"]),
+            (Some(vec![]), 10, vec!["open_store"]),
+            (None, 10, vec!["open_store"]),
+        ];
+
+        for (cached, limit, expected) in cases {
+            let items = browse_menu_items(cached, &installed, limit);
+            assert_eq!(item_ids(&items), expected, "limit: {}", limit);
+        }
+    }
+
+    #[test]
+    fn browse_menu_items_use_plugin_names_as_labels() {
+        let items = browse_menu_items(Some(vec![metadata("alpha", None)]), &HashSet::new(), 10);
+
+        assert!(matches!(&items[0], PluginMenuItem::Action { label, .. } if label == "alpha name"));
+    }
+
+    #[test]
+    fn install_route_matches_only_own_install_events() {
+        let route = install_route("feature_2").unwrap();
+        let cases = [
+            ("feature_2::install::alpha", true),
+            ("feature_2::install::", true),
+            ("feature_2::open_store", false),
+            ("feature_3::install::alpha", false),
+            ("feature_12::install::alpha", false),
+        ];
+
+        for (event_id, expected) in cases {
+            assert_eq!(route.pattern.matches(event_id), expected, "event: {}", event_id);
+        }
+    }
+
+    #[test]
+    fn plugin_ui_url_cases() {
+        let cases = [
+            ("plugin-launcher", 42700, "http://127.0.0.1:42700/plugins/plugin-launcher/"),
+            ("demo", 8080, "http://127.0.0.1:8080/plugins/demo/"),
+        ];
+
+        for (plugin_id, port, expected) in cases {
+            assert_eq!(plugin_ui_url(plugin_id, port), expected);
+        }
+    }
 }
@@ -1,12 +1,29 @@
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 use axum::{
     Router,
     extract::Path as AxumPath,
-    http::{header, StatusCode},
+    http::{header, HeaderName, StatusCode},
     response::{IntoResponse, Response},
     routing::get,
 };
 
+const ESCAPE_KEY_SCRIPT: &str = "document.addEventListener('keydown',e=>{if(e.key==='Escape'){e.preventDefault();window.location.href='/';}})";
+
+static DEFAULT_PLUGIN_CSP: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        "default-src 'self'; script-src 'self' '{}'; style-src 'self' 'unsafe-inline'; img-src 'self' data:; connect-src 'self'",
+        script_hash(ESCAPE_KEY_SCRIPT)
+    )
+});
+
+fn script_hash(script: &str) -> String {
+    use base64::Engine;
+    use sha2::{Digest, Sha256};
+
+    format!("sha256-{}", base64::engine::general_purpose::STANDARD.encode(Sha256::digest(script.as_bytes())))
+}
+
 pub fn router(plugins_dir: PathBuf) -> Router {
     Router::new()
         .route("/{plugin_id}", get(serve_plugin_index))
@@ -38,7 +55,74 @@ async fn serve_plugin_index(
     };
 
     let injected = inject_plugin_wrapper(&contents);
-    ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], injected).into_response()
+    let csp = plugin_csp(&plugins_dir, &plugin_id).await;
+    (plugin_headers("text/html; charset=utf-8", csp), injected).into_response()
+}
+
+fn plugin_headers(content_type: &str, csp: String) -> [(HeaderName, String); 3] {
+    [
+        (header::CONTENT_TYPE, content_type.to_string()),
+        (header::CONTENT_SECURITY_POLICY, csp),
+        (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+    ]
+}
+
+async fn plugin_csp(plugins_dir: &Path, plugin_id: &str) -> String {
+    let manifest_path = plugins_dir.join(plugin_id).join("plugin.toml");
+    let custom = tokio::fs::read_to_string(&manifest_path)
+        .await
+        .ok()
+        .and_then(|content| toml::from_str::<crate::plugins::PluginManifest>(&content).ok())
+        .and_then(|manifest| manifest.ui)
+        .and_then(|ui| ui.csp)
+        .filter(|csp| is_valid_header_value(csp));
+
+    custom.map_or_else(|| DEFAULT_PLUGIN_CSP.clone(), |csp| allow_escape_script(&csp))
+}
+
+fn allow_escape_script(csp: &str) -> String {
+    let hash = format!("'{}'", script_hash(ESCAPE_KEY_SCRIPT));
+    let directives: Vec<(&str, &str)> = csp
+        .split(';')
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .map(|directive| directive.split_once(char::is_whitespace).unwrap_or((directive, "")))
+        .collect();
+    let sources_of = |name: &str| {
+        directives
+            .iter()
+            .find(|(directive, _)| directive.eq_ignore_ascii_case(name))
+            .map(|(_, sources)| sources.trim())
+    };
+
+    match (sources_of("script-src"), sources_of("default-src")) {
+        (Some(sources), _) if accepts_script_hash(sources) => directives
+            .iter()
+            .map(|(name, sources)| {
+                if name.eq_ignore_ascii_case("script-src") {
+                    format!("{} {} {}", name, sources.trim(), hash)
+                } else {
+                    format!("{} {}", name, sources.trim()).trim_end().to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+        (None, Some(sources)) if accepts_script_hash(sources) => {
+            format!("{}; script-src {} {}", csp.trim().trim_end_matches(';'), sources, hash)
+        }
+        _ => csp.to_string(),
+    }
+}
+
+fn accepts_script_hash(sources: &str) -> bool {
+    let tokens: Vec<&str> = sources.split_whitespace().collect();
+    let has = |source: &str| tokens.iter().any(|token| token.eq_ignore_ascii_case(source));
+    let restricts_inline = tokens.iter().any(|token| token.starts_with("'sha") || token.starts_with("'nonce-"));
+    !has("'none'") && (!has("'unsafe-inline'") || restricts_inline)
+}
+
+fn is_valid_header_value(value: &str) -> bool {
+    !value.trim().is_empty() && axum::http::HeaderValue::from_str(value).is_ok()
 }
 
 fn inject_plugin_wrapper(html: &str) -> String {
@@ -49,11 +133,11 @@ fn inject_plugin_wrapper(html: &str) -> String {
 
     const NAV_FOOTER: &str = r#"<div id="qol-plugin-footer" style="position:fixed;bottom:0;left:0;right:0;background:#1a1a1a;border-top:1px solid #333;padding:0.5rem;text-align:center;color:#666;font-size:0.85rem;z-index:9999;font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',Roboto,sans-serif">
 Esc back
-</div>
-<script>document.addEventListener('keydown',e=>{if(e.key==='Escape'){e.preventDefault();window.location.href='/';}})</script>"#;
+</div>"#;
 
+    let footer = format!("{}\n<script>{}</script>", NAV_FOOTER, ESCAPE_KEY_SCRIPT);
     let with_header = inject_after_body_tag(html, NAV_HEADER);
-    inject_before_closing_body(&with_header, NAV_FOOTER)
+    inject_before_closing_body(&with_header, &footer)
 }
 
 fn inject_after_body_tag(html: &str, content: &str) -> String {
@@ -163,7 +247,8 @@ async fn serve_file(plugins_dir: &Path, plugin_id: &str, file_path: &str) -> Res
 
     let mime = guess_mime(&ui_path);
     log::debug!("Serving {:?} as {}", ui_path, mime);
-    ([(header::CONTENT_TYPE, mime)], contents).into_response()
+    let csp = plugin_csp(plugins_dir, plugin_id).await;
+    (plugin_headers(mime, csp), contents).into_response()
 }
 
 use crate::paths::is_safe_path_component;
@@ -237,6 +322,102 @@ mod tests {
         }
     }
 
+    fn setup_plugin_ui(manifest: Option<&str>) -> tempfile::TempDir {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let ui_dir = tmp.path().join("test-plugin").join("ui");
+        std::fs::create_dir_all(&ui_dir).unwrap();
+        std::fs::write(ui_dir.join("app.js"), "console.log('hi');").unwrap();
+        if let Some(manifest) = manifest {
+            std::fs::write(tmp.path().join("test-plugin").join("plugin.toml"), manifest).unwrap();
+        }
+        tmp
+    }
+
+    #[tokio::test]
+    async fn served_plugin_file_has_security_headers() {
+        let tmp = setup_plugin_ui(None);
+
+        let response = serve_file(tmp.path(), "test-plugin", "app.js").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(headers[header::CONTENT_SECURITY_POLICY], DEFAULT_PLUGIN_CSP.as_str());
+        assert_eq!(headers[header::X_CONTENT_TYPE_OPTIONS], "nosniff");
+    }
+
+    #[tokio::test]
+    async fn served_plugin_file_uses_manifest_csp() {
+        let hash = script_hash(ESCAPE_KEY_SCRIPT);
+        let cases = [
+            (
+                "default-src 'self' https://cdn.example.com",
+                format!("default-src 'self' https://cdn.example.com; script-src 'self' https://cdn.example.com '{}'", hash),
+            ),
+            ("", DEFAULT_PLUGIN_CSP.clone()),
+            ("bad\nvalue", DEFAULT_PLUGIN_CSP.clone()),
+        ];
+
+        for (csp, expected) in cases {
+            let manifest = format!(
+                r#"[plugin]
+name = "T"
+description = ""
+version = "1.0.0"
+
+[menu]
+label = "T"
+items = []
+
+[ui]
+csp = {:?}
+"#,
+                csp
+            );
+            let tmp = setup_plugin_ui(Some(&manifest));
+
+            let response = serve_file(tmp.path(), "test-plugin", "app.js").await;
+
+            assert_eq!(response.headers()[header::CONTENT_SECURITY_POLICY], expected.as_str(), "csp: {:?}", csp);
+        }
+    }
+
+    #[test]
+    fn default_csp_allows_only_the_injected_script() {
+        let script_src = DEFAULT_PLUGIN_CSP
+            .split(';')
+            .map(str::trim)
+            .find(|directive| directive.starts_with("script-src"))
+            .unwrap();
+
+        assert!(!script_src.contains("'unsafe-inline'"), "{}", script_src);
+        assert!(script_src.contains(&format!("'{}'", script_hash(ESCAPE_KEY_SCRIPT))), "{}", script_src);
+        let injected = inject_plugin_wrapper("<html><body></body></html>");
+        assert!(injected.contains(&format!("<script>{}</script>", ESCAPE_KEY_SCRIPT)));
+    }
+
+    #[test]
+    fn allow_escape_script_cases() {
+        let hash = format!("'{}'", script_hash(ESCAPE_KEY_SCRIPT));
+        let cases = [
+            ("script-src 'self'; img-src *", format!("script-src 'self' {}; img-src *", hash)),
+            ("default-src 'self';", format!("default-src 'self'; script-src 'self' {}", hash)),
+            ("Script-Src https://cdn.example.com", format!("Script-Src https://cdn.example.com {}", hash)),
+            ("script-src 'self' 'nonce-abc' 'unsafe-inline'", format!("script-src 'self' 'nonce-abc' 'unsafe-inline' {}", hash)),
+            ("script-src 'self' 'unsafe-inline'", "script-src 'self' 'unsafe-inline'".to_string()),
+            ("default-src 'self'; script-src 'none'", "default-src 'self'; script-src 'none'".to_string()),
+            ("img-src *", "img-src *".to_string()),
+        ];
+
+        for (csp, expected) in cases {
+            assert_eq!(allow_escape_script(csp), expected, "csp: {:?}", csp);
+        }
+    }
+
+    #[test]
+    fn script_hash_matches_known_digest() {
+        assert_eq!(script_hash(""), "sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=");
+    }
+
     #[test]
     fn find_body_tag_end_cases() {
         let cases = [
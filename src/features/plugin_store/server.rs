@@ -1,27 +1,42 @@
+use super::bundle::{self, BundlePaths, ConfigBundle};
+use super::debounce::Debouncer;
+use super::covers;
+use super::error::{ApiError, ErrorCode};
+use super::locks::{PluginLockGuard, PluginLocks};
 use super::plugin_ui;
 
 use crate::paths::is_safe_path_component;
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::task::AbortHandle;
 use axum::{
-    extract::{Path, State},
-    response::IntoResponse,
+    extract::{DefaultBodyLimit, Path, Query, Request, State},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
     routing::{get, post},
     Json, Router,
-    http::{StatusCode, header},
+    http::{Method, StatusCode, header},
 };
 use serde::{Deserialize, Serialize};
 use tower_http::set_header::SetResponseHeaderLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::timeout::TimeoutLayer;
 use axum::http::HeaderValue;
 use anyhow::Result;
 use rust_embed::Embed;
 
-use crate::plugins::{PluginConfigManager, PluginLoader, PluginManager};
-use crate::daemon::{Daemon, DaemonEvent};
-#[cfg(feature = "dev")]
+use crate::plugins::schema::ConfigSchema;
+use crate::plugins::loader::PluginProblem;
+use crate::plugins::process_stats::{self, DaemonStats};
+use crate::plugins::{merge_config, DiskUsageCache, PluginConfigManager, PluginLoader, PluginManager};
+use crate::daemon::{Daemon, DaemonEvent, EventEnvelope, Replay};
 use crate::daemon::DiscoveryStatus;
-use crate::hotkeys::trigger_reload;
-#[cfg(feature = "dev")]
+use crate::hotkeys::{self, trigger_reload};
+use crate::notifications::{self, Notice};
+use crate::settings::AppSettings;
 use crate::dev;
 
 #[derive(Clone)]
@@ -29,8 +44,23 @@ struct AppState {
     plugins_dir: PathBuf,
     plugin_manager: Arc<Mutex<PluginManager>>,
     daemon: Daemon,
+    active_installs: Arc<Mutex<HashMap<String, AbortHandle>>>,
+    plugin_locks: PluginLocks,
+    started_at: Instant,
+    disk_usage: DiskUsageCache,
+    dev_tools: bool,
+    offline: bool,
+    reload_debouncer: Debouncer,
 }
 
+static SERVER_STATE: OnceLock<AppState> = OnceLock::new();
+
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(200);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const LONG_REQUEST_TIMEOUT: Duration = Duration::from_secs(600);
+const MAX_REQUEST_BODY: usize = 2 * 1024 * 1024;
+const MAX_IMPORT_BODY: usize = 16 * 1024 * 1024;
+
 #[derive(Embed)]
 #[folder = "ui/"]
 struct UiAssets;
@@ -41,13 +71,18 @@ struct PluginInfo {
     name: String,
     description: String,
     version: String,
+    author: Option<String>,
+    homepage: Option<String>,
+    repo_url: Option<String>,
     installed: bool,
+    has_cover: bool,
 }
 
 #[derive(Serialize)]
 struct PluginsResponse {
     plugins: Vec<PluginInfo>,
     cache_age_secs: Option<u64>,
+    offline: bool,
 }
 
 #[derive(Deserialize, Default)]
@@ -57,7 +92,7 @@ struct PluginsQuery {
 }
 
 #[derive(Serialize)]
-struct UninstallResult {
+struct ActionResult {
     success: bool,
     message: String,
 }
@@ -74,11 +109,16 @@ struct InstalledPlugin {
     name: String,
     description: String,
     version: String,
+    author: Option<String>,
+    homepage: Option<String>,
+    repo_url: Option<String>,
     has_cover: bool,
     has_ui: bool,
     available_version: Option<String>,
     update_available: bool,
     actions: Vec<PluginAction>,
+    size_bytes: u64,
+    git_ref: Option<super::installer::GitRef>,
 }
 
 #[derive(Deserialize)]
@@ -91,8 +131,20 @@ struct TokenStatus {
     has_token: bool,
 }
 
-async fn serve_embedded(Path(path): Path<String>) -> impl IntoResponse {
-    serve_embedded_file(&path)
+async fn serve_embedded(headers: axum::http::HeaderMap, Path(path): Path<String>) -> impl IntoResponse {
+    if UiAssets::get(&path).is_none() {
+        let accept = headers.get(header::ACCEPT).and_then(|v| v.to_str().ok());
+        if is_spa_route(&path, accept) {
+            return serve_embedded_file("index.html").into_response();
+        }
+    }
+    serve_embedded_file(&path).into_response()
+}
+
+fn is_spa_route(path: &str, accept: Option<&str>) -> bool {
+    let has_extension = std::path::Path::new(path).extension().is_some();
+    let wants_html = accept.is_some_and(|a| a.contains("text/html"));
+    !has_extension && wants_html
 }
 
 async fn serve_embedded_index() -> impl IntoResponse {
@@ -134,61 +186,216 @@ pub async fn start_ui_server(
         plugins_dir: plugins_dir.clone(),
         plugin_manager,
         daemon: daemon.clone(),
+        active_installs: Arc::new(Mutex::new(HashMap::new())),
+        plugin_locks: PluginLocks::new(),
+        started_at: Instant::now(),
+        disk_usage: DiskUsageCache::new(),
+        dev_tools: dev::tools_enabled(),
+        offline: crate::http::offline(),
+        reload_debouncer: Debouncer::new(RELOAD_DEBOUNCE),
     };
 
-    let api = Router::new()
+    let _ = SERVER_STATE.set(app_state.clone());
+
+    if app_state.dev_tools {
+        log::info!("Developer tools enabled");
+    }
+    if app_state.offline {
+        log::info!("Offline mode enabled, network features are disabled");
+    }
+
+    let watch_state = app_state.clone();
+    if let Err(e) = super::watcher::spawn_plugins_watcher(plugins_dir.clone(), move || {
+        reload_manager_and_notify(&watch_state)
+    }) {
+        log::warn!("Failed to watch plugins directory: {}", e);
+    }
+
+    let settings = AppSettings::load().unwrap_or_else(|e| {
+        log::warn!("Failed to load settings: {}", e);
+        AppSettings::default()
+    });
+
+    let api = with_cors(api_router(app_state), &settings.cors_origins);
+
+    let no_cache = SetResponseHeaderLayer::overriding(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("no-cache, no-store, must-revalidate"),
+    );
+
+    let task_runner = with_cors(
+        with_body_limit(super::super::task_runner::router(), MAX_REQUEST_BODY),
+        &settings.cors_origins,
+    );
+
+    let app = Router::new()
+        .nest("/api", api)
+        .nest("/api/task-runner", task_runner)
+        .nest("/plugins", with_body_limit(with_timeout(plugin_ui::router(plugins_dir), REQUEST_TIMEOUT), MAX_REQUEST_BODY))
+        .route("/", get(serve_embedded_index))
+        .route("/{*path}", get(serve_embedded))
+        .layer(no_cache);
+
+    if settings.unix_socket {
+        #[cfg(unix)]
+        {
+            let path = crate::paths::socket_path()?;
+            let listener = bind_unix_socket(&path)?;
+            log::info!("Serving API on unix socket {}", path.display());
+            spawn_server(listener, app);
+            return Ok(());
+        }
+        #[cfg(not(unix))]
+        log::warn!("unix_socket is not supported on this platform, falling back to TCP");
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:42700").await?;
+    spawn_server(listener, app);
+
+    Ok(())
+}
+
+fn api_router(app_state: AppState) -> Router {
+    let long_running = Router::new()
         .route("/plugins", get(list_plugins))
+        .route("/cache/refresh", post(refresh_cache))
+        .route("/install/{id}", post(install_plugin))
+        .route("/update/{id}", post(update_plugin));
+
+    let api = Router::new()
         .route("/installed", get(list_installed))
+        .route("/plugins/problems", get(list_plugin_problems))
         .route("/events", get(sse_handler))
         .route("/cover/{id}", get(serve_cover))
-        .route("/install/{id}", post(install_plugin))
-        .route("/update/{id}", post(update_plugin))
+        .route("/install/{id}/cancel", post(cancel_install))
         .route("/uninstall/{id}", post(uninstall_plugin))
+        .route("/plugins/{id}/reveal", post(reveal_plugin))
+        .route("/plugins/{id}/stats", get(get_plugin_stats))
+        .route("/plugins/{id}/daemon/restart", post(restart_plugin_daemon))
         .route("/plugins/{id}/config", get(get_plugin_config))
         .route("/plugins/{id}/config", axum::routing::put(set_plugin_config))
+        .route("/plugins/{id}/config", axum::routing::patch(patch_plugin_config))
+        .route("/plugins/{id}/config", axum::routing::delete(delete_plugin_config))
+        .route("/plugins/{id}/config/reset", post(reset_plugin_config))
         .route("/github-token", get(get_token_status))
         .route("/github-token", post(set_github_token))
         .route("/github-token", axum::routing::delete(delete_github_token))
         .route("/hotkeys", get(get_hotkeys))
         .route("/hotkeys", axum::routing::put(set_hotkeys))
+        .route("/hotkeys/status", get(get_hotkey_status))
+        .route("/hotkeys/keys", get(get_hotkey_keys))
+        .route("/config/export", get(export_config))
+        .route("/hotkeys/suspend", post(suspend_hotkeys))
+        .route("/hotkeys/resume", post(resume_hotkeys))
         .route("/dev/enabled", get(dev_enabled))
-        .route("/version", get(get_version));
+        .route("/version", get(get_version))
+        .route("/health", get(get_health));
 
-    #[cfg(feature = "dev")]
-    let api = api
+    let dev_api = Router::new()
         .route("/dev/reload", post(reload_plugins))
         .route("/dev/links", get(list_linked_plugins))
         .route("/dev/links", post(create_link))
         .route("/dev/links/{id}", axum::routing::delete(delete_link))
+        .route("/dev/link-existing", post(link_existing))
         .route("/dev/discover", post(trigger_discovery))
-        .route("/dev/discovery-state", get(get_discovery_state));
+        .route("/dev/discovery-state", get(get_discovery_state))
+        .route("/dev/search-paths", get(get_search_paths))
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), require_dev_tools));
 
-    let api = api.with_state(app_state);
+    let import = Router::new().route("/config/import", post(import_config));
 
-    let no_cache = SetResponseHeaderLayer::overriding(
-        header::CACHE_CONTROL,
-        HeaderValue::from_static("no-cache, no-store, must-revalidate"),
-    );
+    let limited = with_timeout(api.merge(dev_api), REQUEST_TIMEOUT)
+        .merge(with_timeout(long_running, LONG_REQUEST_TIMEOUT));
+
+    with_body_limit(limited, MAX_REQUEST_BODY)
+        .merge(with_body_limit(with_timeout(import, REQUEST_TIMEOUT), MAX_IMPORT_BODY))
+        .with_state(app_state)
+}
 
-    let task_runner = super::super::task_runner::router();
+fn with_body_limit<S: Clone + Send + Sync + 'static>(router: Router<S>, limit: usize) -> Router<S> {
+    router
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(limit))
+}
 
-    let app = Router::new()
-        .nest("/api", api)
-        .nest("/api/task-runner", task_runner)
-        .nest("/plugins", plugin_ui::router(plugins_dir))
-        .route("/", get(serve_embedded_index))
-        .route("/{*path}", get(serve_embedded))
-        .layer(no_cache);
+fn with_cors<S: Clone + Send + Sync + 'static>(router: Router<S>, configured: &[String]) -> Router<S> {
+    let origins = allowed_cors_origins(configured);
+    if origins.is_empty() {
+        return router;
+    }
 
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:42700").await?;
+    let cors = CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
+        .allow_headers([header::CONTENT_TYPE, header::HeaderName::from_static("last-event-id")]);
+    router.layer(cors)
+}
+
+fn allowed_cors_origins(configured: &[String]) -> Vec<HeaderValue> {
+    configured
+        .iter()
+        .filter_map(|origin| {
+            let normalized = normalize_local_origin(origin);
+            if normalized.is_none() {
+                log::warn!("Ignoring CORS origin {:?}: only http(s) localhost origins are allowed", origin);
+            }
+            normalized
+        })
+        .filter_map(|origin| HeaderValue::from_str(&origin).ok())
+        .collect()
+}
+
+fn normalize_local_origin(origin: &str) -> Option<String> {
+    let url = reqwest::Url::parse(origin.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") || url.path() != "/" || url.query().is_some() {
+        return None;
+    }
+    if !url.username().is_empty() || url.password().is_some() {
+        return None;
+    }
+    let host = url.host_str()?;
+    if !matches!(host, "localhost" | "127.0.0.1" | "[::1]") {
+        return None;
+    }
+    Some(url.origin().ascii_serialization())
+}
+
+fn with_timeout<S: Clone + Send + Sync + 'static>(router: Router<S>, timeout: Duration) -> Router<S> {
+    router.layer(TimeoutLayer::with_status_code(StatusCode::REQUEST_TIMEOUT, timeout))
+}
+
+async fn require_dev_tools(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    if !state.dev_tools {
+        return ApiError::not_found("Developer tools are disabled").into_response();
+    }
+    next.run(request).await
+}
 
+fn spawn_server<L>(listener: L, app: Router)
+where
+    L: axum::serve::Listener,
+    L::Addr: std::fmt::Debug,
+{
     tokio::spawn(async move {
         if let Err(e) = axum::serve(listener, app).await {
             log::error!("UI server error: {}", e);
         }
     });
+}
 
-    Ok(())
+#[cfg(unix)]
+fn bind_unix_socket(path: &std::path::Path) -> Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    let listener = tokio::net::UnixListener::bind(path)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
 }
 
 fn get_installed_plugin_ids(plugins_dir: &std::path::Path) -> std::collections::HashSet<String> {
@@ -208,18 +415,23 @@ fn get_installed_plugin_ids(plugins_dir: &std::path::Path) -> std::collections::
 }
 
 async fn list_plugins(
+    State(state): State<AppState>,
     axum::extract::Query(query): axum::extract::Query<PluginsQuery>,
 ) -> Json<PluginsResponse> {
-    use super::github::{GitHubClient, cache_age_secs};
+    use super::github::{GitHubClient, cache_age_secs, configured_plugin_org};
+    use super::installer::normalize_repo_url;
 
     log::info!("API /plugins called (refresh={})", query.refresh);
 
-    let client = GitHubClient::new("qol-tools");
+    let cover_cache = covers::cache_dir();
+    let client = GitHubClient::new(configured_plugin_org())
+        .with_cover_cache(cover_cache.clone())
+        .with_offline(state.offline);
     let plugins_dir = match PluginLoader::default_plugin_dir() {
         Ok(dir) => dir,
         Err(e) => {
             log::error!("Failed to determine config directory: {}", e);
-            return Json(PluginsResponse { plugins: vec![], cache_age_secs: None });
+            return Json(PluginsResponse { plugins: vec![], cache_age_secs: None, offline: state.offline });
         }
     };
 
@@ -234,11 +446,15 @@ async fn list_plugins(
                 .into_iter()
                 .filter(|m| m.supports_current_platform())
                 .map(|m| PluginInfo {
-                    id: m.id.clone(),
+                    has_cover: covers::resolve_cover(&plugins_dir, cover_cache.as_deref(), &m.id).is_some(),
+                    installed: installed_plugins.contains(&m.id),
+                    id: m.id,
                     name: m.name,
                     description: m.description,
                     version: m.version,
-                    installed: installed_plugins.contains(&m.id),
+                    author: m.author,
+                    homepage: m.homepage,
+                    repo_url: normalize_repo_url(&m.repo_url),
                 })
                 .collect()
         }
@@ -251,35 +467,57 @@ async fn list_plugins(
     Json(PluginsResponse {
         plugins,
         cache_age_secs: cache_age,
+        offline: state.offline,
     })
 }
 
-async fn install_plugin(
-    Path(id): Path<String>,
-    State(state): State<AppState>,
-) -> Result<Json<PluginInfo>, (StatusCode, String)> {
-    use super::installer::PluginInstaller;
+#[derive(Serialize)]
+struct CacheRefreshResponse {
+    plugins: usize,
+    reconciled: Vec<String>,
+    cache_age_secs: Option<u64>,
+}
 
-    if !is_safe_path_component(&id) {
-        return Err((StatusCode::BAD_REQUEST, "Invalid plugin ID".to_string()));
-    }
+async fn refresh_cache(State(state): State<AppState>) -> Result<Json<CacheRefreshResponse>, ApiError> {
+    use super::github::{cache_age_secs, configured_plugin_org, reconcile_cached_versions, GitHubClient};
 
-    log::info!("Install requested for plugin: {}", id);
+    let plugins = GitHubClient::new(configured_plugin_org())
+        .with_cover_cache(covers::cache_dir())
+        .with_offline(state.offline)
+        .list_plugins_cached(true)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to refresh plugin list: {}", e);
+            ApiError::internal("Failed to refresh plugin list")
+        })?;
+
+    let installed_versions: HashMap<String, String> = get_installed_plugin_ids(&state.plugins_dir)
+        .into_iter()
+        .filter_map(|id| {
+            let version = read_plugin_version(&state.plugins_dir.join(&id)).ok()?;
+            Some((id, version))
+        })
+        .collect();
 
-    let plugins_dir = PluginLoader::ensure_plugin_dir().map_err(|e| {
-        log::error!("Failed to get plugins directory: {}", e);
-        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to access plugins directory".to_string())
+    let reconciled = reconcile_cached_versions(&installed_versions).map_err(|e| {
+        log::error!("Failed to reconcile plugin cache: {}", e);
+        ApiError::internal("Failed to reconcile plugin cache")
     })?;
 
-    let installer = PluginInstaller::new(plugins_dir.clone());
-    let repo_url = format!("https://github.com/qol-tools/{}.git", id);
+    reload_manager_and_notify(&state);
 
-    installer.install(&repo_url, &id).await.map_err(|e| {
-        log::error!("Failed to install plugin {}: {}", id, e);
-        (StatusCode::INTERNAL_SERVER_ERROR, "Installation failed".to_string())
-    })?;
+    Ok(Json(CacheRefreshResponse {
+        plugins: plugins.len(),
+        reconciled,
+        cache_age_secs: cache_age_secs(),
+    }))
+}
 
-    reload_manager_and_notify(&state);
+async fn install_plugin(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<PluginInfo>, ApiError> {
+    let plugins_dir = install(state, id.clone()).await.map_err(ApiError::into_install_failure)?;
 
     log::info!("Plugin {} installed successfully", id);
     let version = read_plugin_version(&plugins_dir.join(&id)).unwrap_or_else(|_| "unknown".into());
@@ -287,34 +525,208 @@ async fn install_plugin(
         id: id.clone(),
         name: id.clone(),
         description: "Installed successfully".to_string(),
+        author: None,
+        homepage: None,
+        repo_url: super::installer::origin_url(&plugins_dir.join(&id)).await,
+        has_cover: plugins_dir.join(&id).join("cover.png").is_file(),
         version,
         installed: true,
     }))
 }
 
+pub(super) async fn install_from_menu(id: String) -> Result<(), ApiError> {
+    let Some(state) = SERVER_STATE.get().cloned() else {
+        return Err(ApiError::internal("Plugin server is not running"));
+    };
+    install(state, id).await.map(|_| ())
+}
+
+pub(super) fn toggle_config_from_menu(plugin_id: &str, key: &str, default: bool) -> Result<bool> {
+    let toggled = PluginConfigManager::new()?.toggle_config_value(plugin_id, key, default)?;
+    if let Some(state) = SERVER_STATE.get() {
+        state.daemon.events.send(DaemonEvent::PluginConfigChanged { id: plugin_id.to_string() });
+    }
+    Ok(toggled)
+}
+
+async fn install(state: AppState, id: String) -> Result<PathBuf, ApiError> {
+    if !is_safe_path_component(&id) {
+        return Err(ApiError::invalid_id());
+    }
+
+    if state.offline {
+        return Err(ApiError::offline());
+    }
+
+    log::info!("Install requested for plugin: {}", id);
+
+    let lock = acquire_plugin_lock(&state, &id)?;
+
+    let plugins_dir = PluginLoader::ensure_plugin_dir().map_err(|e| {
+        log::error!("Failed to get plugins directory: {}", e);
+        ApiError::internal("Failed to access plugins directory")
+    })?;
+
+    if plugins_dir.join(&id).exists() {
+        return Err(ApiError::already_installed());
+    }
+
+    let task = tokio::spawn(run_install(state.clone(), plugins_dir.clone(), id.clone(), lock));
+    task.await.map_err(|e| {
+        log::error!("Install task for plugin {} failed: {}", id, e);
+        ApiError::internal("Installation failed")
+    })??;
+    Ok(plugins_dir)
+}
+
+async fn run_install(
+    state: AppState,
+    plugins_dir: PathBuf,
+    id: String,
+    _lock: PluginLockGuard,
+) -> Result<(), ApiError> {
+    use super::installer::{remove_partial_install, PluginInstaller};
+
+    let installer = PluginInstaller::new(plugins_dir);
+    let target_dir = installer.target_dir(&id);
+    let repo_url = installer.repo_url(&super::github::configured_plugin_org(), &id);
+
+    let task = {
+        let id = id.clone();
+        let events = state.daemon.events.clone();
+        tokio::spawn(async move {
+            installer
+                .install_with_progress(&repo_url, &id, |progress| {
+                    events.send(DaemonEvent::InstallProgress {
+                        id: id.clone(),
+                        stage: progress.stage,
+                        percent: progress.percent,
+                    });
+                })
+                .await
+        })
+    };
+    track_install(&state, &id, Some(task.abort_handle()));
+    let result = task.await;
+    track_install(&state, &id, None);
+
+    match result {
+        Ok(Ok(())) => {
+            reload_manager_and_notify(&state);
+            notifications::notify(Notice::PluginInstalled { id });
+            Ok(())
+        }
+        Ok(Err(e)) => {
+            log::error!("Failed to install plugin {}: {}", id, e);
+            remove_partial_install(&target_dir).await;
+            Err(ApiError::from_installer(&e, "Installation failed"))
+        }
+        Err(e) if e.is_cancelled() => {
+            log::info!("Install of plugin {} was cancelled", id);
+            remove_partial_install(&target_dir).await;
+            Err(ApiError::cancelled())
+        }
+        Err(e) => {
+            log::error!("Install task for plugin {} failed: {}", id, e);
+            remove_partial_install(&target_dir).await;
+            Err(ApiError::internal("Installation failed"))
+        }
+    }
+}
+
+fn acquire_plugin_lock(state: &AppState, id: &str) -> Result<PluginLockGuard, ApiError> {
+    state.plugin_locks.try_acquire(id).ok_or_else(|| {
+        log::warn!("Operation already in progress for plugin: {}", id);
+        ApiError::operation_in_progress()
+    })
+}
+
+fn track_install(state: &AppState, id: &str, handle: Option<AbortHandle>) {
+    let Ok(mut installs) = state.active_installs.lock() else {
+        log::error!("Active installs mutex poisoned");
+        return;
+    };
+    match handle {
+        Some(handle) => installs.insert(id.to_string(), handle),
+        None => installs.remove(id),
+    };
+}
+
+async fn cancel_install(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<ActionResult>, ApiError> {
+    if !is_safe_path_component(&id) {
+        return Err(ApiError::invalid_id());
+    }
+
+    let handle = state
+        .active_installs
+        .lock()
+        .map_err(|_| ApiError::internal("Active installs lock failed"))?
+        .remove(&id);
+
+    let Some(handle) = handle else {
+        return Err(ApiError::not_found("No install in progress"));
+    };
+
+    log::info!("Cancelling install of plugin: {}", id);
+    handle.abort();
+    Ok(Json(ActionResult {
+        success: true,
+        message: "Install cancelled".to_string(),
+    }))
+}
+
+#[derive(Deserialize)]
+struct UpdateQuery {
+    #[serde(default)]
+    force: bool,
+}
+
 async fn update_plugin(
     Path(id): Path<String>,
     State(state): State<AppState>,
-) -> Json<UninstallResult> {
+    Query(query): Query<UpdateQuery>,
+) -> Response {
+    match update(state, id, query.force).await {
+        Ok(result) => Json(result).into_response(),
+        Err(e) => e.into_action_failure(),
+    }
+}
+
+async fn update(state: AppState, id: String, force: bool) -> Result<ActionResult, ApiError> {
     use super::installer::PluginInstaller;
 
     if !is_safe_path_component(&id) {
-        return Json(UninstallResult {
-            success: false,
-            message: "Invalid plugin ID".to_string(),
-        });
+        return Err(ApiError::invalid_id());
+    }
+
+    if state.offline {
+        return Err(ApiError::offline());
     }
 
     log::info!("Update requested for plugin: {}", id);
 
+    let lock = acquire_plugin_lock(&state, &id)?;
+
     let installer = PluginInstaller::new(state.plugins_dir.clone());
 
-    if let Err(e) = installer.update(&id).await {
+    let task = {
+        let id = id.clone();
+        tokio::spawn(async move {
+            let _lock = lock;
+            installer.update(&id, force).await
+        })
+    };
+    let result = task.await.map_err(|e| {
+        log::error!("Update task for plugin {} failed: {}", id, e);
+        ApiError::internal("Update failed")
+    })?;
+
+    if let Err(e) = result {
         log::error!("Failed to update plugin {}: {}", id, e);
-        return Json(UninstallResult {
-            success: false,
-            message: "Update failed".to_string(),
-        });
+        return Err(ApiError::from_installer(&e, "Update failed"));
     }
 
     if let Ok(version) = read_plugin_version(&state.plugins_dir.join(&id)) {
@@ -322,9 +734,10 @@ async fn update_plugin(
     }
 
     reload_manager_and_notify(&state);
+    notifications::notify(Notice::PluginUpdated { id: id.clone() });
 
     log::info!("Plugin {} updated successfully", id);
-    Json(UninstallResult {
+    Ok(ActionResult {
         success: true,
         message: "Updated successfully".to_string(),
     })
@@ -337,8 +750,22 @@ fn read_plugin_version(plugin_dir: &std::path::Path) -> Result<String, ()> {
     Ok(manifest.plugin.version)
 }
 
+fn read_config_schema(plugin_dir: &std::path::Path) -> Option<ConfigSchema> {
+    let content = std::fs::read_to_string(plugin_dir.join("plugin.toml")).ok()?;
+    let manifest: crate::plugins::PluginManifest = toml::from_str(&content).ok()?;
+    manifest.config_schema
+}
+
 fn reload_manager_and_notify(state: &AppState) {
-    let mut manager = match state.plugin_manager.lock() {
+    let plugin_manager = Arc::clone(&state.plugin_manager);
+    let daemon = state.daemon.clone();
+    state
+        .reload_debouncer
+        .schedule(move || reload_and_notify(&plugin_manager, &daemon));
+}
+
+fn reload_and_notify(plugin_manager: &Mutex<PluginManager>, daemon: &Daemon) {
+    let mut manager = match plugin_manager.lock() {
         Ok(m) => m,
         Err(e) => {
             log::error!("Plugin manager mutex poisoned: {}", e);
@@ -348,79 +775,163 @@ fn reload_manager_and_notify(state: &AppState) {
     if let Err(e) = manager.reload_plugins() {
         log::error!("Failed to reload plugins: {}", e);
     }
-    state.daemon.events.send(DaemonEvent::PluginsChanged);
+    daemon.events.send(DaemonEvent::PluginsChanged);
 }
 
 async fn sse_handler(
+    headers: axum::http::HeaderMap,
     State(state): State<AppState>,
 ) -> impl IntoResponse {
     use axum::response::sse::{Event, KeepAlive, Sse};
     use tokio_stream::wrappers::BroadcastStream;
     use tokio_stream::StreamExt;
 
-    let rx = state.daemon.events.subscribe();
-    let stream = BroadcastStream::new(rx).filter_map(|result| {
-        result.ok().and_then(|event| {
-            serde_json::to_string(&event)
-                .ok()
-                .map(|json| Ok::<_, std::convert::Infallible>(Event::default().data(json)))
-        })
-    });
+    let last_seen = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok());
+    let (initial, rx) = initial_events(&state, last_seen);
+
+    let stream = tokio_stream::iter(initial.into_iter().map(Ok))
+        .chain(BroadcastStream::new(rx))
+        .filter_map(|result| {
+            sse_payload(result).map(|(id, json)| {
+                let event = Event::default().data(json);
+                Ok::<_, std::convert::Infallible>(match id {
+                    Some(id) => event.id(id.to_string()),
+                    None => event,
+                })
+            })
+        });
 
     Sse::new(stream).keep_alive(KeepAlive::default())
 }
 
+fn initial_events(
+    state: &AppState,
+    last_seen: Option<u64>,
+) -> (Vec<EventEnvelope>, tokio::sync::broadcast::Receiver<EventEnvelope>) {
+    let Some(last_seen) = last_seen else {
+        let (id, rx) = state.daemon.events.subscribe();
+        return (vec![EventEnvelope { id, event: snapshot_event(state) }], rx);
+    };
+
+    match state.daemon.events.subscribe_after(last_seen) {
+        (Replay::Events(events), rx) => (events, rx),
+        (Replay::Gap { missed, last_id }, rx) => {
+            log::info!("SSE client resumed after {} but {} events are gone, asking it to resync", last_seen, missed);
+            (vec![EventEnvelope { id: last_id, event: DaemonEvent::Resync { missed } }], rx)
+        }
+    }
+}
+
+fn snapshot_event(state: &AppState) -> DaemonEvent {
+    let mut plugins: Vec<String> = match state.plugin_manager.lock() {
+        Ok(manager) => manager.plugins().map(|p| p.id.clone()).collect(),
+        Err(e) => {
+            log::error!("Plugin manager mutex poisoned: {}", e);
+            Vec::new()
+        }
+    };
+    plugins.sort();
+
+    let (discovery, discovered, discovery_errors) = match state.daemon.state.discovery.read() {
+        Ok(discovery) => (discovery.status.clone(), discovery.plugins.clone(), discovery.errors.clone()),
+        Err(e) => {
+            log::error!("Discovery state lock poisoned: {}", e);
+            (DiscoveryStatus::Idle, Vec::new(), Vec::new())
+        }
+    };
+    DaemonEvent::Snapshot {
+        plugins,
+        discovery,
+        discovered,
+        discovery_errors,
+    }
+}
+
+fn sse_payload(
+    result: Result<EventEnvelope, tokio_stream::wrappers::errors::BroadcastStreamRecvError>,
+) -> Option<(Option<u64>, String)> {
+    use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+
+    let (id, event) = match result {
+        Ok(envelope) => (Some(envelope.id), envelope.event),
+        Err(BroadcastStreamRecvError::Lagged(missed)) => {
+            log::warn!("SSE client lagged behind by {} events, asking it to resync", missed);
+            (None, DaemonEvent::Resync { missed })
+        }
+    };
+    serde_json::to_string(&event).ok().map(|json| (id, json))
+}
+
 async fn uninstall_plugin(
     Path(id): Path<String>,
     State(state): State<AppState>,
-) -> Json<UninstallResult> {
+) -> Response {
+    match uninstall(state, id).await {
+        Ok(result) => Json(result).into_response(),
+        Err(e) => e.into_action_failure(),
+    }
+}
+
+async fn uninstall(state: AppState, id: String) -> Result<ActionResult, ApiError> {
     use super::installer::PluginInstaller;
 
     if !is_safe_path_component(&id) {
-        return Json(UninstallResult {
-            success: false,
-            message: "Invalid plugin ID".to_string(),
-        });
+        return Err(ApiError::invalid_id());
     }
 
     log::info!("Uninstall requested for plugin: {}", id);
 
+    let _lock = acquire_plugin_lock(&state, &id)?;
+
+    backup_plugin_config(&state, &id);
+
     let installer = PluginInstaller::new(state.plugins_dir.clone());
 
     if let Err(e) = installer.uninstall(&id).await {
         log::error!("Failed to uninstall plugin {}: {}", id, e);
-        return Json(UninstallResult {
-            success: false,
-            message: "Uninstall failed".to_string(),
-        });
+        return Err(ApiError::from_installer(&e, "Uninstall failed"));
     }
 
     reload_manager_and_notify(&state);
+    notifications::notify(Notice::PluginUninstalled { id: id.clone() });
 
     log::info!("Plugin {} uninstalled successfully", id);
-    Json(UninstallResult {
+    Ok(ActionResult {
         success: true,
         message: "Uninstalled successfully".to_string(),
     })
 }
 
+fn backup_plugin_config(state: &AppState, id: &str) {
+    let plugin_dir = state.plugins_dir.join(id);
+    let result = PluginConfigManager::new()
+        .and_then(|manager| manager.backup_live_config(id, &plugin_dir));
+
+    if let Err(e) = result {
+        log::warn!("Failed to back up config for plugin {} before uninstall: {}", id, e);
+    }
+}
+
 async fn list_installed(
     State(state): State<AppState>,
-) -> Result<Json<Vec<InstalledPlugin>>, StatusCode> {
+) -> Result<Json<Vec<InstalledPlugin>>, ApiError> {
     use super::github::read_cache;
-    use std::collections::HashMap;
-
-    let manager = state.plugin_manager.lock().map_err(|e| {
-        log::error!("Plugin manager mutex poisoned: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    use super::installer::{current_ref, origin_url};
 
     let cached_versions: HashMap<String, String> = read_cache()
         .map(|c| c.plugins.into_iter().map(|p| (p.id, p.version)).collect())
         .unwrap_or_default();
 
-    let plugins: Vec<InstalledPlugin> = manager.plugins()
-        .map(|plugin| {
+    let entries: Vec<(InstalledPlugin, PathBuf)> = {
+        let manager = state.plugin_manager.lock().map_err(|e| {
+            log::error!("Plugin manager mutex poisoned: {}", e);
+            ApiError::internal("Plugin manager lock failed")
+        })?;
+
+        manager.plugins().map(|plugin| {
             let cover_path = plugin.path.join("cover.png");
             let ui_path = plugin.path.join("ui").join("index.html");
             let available_version = cached_versions.get(&plugin.id).cloned();
@@ -431,51 +942,112 @@ async fn list_installed(
 
             let actions = extract_actions(&plugin.manifest.menu.items);
 
-            InstalledPlugin {
+            let info = InstalledPlugin {
                 id: plugin.id.clone(),
                 name: plugin.manifest.plugin.name.clone(),
                 description: plugin.manifest.plugin.description.clone(),
                 version: plugin.manifest.plugin.version.clone(),
+                author: plugin.manifest.plugin.author.clone(),
+                homepage: plugin.manifest.plugin.homepage.clone(),
+                repo_url: None,
                 has_cover: cover_path.exists(),
                 has_ui: ui_path.exists(),
                 available_version,
                 update_available,
                 actions,
-            }
+                size_bytes: state.disk_usage.size_of(&plugin.path),
+                git_ref: None,
+            };
+            (info, plugin.path.clone())
+        })
+        .collect()
+    };
+
+    let lookups: Vec<_> = entries
+        .iter()
+        .map(|(_, path)| {
+            let path = path.clone();
+            tokio::spawn(async move { tokio::join!(origin_url(&path), current_ref(&path)) })
         })
         .collect();
 
+    let mut plugins = Vec::with_capacity(entries.len());
+    for ((mut info, _), lookup) in entries.into_iter().zip(lookups) {
+        match lookup.await {
+            Ok((repo_url, git_ref)) => {
+                info.repo_url = repo_url;
+                info.git_ref = git_ref;
+            }
+            Err(e) => log::warn!("Git lookup for plugin {} failed: {}", info.id, e),
+        }
+        plugins.push(info);
+    }
+
     Ok(Json(plugins))
 }
 
-async fn dev_enabled() -> Json<bool> {
-    Json(cfg!(feature = "dev"))
+async fn list_plugin_problems(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<PluginProblem>>, ApiError> {
+    let problems = PluginLoader::find_problems(&state.plugins_dir).map_err(|e| {
+        log::error!("Failed to scan plugins directory: {}", e);
+        ApiError::internal("Failed to scan plugins directory")
+    })?;
+    Ok(Json(problems))
+}
+
+async fn dev_enabled(State(state): State<AppState>) -> Json<bool> {
+    Json(state.dev_tools)
 }
 
 async fn get_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
-#[cfg(feature = "dev")]
-async fn reload_plugins(State(state): State<AppState>) -> impl IntoResponse {
+#[derive(Serialize)]
+struct HealthResponse {
+    status: &'static str,
+    version: &'static str,
+    uptime_secs: u64,
+    plugins_loaded: usize,
+}
+
+async fn get_health(State(state): State<AppState>) -> Result<Json<HealthResponse>, ApiError> {
+    let plugins_loaded = state
+        .plugin_manager
+        .lock()
+        .map_err(|_| ApiError::internal("Plugin manager lock failed"))?
+        .plugins()
+        .count();
+
+    Ok(Json(HealthResponse {
+        status: "ok",
+        version: env!("CARGO_PKG_VERSION"),
+        uptime_secs: state.started_at.elapsed().as_secs(),
+        plugins_loaded,
+    }))
+}
+
+async fn reload_plugins(State(state): State<AppState>) -> Result<impl IntoResponse, ApiError> {
     log::info!("Developer reload requested");
-    let mut manager = match state.plugin_manager.lock() {
-        Ok(m) => m,
-        Err(e) => {
+    let plugin_manager = Arc::clone(&state.plugin_manager);
+    tokio::task::spawn_blocking(move || {
+        let mut manager = plugin_manager.lock().map_err(|e| {
             log::error!("Plugin manager mutex poisoned: {}", e);
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Plugin manager lock failed").into_response();
-        }
-    };
-    match manager.reload_plugins() {
-        Ok(_) => {
-            log::info!("Plugins reloaded successfully");
-            (StatusCode::OK, "Plugins reloaded").into_response()
-        }
-        Err(e) => {
+            ApiError::internal("Plugin manager lock failed")
+        })?;
+        manager.restart_plugins().map_err(|e| {
             log::error!("Failed to reload plugins: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed: {}", e)).into_response()
-        }
-    }
+            ApiError::internal(format!("Failed: {}", e))
+        })
+    })
+    .await
+    .map_err(|e| {
+        log::error!("Plugin reload task failed: {}", e);
+        ApiError::internal("Plugin reload failed")
+    })??;
+    log::info!("Plugins reloaded successfully");
+    Ok((StatusCode::OK, "Plugins reloaded"))
 }
 
 fn extract_actions(items: &[crate::plugins::MenuItem]) -> Vec<PluginAction> {
@@ -495,92 +1067,261 @@ fn is_newer_version(available: &str, installed: &str) -> bool {
     Version::parse(available).is_newer_than(&Version::parse(installed))
 }
 
-const MAX_COVER_SIZE: usize = 5 * 1024 * 1024;
+async fn reveal_plugin(
+    Path(plugin_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let plugin_dir = installed_plugin_dir(&state.plugins_dir, &plugin_id)?;
 
-async fn serve_cover(
+    crate::paths::open_path(&plugin_dir).map_err(|e| {
+        log::error!("Failed to reveal plugin {}: {:#}", plugin_id, e);
+        ApiError::internal("Failed to open file manager")
+    })?;
+
+    Ok((StatusCode::OK, "Plugin directory opened"))
+}
+
+async fn get_plugin_stats(
     Path(plugin_id): Path<String>,
     State(state): State<AppState>,
-) -> impl IntoResponse {
+) -> Result<impl IntoResponse, ApiError> {
     if !is_safe_path_component(&plugin_id) {
-        return (StatusCode::BAD_REQUEST, "Invalid plugin ID").into_response();
+        return Err(ApiError::invalid_id());
     }
 
-    let cover_path = state.plugins_dir.join(&plugin_id).join("cover.png");
+    let pid = {
+        let manager = state.plugin_manager.lock().map_err(|e| {
+            log::error!("Plugin manager mutex poisoned: {}", e);
+            ApiError::internal("Plugin manager lock failed")
+        })?;
+        let plugin = manager
+            .plugins()
+            .find(|plugin| plugin.id == plugin_id)
+            .ok_or_else(|| ApiError::not_found("Plugin not loaded"))?;
+        plugin.daemon_pid()
+    };
+
+    let stats = match pid {
+        Some(pid) => process_stats::sample(pid).await,
+        None => DaemonStats::NotRunning,
+    };
+    Ok(Json(stats))
+}
 
-    if !cover_path.exists() {
-        return (StatusCode::NOT_FOUND, "Cover not found").into_response();
+async fn restart_plugin_daemon(
+    Path(plugin_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !is_safe_path_component(&plugin_id) {
+        return Err(ApiError::invalid_id());
     }
 
-    let data = match tokio::fs::read(&cover_path).await {
-        Ok(data) => data,
-        Err(e) => {
-            log::error!("Failed to read cover image: {}", e);
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read cover").into_response();
-        }
+    let plugin_manager = Arc::clone(&state.plugin_manager);
+    let id = plugin_id.clone();
+    let pid = tokio::task::spawn_blocking(move || {
+        let mut manager = plugin_manager.lock().map_err(|e| {
+            log::error!("Plugin manager mutex poisoned: {}", e);
+            ApiError::internal("Plugin manager lock failed")
+        })?;
+        manager.restart_daemon(&id).map_err(|e| {
+            log::error!("Failed to restart daemon for {}: {}", id, e);
+            ApiError::from(e)
+        })
+    })
+    .await
+    .map_err(|e| {
+        log::error!("Daemon restart task failed: {}", e);
+        ApiError::internal("Daemon restart failed")
+    })??;
+
+    state.daemon.events.send(DaemonEvent::DaemonRestarted { id: plugin_id, pid });
+    Ok(Json(DaemonRestarted { pid }))
+}
+
+#[derive(Serialize)]
+struct DaemonRestarted {
+    pid: u32,
+}
+
+fn installed_plugin_dir(plugins_dir: &std::path::Path, plugin_id: &str) -> Result<PathBuf, ApiError> {
+    if !is_safe_path_component(plugin_id) {
+        return Err(ApiError::invalid_id());
+    }
+
+    let plugin_dir = plugins_dir.join(plugin_id);
+    if !plugin_dir.is_dir() {
+        return Err(ApiError::not_installed());
+    }
+    Ok(plugin_dir)
+}
+
+async fn serve_cover(
+    Path(plugin_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !is_safe_path_component(&plugin_id) {
+        return Err(ApiError::invalid_id());
+    }
+
+    let cache_dir = covers::cache_dir();
+    let Some(cover_path) = covers::resolve_cover(&state.plugins_dir, cache_dir.as_deref(), &plugin_id) else {
+        return Err(ApiError::not_found("Cover not found"));
     };
 
-    if data.len() > MAX_COVER_SIZE {
-        return (StatusCode::PAYLOAD_TOO_LARGE, "Cover image too large").into_response();
+    let data = tokio::fs::read(&cover_path).await.map_err(|e| {
+        log::error!("Failed to read cover image: {}", e);
+        ApiError::internal("Failed to read cover")
+    })?;
+
+    if data.len() > covers::MAX_COVER_SIZE {
+        return Err(ApiError::payload_too_large("Cover image too large"));
     }
 
-    (StatusCode::OK, [(header::CONTENT_TYPE, "image/png")], data).into_response()
+    Ok((StatusCode::OK, [(header::CONTENT_TYPE, "image/png")], data))
 }
 
 const MAX_CONFIG_SIZE: usize = 1024 * 1024;
 
-async fn get_plugin_config(Path(plugin_id): Path<String>) -> impl IntoResponse {
+async fn get_plugin_config(Path(plugin_id): Path<String>) -> Result<impl IntoResponse, ApiError> {
     if !is_safe_path_component(&plugin_id) {
-        return (StatusCode::BAD_REQUEST, "Invalid plugin ID").into_response();
+        return Err(ApiError::invalid_id());
     }
 
     let config = match PluginConfigManager::new().and_then(|m| m.get_config(&plugin_id)) {
         Ok(Some(config)) => config,
-        Ok(None) => return (StatusCode::NOT_FOUND, "Config not found").into_response(),
+        Ok(None) => return Err(ApiError::not_found("Config not found")),
         Err(e) => {
             log::error!("Failed to read config: {}", e);
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read config").into_response();
+            return Err(ApiError::internal("Failed to read config"));
         }
     };
 
-    match serde_json::to_vec(&config) {
-        Ok(data) => (StatusCode::OK, [(header::CONTENT_TYPE, "application/json")], data).into_response(),
-        Err(e) => {
-            log::error!("Failed to serialize config: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to serialize config").into_response()
-        }
-    }
+    let data = serde_json::to_vec(&config).map_err(|e| {
+        log::error!("Failed to serialize config: {}", e);
+        ApiError::internal("Failed to serialize config")
+    })?;
+
+    Ok((StatusCode::OK, [(header::CONTENT_TYPE, "application/json")], data))
 }
 
 async fn set_plugin_config(
     Path(plugin_id): Path<String>,
+    State(state): State<AppState>,
     body: axum::body::Bytes,
-) -> impl IntoResponse {
+) -> Result<impl IntoResponse, ApiError> {
     if !is_safe_path_component(&plugin_id) {
-        return (StatusCode::BAD_REQUEST, "Invalid plugin ID").into_response();
+        return Err(ApiError::invalid_id());
     }
 
+    let config = parse_config_body(&body)?;
+    save_plugin_config(&state, &plugin_id, config)?;
+    Ok((StatusCode::OK, "Config saved"))
+}
+
+async fn patch_plugin_config(
+    Path(plugin_id): Path<String>,
+    State(state): State<AppState>,
+    body: axum::body::Bytes,
+) -> Result<impl IntoResponse, ApiError> {
+    if !is_safe_path_component(&plugin_id) {
+        return Err(ApiError::invalid_id());
+    }
+
+    let patch = parse_config_body(&body)?;
+    if !patch.is_object() {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, ErrorCode::InvalidJson, "Config patch must be a JSON object"));
+    }
+
+    let mut config = PluginConfigManager::new()
+        .and_then(|m| m.get_config(&plugin_id))
+        .map_err(|e| {
+            log::error!("Failed to load config: {}", e);
+            ApiError::internal("Failed to load config")
+        })?
+        .unwrap_or_else(|| serde_json::json!({}));
+    merge_config(&mut config, patch);
+
+    save_plugin_config(&state, &plugin_id, config.clone())?;
+    Ok(Json(config))
+}
+
+fn parse_config_body(body: &[u8]) -> Result<serde_json::Value, ApiError> {
     if body.len() > MAX_CONFIG_SIZE {
-        return (StatusCode::PAYLOAD_TOO_LARGE, "Config too large").into_response();
+        return Err(ApiError::payload_too_large("Config too large"));
     }
 
-    let config: serde_json::Value = match serde_json::from_slice(&body) {
-        Ok(c) => c,
-        Err(e) => {
-            log::error!("Invalid JSON in config: {}", e);
-            return (StatusCode::BAD_REQUEST, "Invalid JSON").into_response();
-        }
-    };
+    serde_json::from_slice(body).map_err(|e| {
+        log::error!("Invalid JSON in config: {}", e);
+        ApiError::invalid_json()
+    })
+}
 
-    match PluginConfigManager::new().and_then(|m| m.set_config(&plugin_id, config)) {
-        Ok(()) => {
-            log::info!("Config saved for plugin: {}", plugin_id);
-            (StatusCode::OK, "Config saved").into_response()
-        }
-        Err(e) => {
+fn save_plugin_config(state: &AppState, plugin_id: &str, config: serde_json::Value) -> Result<(), ApiError> {
+    if let Some(schema) = read_config_schema(&state.plugins_dir.join(plugin_id)) {
+        schema.validate(&config).map_err(|fields| {
+            log::warn!("Config for plugin {} does not match schema: {:?}", plugin_id, fields);
+            ApiError::invalid_config(fields)
+        })?;
+    }
+
+    PluginConfigManager::new()
+        .and_then(|m| m.set_config(plugin_id, config))
+        .map_err(|e| {
             log::error!("Failed to save config: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to save config").into_response()
-        }
+            ApiError::internal("Failed to save config")
+        })?;
+
+    state.daemon.events.send(DaemonEvent::PluginConfigChanged { id: plugin_id.to_string() });
+    log::info!("Config saved for plugin: {}", plugin_id);
+    Ok(())
+}
+
+async fn delete_plugin_config(
+    Path(plugin_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    if !is_safe_path_component(&plugin_id) {
+        return Err(ApiError::invalid_id());
     }
+
+    let plugin_dir = state.plugins_dir.join(&plugin_id);
+    let deleted = PluginConfigManager::new()
+        .and_then(|m| m.delete_config(&plugin_id, &plugin_dir))
+        .map_err(|e| {
+            log::error!("Failed to delete config: {}", e);
+            ApiError::internal("Failed to delete config")
+        })?;
+
+    if !deleted {
+        return Err(ApiError::not_found("Config not found"));
+    }
+
+    state.daemon.events.send(DaemonEvent::PluginConfigChanged { id: plugin_id.clone() });
+    log::info!("Config deleted for plugin: {}", plugin_id);
+    Ok((StatusCode::OK, "Config deleted"))
+}
+
+async fn reset_plugin_config(
+    Path(plugin_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, ApiError> {
+    let plugin_dir = installed_plugin_dir(&state.plugins_dir, &plugin_id)?;
+
+    let restored_default = PluginConfigManager::new()
+        .and_then(|m| m.reset_config(&plugin_id, &plugin_dir))
+        .map_err(|e| {
+            log::error!("Failed to reset config: {}", e);
+            ApiError::internal("Failed to reset config")
+        })?;
+
+    state.daemon.events.send(DaemonEvent::PluginConfigChanged { id: plugin_id.clone() });
+    log::info!("Config reset for plugin: {} (default restored: {})", plugin_id, restored_default);
+    Ok(Json(ConfigReset { restored_default }))
+}
+
+#[derive(Serialize)]
+struct ConfigReset {
+    restored_default: bool,
 }
 
 async fn get_token_status() -> Json<TokenStatus> {
@@ -589,149 +1330,306 @@ async fn get_token_status() -> Json<TokenStatus> {
     })
 }
 
-async fn set_github_token(Json(payload): Json<TokenRequest>) -> impl IntoResponse {
-    if let Err(e) = super::github::store_token(&payload.token) {
+async fn set_github_token(Json(payload): Json<TokenRequest>) -> Result<impl IntoResponse, ApiError> {
+    super::github::store_token(&payload.token).map_err(|e| {
         log::error!("Failed to store GitHub token: {}", e);
-        return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to store token".to_string()).into_response();
-    }
+        ApiError::internal("Failed to store token")
+    })?;
 
     log::info!("GitHub token stored successfully");
-    (StatusCode::OK, "Token stored".to_string()).into_response()
+    Ok((StatusCode::OK, "Token stored"))
 }
 
-async fn delete_github_token() -> impl IntoResponse {
-    if let Err(e) = super::github::delete_token() {
+async fn delete_github_token() -> Result<impl IntoResponse, ApiError> {
+    super::github::delete_token().map_err(|e| {
         log::error!("Failed to delete GitHub token: {}", e);
-        return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to delete token".to_string()).into_response();
-    }
+        ApiError::internal("Failed to delete token")
+    })?;
 
     log::info!("GitHub token deleted");
-    (StatusCode::OK, "Token deleted".to_string()).into_response()
+    Ok((StatusCode::OK, "Token deleted"))
 }
 
-async fn get_hotkeys() -> impl IntoResponse {
+async fn get_hotkeys() -> Result<impl IntoResponse, ApiError> {
     use crate::hotkeys::HotkeyManager;
 
-    let manager = match HotkeyManager::new() {
-        Ok(m) => m,
-        Err(e) => {
-            log::error!("Failed to create HotkeyManager: {}", e);
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to load hotkeys").into_response();
-        }
-    };
+    let manager = HotkeyManager::new().map_err(|e| {
+        log::error!("Failed to create HotkeyManager: {}", e);
+        ApiError::internal("Failed to load hotkeys")
+    })?;
 
-    let config = match manager.load_config() {
-        Ok(c) => c,
-        Err(e) => {
-            log::error!("Failed to load hotkey config: {}", e);
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to load hotkeys").into_response();
-        }
-    };
+    let config = manager.load_config().map_err(|e| {
+        log::error!("Failed to load hotkey config: {}", e);
+        ApiError::internal("Failed to load hotkeys")
+    })?;
 
-    let json = match serde_json::to_vec(&config) {
-        Ok(j) => j,
-        Err(e) => {
-            log::error!("Failed to serialize hotkey config: {}", e);
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to serialize hotkeys").into_response();
-        }
-    };
+    let json = serde_json::to_vec(&config).map_err(|e| {
+        log::error!("Failed to serialize hotkey config: {}", e);
+        ApiError::internal("Failed to serialize hotkeys")
+    })?;
+
+    Ok((StatusCode::OK, [(header::CONTENT_TYPE, "application/json")], json))
+}
+
+#[derive(Serialize)]
+struct HotkeyModifier {
+    name: &'static str,
+    aliases: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct HotkeyKeys {
+    keys: Vec<&'static str>,
+    modifiers: Vec<HotkeyModifier>,
+}
+
+async fn get_hotkey_keys() -> Json<HotkeyKeys> {
+    let modifiers = hotkeys::modifier_aliases()
+        .into_iter()
+        .map(|(name, aliases)| HotkeyModifier { name, aliases })
+        .collect();
+    Json(HotkeyKeys {
+        keys: hotkeys::available_key_names(),
+        modifiers,
+    })
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+struct HotkeyWarning {
+    hotkey_id: String,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct HotkeysSaved {
+    warnings: Vec<HotkeyWarning>,
+}
+
+fn dangling_hotkey_warnings<'a>(
+    config: &crate::hotkeys::HotkeyConfig,
+    plugins: impl IntoIterator<Item = &'a crate::plugins::Plugin>,
+) -> Vec<HotkeyWarning> {
+    let actions: HashMap<&str, Vec<PluginAction>> = plugins
+        .into_iter()
+        .map(|plugin| (plugin.id.as_str(), extract_actions(&plugin.manifest.menu.items)))
+        .collect();
 
-    (StatusCode::OK, [(header::CONTENT_TYPE, "application/json")], json).into_response()
+    config
+        .hotkeys
+        .iter()
+        .filter_map(|binding| {
+            let message = match actions.get(binding.plugin_id.as_str()) {
+                None => format!("Plugin {} is not installed", binding.plugin_id),
+                Some(plugin_actions) if !plugin_actions.iter().any(|a| a.id == binding.action) => {
+                    format!("Plugin {} has no action {}", binding.plugin_id, binding.action)
+                }
+                Some(_) => return None,
+            };
+            Some(HotkeyWarning {
+                hotkey_id: binding.id.clone(),
+                message,
+            })
+        })
+        .collect()
 }
 
-async fn set_hotkeys(body: axum::body::Bytes) -> impl IntoResponse {
+async fn set_hotkeys(
+    State(state): State<AppState>,
+    body: axum::body::Bytes,
+) -> Result<Json<HotkeysSaved>, ApiError> {
     use crate::hotkeys::{HotkeyConfig, HotkeyManager};
 
-    let config: HotkeyConfig = match serde_json::from_slice(&body) {
-        Ok(c) => c,
-        Err(e) => {
-            log::error!("Invalid hotkey config JSON: {}", e);
-            return (StatusCode::BAD_REQUEST, "Invalid JSON").into_response();
-        }
-    };
+    let config: HotkeyConfig = serde_json::from_slice(&body).map_err(|e| {
+        log::error!("Invalid hotkey config JSON: {}", e);
+        ApiError::invalid_json()
+    })?;
 
-    let manager = match HotkeyManager::new() {
-        Ok(m) => m,
-        Err(e) => {
-            log::error!("Failed to create HotkeyManager: {}", e);
-            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to save hotkeys").into_response();
-        }
+    let warnings = {
+        let plugin_manager = state.plugin_manager.lock().map_err(|e| {
+            log::error!("Plugin manager mutex poisoned: {}", e);
+            ApiError::internal("Plugin manager lock failed")
+        })?;
+        dangling_hotkey_warnings(&config, plugin_manager.plugins())
     };
+    for warning in &warnings {
+        log::warn!("Hotkey {}: {}", warning.hotkey_id, warning.message);
+    }
 
-    if let Err(e) = manager.save_config(&config) {
+    let manager = HotkeyManager::new().map_err(|e| {
+        log::error!("Failed to create HotkeyManager: {}", e);
+        ApiError::internal("Failed to save hotkeys")
+    })?;
+
+    manager.save_config(&config).map_err(|e| {
         log::error!("Failed to save hotkey config: {}", e);
-        return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to save hotkeys").into_response();
-    }
+        ApiError::internal("Failed to save hotkeys")
+    })?;
 
     trigger_reload();
+    state.daemon.events.send(DaemonEvent::HotkeysChanged);
     log::info!("Hotkey config saved");
-    (StatusCode::OK, "Hotkeys saved").into_response()
+    Ok(Json(HotkeysSaved { warnings }))
+}
+
+#[derive(Deserialize)]
+struct ExportQuery {
+    #[serde(default)]
+    include_token: bool,
+}
+
+#[derive(Serialize)]
+struct ImportResult {
+    installing: Vec<String>,
+    skipped: Vec<String>,
+}
+
+async fn export_config(
+    axum::extract::Query(query): axum::extract::Query<ExportQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<ConfigBundle>, ApiError> {
+    let mut bundle = BundlePaths::new()
+        .and_then(|paths| bundle::export(&paths, &state.plugins_dir))
+        .map_err(|e| {
+            log::error!("Failed to export config: {}", e);
+            ApiError::internal("Failed to export config")
+        })?;
+
+    if query.include_token {
+        bundle.github_token = super::github::get_stored_token();
+    }
+
+    log::info!("Config exported with {} plugins", bundle.plugins.len());
+    Ok(Json(bundle))
+}
+
+async fn import_config(
+    State(state): State<AppState>,
+    body: axum::body::Bytes,
+) -> Result<Json<ImportResult>, ApiError> {
+    let bundle: ConfigBundle = serde_json::from_slice(&body).map_err(|e| {
+        log::error!("Invalid config bundle: {}", e);
+        ApiError::invalid_json()
+    })?;
+
+    let missing = BundlePaths::new()
+        .and_then(|paths| bundle::import(&bundle, &paths, &state.plugins_dir))
+        .map_err(|e| {
+            log::error!("Failed to import config: {}", e);
+            ApiError::internal("Failed to import config")
+        })?;
+
+    if let Some(token) = &bundle.github_token {
+        if let Err(e) = super::github::store_token(token) {
+            log::error!("Failed to store imported GitHub token: {}", e);
+        }
+    }
+
+    trigger_reload();
+    state.daemon.events.send(DaemonEvent::HotkeysChanged);
+
+    if state.offline {
+        log::info!("Config imported, offline so not installing plugins: {:?}", missing);
+        return Ok(Json(ImportResult { installing: Vec::new(), skipped: missing }));
+    }
+
+    let installing: Vec<String> = missing
+        .into_iter()
+        .filter_map(|id| {
+            let lock = state.plugin_locks.try_acquire(&id)?;
+            tokio::spawn(run_install(state.clone(), state.plugins_dir.clone(), id.clone(), lock));
+            Some(id)
+        })
+        .collect();
+
+    log::info!("Config imported, installing plugins: {:?}", installing);
+    Ok(Json(ImportResult { installing, skipped: Vec::new() }))
+}
+
+async fn get_hotkey_status() -> Json<hotkeys::HotkeyStatusReport> {
+    Json(hotkeys::registration_status())
+}
+
+async fn suspend_hotkeys() -> Result<impl IntoResponse, ApiError> {
+    if !hotkeys::suspend() {
+        return Err(ApiError::internal("Hotkey listener is not running"));
+    }
+    log::info!("Hotkey suspend requested");
+    Ok((StatusCode::OK, "Hotkeys suspended"))
+}
+
+async fn resume_hotkeys() -> Result<impl IntoResponse, ApiError> {
+    if !hotkeys::resume() {
+        return Err(ApiError::internal("Hotkey listener is not running"));
+    }
+    log::info!("Hotkey resume requested");
+    Ok((StatusCode::OK, "Hotkeys resumed"))
 }
 
-#[cfg(feature = "dev")]
 async fn list_linked_plugins(
     State(state): State<AppState>,
-) -> Result<Json<Vec<dev::LinkedPlugin>>, StatusCode> {
+) -> Result<Json<Vec<dev::LinkedPlugin>>, ApiError> {
     dev::list_linked_plugins(&state.plugins_dir)
         .map(Json)
         .map_err(|e| {
             log::error!("Failed to list linked plugins: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+            ApiError::internal("Failed to list linked plugins")
         })
 }
 
-#[cfg(feature = "dev")]
 async fn create_link(
     State(state): State<AppState>,
     Json(req): Json<dev::LinkRequest>,
-) -> impl IntoResponse {
+) -> Result<impl IntoResponse, ApiError> {
     let source = std::path::Path::new(&req.path);
 
-    match dev::create_link(source, &state.plugins_dir) {
-        Ok(_) => {
-            state.daemon.start_discovery(state.plugins_dir.clone());
-            (StatusCode::OK, "Link created").into_response()
-        }
-        Err(e) if e.contains("Already linked") => (StatusCode::CONFLICT, e).into_response(),
-        Err(e) if e.contains("does not exist") || e.contains("No plugin.toml") => {
-            (StatusCode::BAD_REQUEST, e).into_response()
-        }
-        Err(e) => {
-            log::error!("Failed to create link: {}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, e).into_response()
-        }
-    }
+    dev::create_link(source, &state.plugins_dir).map_err(|e| {
+        log::error!("Failed to create link: {}", e);
+        ApiError::from(e)
+    })?;
+
+    state.daemon.start_discovery(state.plugins_dir.clone(), false);
+    Ok((StatusCode::OK, "Link created"))
+}
+
+async fn link_existing(
+    State(state): State<AppState>,
+    Json(req): Json<dev::LinkExistingRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let source = std::path::Path::new(&req.path);
+
+    dev::link_existing(&req.id, source, &state.plugins_dir).map_err(|e| {
+        log::error!("Failed to link existing plugin {}: {}", req.id, e);
+        ApiError::from(e)
+    })?;
+
+    state.daemon.start_discovery(state.plugins_dir.clone(), false);
+    Ok((StatusCode::OK, "Installed plugin replaced with link"))
 }
 
-#[cfg(feature = "dev")]
 async fn delete_link(
     Path(id): Path<String>,
     State(state): State<AppState>,
-) -> impl IntoResponse {
+) -> Result<impl IntoResponse, ApiError> {
     if !is_safe_path_component(&id) {
-        return (StatusCode::BAD_REQUEST, "Invalid plugin ID".to_string()).into_response();
+        return Err(ApiError::invalid_id());
     }
 
-    match dev::remove_link(&id, &state.plugins_dir) {
-        Ok(()) => {
-            state.daemon.start_discovery(state.plugins_dir.clone());
-            (StatusCode::OK, "Unlinked".to_string()).into_response()
-        }
-        Err(e) => {
-            log::error!("Failed to remove link for {}: {}", id, e);
-            (StatusCode::BAD_REQUEST, e).into_response()
-        }
-    }
+    dev::remove_link(&id, &state.plugins_dir).map_err(|e| {
+        log::error!("Failed to remove link for {}: {}", id, e);
+        ApiError::from(e)
+    })?;
+
+    state.daemon.start_discovery(state.plugins_dir.clone(), false);
+    Ok((StatusCode::OK, "Unlinked"))
 }
 
-#[cfg(feature = "dev")]
 #[derive(Serialize)]
 struct DiscoveryStateResponse {
     status: String,
     plugins: Vec<crate::daemon::DiscoveredPluginInfo>,
+    errors: Vec<crate::daemon::DiscoveryErrorInfo>,
 }
 
-#[cfg(feature = "dev")]
 async fn get_discovery_state(
     State(state): State<AppState>,
 ) -> Json<DiscoveryStateResponse> {
@@ -744,12 +1642,621 @@ async fn get_discovery_state(
     Json(DiscoveryStateResponse {
         status: status.to_string(),
         plugins: guard.plugins.clone(),
+        errors: guard.errors.clone(),
     })
 }
 
-#[cfg(feature = "dev")]
-async fn trigger_discovery(State(state): State<AppState>) -> impl IntoResponse {
-    log::info!("Discovery refresh requested");
-    state.daemon.start_discovery(state.plugins_dir.clone());
+#[derive(Debug, PartialEq, Serialize)]
+struct SearchPath {
+    path: String,
+    exists: bool,
+}
+
+async fn get_search_paths() -> Json<Vec<SearchPath>> {
+    let config = dev::DevConfig::load().unwrap_or_else(|e| {
+        log::warn!("Failed to load dev config: {}", e);
+        dev::DevConfig::default()
+    });
+    Json(search_paths(&config))
+}
+
+fn search_paths(config: &dev::DevConfig) -> Vec<SearchPath> {
+    config
+        .effective_search_paths()
+        .into_iter()
+        .map(|path| SearchPath {
+            exists: path.is_dir(),
+            path: path.to_string_lossy().into_owned(),
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct DiscoverQuery {
+    #[serde(default)]
+    force: bool,
+}
+
+async fn trigger_discovery(
+    State(state): State<AppState>,
+    Query(query): Query<DiscoverQuery>,
+) -> impl IntoResponse {
+    log::info!("Discovery refresh requested (force: {})", query.force);
+    state.daemon.start_discovery(state.plugins_dir.clone(), query.force);
     StatusCode::OK
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderMap;
+    use std::time::Duration;
+
+    fn test_state(started_at: Instant) -> AppState {
+        AppState {
+            plugins_dir: PathBuf::from("/nonexistent"),
+            plugin_manager: Arc::new(Mutex::new(PluginManager::new())),
+            daemon: Daemon::new(),
+            active_installs: Arc::new(Mutex::new(HashMap::new())),
+            plugin_locks: PluginLocks::new(),
+            started_at,
+            disk_usage: DiskUsageCache::new(),
+            dev_tools: false,
+            offline: false,
+            reload_debouncer: Debouncer::new(RELOAD_DEBOUNCE),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn serves_api_over_unix_socket() {
+        use std::os::unix::fs::PermissionsExt;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("qol.sock");
+        std::fs::write(&path, "stale").unwrap();
+
+        let listener = bind_unix_socket(&path).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o600);
+
+        spawn_server(listener, Router::new().route("/api/version", get(get_version)));
+
+        let mut stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        stream
+            .write_all(b"GET /api/version HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "response: {}", response);
+        assert!(response.ends_with(env!("CARGO_PKG_VERSION")), "response: {}", response);
+    }
+
+    async fn serve_api(state: AppState) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        spawn_server(listener, Router::new().nest("/api", api_router(state)));
+        format!("http://{}/api", addr)
+    }
+
+    #[tokio::test]
+    async fn dev_routes_are_gated_by_dev_tools_flag() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let client = reqwest::Client::new();
+
+        let cases = [
+            (false, "/dev/links", StatusCode::NOT_FOUND),
+            (false, "/dev/discovery-state", StatusCode::NOT_FOUND),
+            (true, "/dev/links", StatusCode::OK),
+            (true, "/dev/discovery-state", StatusCode::OK),
+            (false, "/dev/search-paths", StatusCode::NOT_FOUND),
+            (true, "/dev/search-paths", StatusCode::OK),
+        ];
+
+        for (dev_tools, path, expected) in cases {
+            let mut state = test_state(Instant::now());
+            state.plugins_dir = tmp.path().to_path_buf();
+            state.dev_tools = dev_tools;
+            let base = serve_api(state).await;
+
+            let response = client.get(format!("{}{}", base, path)).send().await.unwrap();
+            assert_eq!(response.status().as_u16(), expected.as_u16(), "dev_tools={} path={}", dev_tools, path);
+
+            let enabled: bool = client.get(format!("{}/dev/enabled", base)).send().await.unwrap().json().await.unwrap();
+            assert_eq!(enabled, dev_tools);
+        }
+    }
+
+    #[test]
+    fn search_paths_report_configured_directories() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let existing = tmp.path().join("projects");
+        std::fs::create_dir_all(&existing).unwrap();
+        let missing = tmp.path().join("missing");
+        let config = dev::DevConfig {
+            search_paths: vec![existing.clone(), missing.clone(), existing.clone()],
+            ..Default::default()
+        };
+
+        let paths = search_paths(&config);
+
+        assert_eq!(
+            paths,
+            vec![SearchPath { path: existing.canonicalize().unwrap().to_string_lossy().into_owned(), exists: true }]
+        );
+    }
+
+    async fn read_sse(response: &mut reqwest::Response, count: usize) -> Vec<(Option<u64>, serde_json::Value)> {
+        let mut body = String::new();
+        while body.matches("data: ").count() < count {
+            let chunk = tokio::time::timeout(Duration::from_secs(5), response.chunk()).await.unwrap().unwrap().unwrap();
+            body.push_str(&String::from_utf8_lossy(&chunk));
+        }
+        body.split("\n\n")
+            .filter(|block| block.contains("data: "))
+            .map(|block| {
+                let field = |name: &str| block.lines().find_map(|line| line.strip_prefix(name));
+                let id = field("id: ").map(|id| id.parse().unwrap());
+                (id, serde_json::from_str(field("data: ").unwrap()).unwrap())
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn event_stream_starts_with_snapshot() {
+        let state = test_state(Instant::now());
+        let daemon = state.daemon.clone();
+        daemon.events.send(DaemonEvent::HotkeysChanged);
+        let base = serve_api(state).await;
+
+        let mut response = reqwest::get(format!("{}/events", base)).await.unwrap();
+        daemon.events.send(DaemonEvent::PluginsChanged);
+        let events = read_sse(&mut response, 2).await;
+
+        assert_eq!(events[0].0, Some(1));
+        assert_eq!(
+            events[0].1,
+            serde_json::json!({
+                "type": "snapshot",
+                "plugins": [],
+                "discovery": "idle",
+                "discovered": [],
+                "discovery_errors": []
+            })
+        );
+        assert_eq!(events[1].0, Some(2));
+        assert_eq!(events[1].1["type"], "plugins_changed");
+    }
+
+    #[tokio::test]
+    async fn event_stream_replays_from_last_event_id() {
+        let client = reqwest::Client::new();
+        let cases = [
+            ("1", vec![(Some(2), "hotkeys_changed"), (Some(3), "plugins_changed")]),
+            ("3", vec![(Some(4), "plugins_changed")]),
+            ("99", vec![(Some(3), "resync"), (Some(4), "plugins_changed")]),
+            ("junk", vec![(Some(3), "snapshot"), (Some(4), "plugins_changed")]),
+        ];
+
+        for (last_event_id, expected) in cases {
+            let state = test_state(Instant::now());
+            let daemon = state.daemon.clone();
+            daemon.events.send(DaemonEvent::PluginsChanged);
+            daemon.events.send(DaemonEvent::HotkeysChanged);
+            daemon.events.send(DaemonEvent::PluginsChanged);
+            let base = serve_api(state).await;
+
+            let mut response = client
+                .get(format!("{}/events", base))
+                .header("Last-Event-ID", last_event_id)
+                .send()
+                .await
+                .unwrap();
+            daemon.events.send(DaemonEvent::PluginsChanged);
+
+            let events: Vec<(Option<u64>, String)> = read_sse(&mut response, expected.len())
+                .await
+                .into_iter()
+                .map(|(id, event)| (id, event["type"].as_str().unwrap().to_string()))
+                .collect();
+            let expected: Vec<(Option<u64>, String)> = expected.into_iter().map(|(id, t)| (id, t.to_string())).collect();
+            assert_eq!(events, expected, "Last-Event-ID: {}", last_event_id);
+        }
+    }
+
+    #[tokio::test]
+    async fn lagged_event_stream_emits_resync() {
+        use tokio_stream::wrappers::BroadcastStream;
+        use tokio_stream::StreamExt;
+
+        let daemon = Daemon::new();
+        let (_, rx) = daemon.events.subscribe();
+        let mut stream = BroadcastStream::new(rx);
+        for _ in 0..70 {
+            daemon.events.send(DaemonEvent::PluginsChanged);
+        }
+
+        let (first_id, first) = sse_payload(stream.next().await.unwrap()).unwrap();
+        let (second_id, second) = sse_payload(stream.next().await.unwrap()).unwrap();
+        let first: serde_json::Value = serde_json::from_str(&first).unwrap();
+        let second: serde_json::Value = serde_json::from_str(&second).unwrap();
+
+        assert_eq!(first_id, None);
+        assert_eq!(first, serde_json::json!({"type": "resync", "missed": 6}));
+        assert_eq!(second_id, Some(7));
+        assert_eq!(second["type"], "plugins_changed");
+    }
+
+    #[tokio::test]
+    async fn slow_handlers_are_cut_off_by_timeout() {
+        async fn slow() -> &'static str {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            "done"
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = with_timeout(Router::new().route("/slow", get(slow)), Duration::from_millis(50))
+            .merge(Router::new().route("/fast", get(|| async { "done" })));
+        spawn_server(listener, router);
+
+        let cases = [("/slow", StatusCode::REQUEST_TIMEOUT), ("/fast", StatusCode::OK)];
+
+        for (path, expected) in cases {
+            let started = Instant::now();
+            let response = reqwest::get(format!("http://{}{}", addr, path)).await.unwrap();
+            assert_eq!(response.status().as_u16(), expected.as_u16(), "path: {}", path);
+            assert!(started.elapsed() < Duration::from_secs(2), "path: {}", path);
+        }
+    }
+
+    #[test]
+    fn normalize_local_origin_cases() {
+        let cases = [
+            ("http://localhost:5173", Some("http://localhost:5173")),
+            ("http://localhost:5173/", Some("http://localhost:5173")),
+            (" https://127.0.0.1:8443 ", Some("https://127.0.0.1:8443")),
+            ("http://[::1]:3000", Some("http://[::1]:3000")),
+            ("http://localhost", Some("http://localhost")),
+            ("https://example.com", None),
+            ("http://localhost.example.com", None),
+            ("http://localhost:5173/app", None),
+            ("http://user@localhost:5173", None),
+            ("file:///tmp", None),
+            ("*", None),
+            ("", None),
+        ];
+
+        for (origin, expected) in cases {
+            assert_eq!(normalize_local_origin(origin).as_deref(), expected, "origin: {}", origin);
+        }
+    }
+
+    #[tokio::test]
+    async fn cors_headers_only_sent_to_configured_origins() {
+        let configured = vec!["http://localhost:5173".to_string(), "https://example.com".to_string()];
+        let router = Router::new().nest("/api", with_cors(api_router(test_state(Instant::now())), &configured));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}/api", listener.local_addr().unwrap());
+        spawn_server(listener, router);
+        let client = reqwest::Client::new();
+
+        let cases = [
+            ("http://localhost:5173", true),
+            ("http://localhost:3000", false),
+            ("https://example.com", false),
+        ];
+
+        for (origin, allowed) in cases {
+            let response = client
+                .get(format!("{}/version", base))
+                .header(header::ORIGIN, origin)
+                .send()
+                .await
+                .unwrap();
+            let allow_origin = response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).cloned();
+            assert_eq!(allow_origin.is_some(), allowed, "origin: {}", origin);
+
+            let preflight = client
+                .request(reqwest::Method::OPTIONS, format!("{}/hotkeys", base))
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "PUT")
+                .send()
+                .await
+                .unwrap();
+            let preflight_origin = preflight.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN);
+            assert_eq!(preflight_origin.is_some(), allowed, "preflight origin: {}", origin);
+
+            if !allowed {
+                continue;
+            }
+            let patch_preflight = client
+                .request(reqwest::Method::OPTIONS, format!("{}/plugins/demo/config", base))
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "PATCH")
+                .send()
+                .await
+                .unwrap();
+            let allowed_methods = patch_preflight
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_METHODS)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default();
+            assert!(allowed_methods.contains("PATCH"), "allowed methods: {}", allowed_methods);
+        }
+    }
+
+    #[tokio::test]
+    async fn cors_is_off_without_configured_origins() {
+        let router = Router::new().nest("/api", with_cors(api_router(test_state(Instant::now())), &[]));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}/api", listener.local_addr().unwrap());
+        spawn_server(listener, router);
+
+        let response = reqwest::Client::new()
+            .get(format!("{}/version", base))
+            .header(header::ORIGIN, "http://localhost:5173")
+            .send()
+            .await
+            .unwrap();
+
+        assert!(response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+    }
+
+    #[tokio::test]
+    async fn patch_config_rejects_non_object_bodies() {
+        let base = serve_api(test_state(Instant::now())).await;
+        let client = reqwest::Client::new();
+
+        for body in ["[1, 2]", "\"text\"", "null", "{not json"] {
+            let response = client
+                .patch(format!("{}/plugins/demo/config", base))
+                .body(body)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status().as_u16(), StatusCode::BAD_REQUEST.as_u16(), "body: {}", body);
+        }
+    }
+
+    #[tokio::test]
+    async fn reset_config_rejects_invalid_or_missing_plugins() {
+        let base = serve_api(test_state(Instant::now())).await;
+        let client = reqwest::Client::new();
+
+        let cases = [
+            ("bad%5Cid", StatusCode::BAD_REQUEST),
+            ("bad%00id", StatusCode::BAD_REQUEST),
+            ("not-installed", StatusCode::NOT_FOUND),
+        ];
+
+        for (id, expected) in cases {
+            let response = client
+                .post(format!("{}/plugins/{}/config/reset", base, id))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status().as_u16(), expected.as_u16(), "id: {}", id);
+        }
+    }
+
+    #[tokio::test]
+    async fn plugin_stats_rejects_invalid_or_unloaded_plugins() {
+        let base = serve_api(test_state(Instant::now())).await;
+
+        let cases = [
+            ("bad%5Cid", StatusCode::BAD_REQUEST),
+            ("bad%00id", StatusCode::BAD_REQUEST),
+            ("not-loaded", StatusCode::NOT_FOUND),
+        ];
+
+        for (id, expected) in cases {
+            let response = reqwest::get(format!("{}/plugins/{}/stats", base, id)).await.unwrap();
+            assert_eq!(response.status().as_u16(), expected.as_u16(), "id: {}", id);
+        }
+    }
+
+    #[tokio::test]
+    async fn daemon_restart_rejects_invalid_or_unloaded_plugins() {
+        let base = serve_api(test_state(Instant::now())).await;
+        let client = reqwest::Client::new();
+
+        let cases = [
+            ("bad%5Cid", StatusCode::BAD_REQUEST),
+            ("bad%00id", StatusCode::BAD_REQUEST),
+            ("not-loaded", StatusCode::NOT_FOUND),
+        ];
+
+        for (id, expected) in cases {
+            let response = client
+                .post(format!("{}/plugins/{}/daemon/restart", base, id))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status().as_u16(), expected.as_u16(), "id: {}", id);
+        }
+    }
+
+    #[tokio::test]
+    async fn offline_mode_rejects_install_and_update() {
+        let base = serve_api(AppState { offline: true, ..test_state(Instant::now()) }).await;
+        let client = reqwest::Client::new();
+
+        for path in ["/install/demo", "/update/demo"] {
+            let response = client.post(format!("{}{}", base, path)).send().await.unwrap();
+            assert_eq!(response.status().as_u16(), StatusCode::SERVICE_UNAVAILABLE.as_u16(), "path: {}", path);
+            let body: serde_json::Value = response.json().await.unwrap();
+            assert_eq!(body["error"]["code"], "offline", "path: {}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn oversized_request_bodies_are_rejected() {
+        let base = serve_api(test_state(Instant::now())).await;
+        let client = reqwest::Client::new();
+        let oversized = vec![b' '; MAX_REQUEST_BODY + 1];
+
+        let cases = [
+            (reqwest::Method::PUT, "/hotkeys", StatusCode::PAYLOAD_TOO_LARGE),
+            (reqwest::Method::POST, "/github-token", StatusCode::PAYLOAD_TOO_LARGE),
+            (reqwest::Method::PUT, "/plugins/demo/config", StatusCode::PAYLOAD_TOO_LARGE),
+            (reqwest::Method::POST, "/config/import", StatusCode::BAD_REQUEST),
+        ];
+
+        for (method, path, expected) in cases {
+            let response = client
+                .request(method, format!("{}{}", base, path))
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(oversized.clone())
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status().as_u16(), expected.as_u16(), "path: {}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn hotkey_keys_lists_key_names_and_modifiers() {
+        let base = serve_api(test_state(Instant::now())).await;
+
+        let body: serde_json::Value = reqwest::get(format!("{}/hotkeys/keys", base))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+
+        let keys: Vec<&str> = body["keys"].as_array().unwrap().iter().map(|k| k.as_str().unwrap()).collect();
+        for key in ["space", "f12", "a", "enter"] {
+            assert!(keys.contains(&key), "missing key: {}", key);
+        }
+        assert_eq!(body["modifiers"][0]["name"], "ctrl");
+        assert_eq!(body["modifiers"][0]["aliases"], serde_json::json!(["ctrl", "control"]));
+    }
+
+    fn plugin_with_actions(id: &str, actions: &[&str]) -> crate::plugins::Plugin {
+        let items: Vec<String> = actions
+            .iter()
+            .map(|a| format!(r#"{{ type = "action", id = "{0}", label = "{0}", action = "run" }}"#, a))
+            .collect();
+        let manifest = format!(
+            "[plugin]\nname = \"{0}\"\ndescription = \"\"\nversion = \"1.0.0\"\n\n[menu]\nlabel = \"{0}\"\nitems = [{1}]\n",
+            id,
+            items.join(", ")
+        );
+        crate::plugins::Plugin::new(id.to_string(), toml::from_str(&manifest).unwrap(), PathBuf::from(id), 0)
+    }
+
+    #[test]
+    fn dangling_hotkey_warnings_cases() {
+        let plugins = [plugin_with_actions("plugin-demo", &["open", "close"]), plugin_with_actions("plugin-empty", &[])];
+        let config: crate::hotkeys::HotkeyConfig = serde_json::from_value(serde_json::json!({"hotkeys": [
+            {"id": "ok", "key": "Super+A", "plugin_id": "plugin-demo", "action": "open", "enabled": true},
+            {"id": "gone", "key": "Super+B", "plugin_id": "plugin-deleted", "action": "open", "enabled": true},
+            {"id": "typo", "key": "Super+C", "plugin_id": "plugin-demo", "action": "opne", "enabled": false},
+            {"id": "empty", "key": "Super+D", "plugin_id": "plugin-empty", "action": "open", "enabled": true}
+        ]})).unwrap();
+
+        let warnings = dangling_hotkey_warnings(&config, &plugins);
+
+        let expected = [
+            ("gone", "Plugin plugin-deleted is not installed"),
+            ("typo", "Plugin plugin-demo has no action opne"),
+            ("empty", "Plugin plugin-empty has no action open"),
+        ];
+        assert_eq!(warnings.len(), expected.len(), "{:?}", warnings);
+        for (warning, (hotkey_id, message)) in warnings.iter().zip(expected) {
+            assert_eq!(warning.hotkey_id, hotkey_id);
+            assert_eq!(warning.message, message);
+        }
+    }
+
+    #[test]
+    fn installed_plugin_dir_validates_id() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join("installed")).unwrap();
+        std::fs::write(tmp.path().join("file"), "").unwrap();
+
+        let cases = [
+            ("installed", StatusCode::OK),
+            ("missing", StatusCode::NOT_FOUND),
+            ("file", StatusCode::NOT_FOUND),
+            ("..", StatusCode::BAD_REQUEST),
+            ("../etc", StatusCode::BAD_REQUEST),
+            ("a\\b", StatusCode::BAD_REQUEST),
+            ("", StatusCode::BAD_REQUEST),
+        ];
+
+        for (id, expected) in cases {
+            let status = match installed_plugin_dir(tmp.path(), id) {
+                Ok(dir) => {
+                    assert_eq!(dir, tmp.path().join(id));
+                    StatusCode::OK
+                }
+                Err(e) => e.into_response().status(),
+            };
+            assert_eq!(status, expected, "id: {:?}", id);
+        }
+    }
+
+    #[tokio::test]
+    async fn health_reports_status_version_and_uptime() {
+        let started_at = Instant::now() - Duration::from_secs(90);
+
+        let Json(health) = get_health(State(test_state(started_at))).await.unwrap();
+        let json = serde_json::to_value(health).unwrap();
+
+        assert_eq!(json["status"], "ok");
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(json["plugins_loaded"], 0);
+        let uptime = json["uptime_secs"].as_u64().unwrap();
+        assert!((90..120).contains(&uptime), "uptime: {}", uptime);
+    }
+
+    async fn get_embedded(path: &str, accept: Option<&str>) -> (StatusCode, Vec<u8>) {
+        let mut headers = HeaderMap::new();
+        if let Some(accept) = accept {
+            headers.insert(header::ACCEPT, HeaderValue::from_str(accept).unwrap());
+        }
+        let response = serve_embedded(headers, Path(path.to_string())).await.into_response();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, body.to_vec())
+    }
+
+    #[tokio::test]
+    async fn deep_route_falls_back_to_index() {
+        let index = UiAssets::get("index.html").unwrap().data.into_owned();
+
+        let (status, body) = get_embedded("some/deep/route", Some("text/html,application/xhtml+xml")).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, index);
+    }
+
+    #[tokio::test]
+    async fn missing_asset_returns_404() {
+        let cases = [
+            ("missing.js", Some("text/html")),
+            ("missing.js", Some("*/*")),
+            ("some/deep/route", Some("application/json")),
+            ("some/deep/route", None),
+        ];
+
+        for (path, accept) in cases {
+            let (status, _) = get_embedded(path, accept).await;
+            assert_eq!(status, StatusCode::NOT_FOUND, "path: {} accept: {:?}", path, accept);
+        }
+    }
+
+    #[tokio::test]
+    async fn existing_asset_is_served() {
+        let (status, body) = get_embedded("utils.js", Some("*/*")).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert!(!body.is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+use anyhow::Result;
+use notify::event::{EventKind, ModifyKind};
+use notify::{RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+const MANIFEST_FILE: &str = "plugin.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Change {
+    Ignore,
+    Manifest,
+    PluginDir,
+}
+
+pub(super) fn spawn_plugins_watcher<F>(plugins_dir: PathBuf, on_change: F) -> Result<JoinHandle<()>>
+where
+    F: Fn() + Send + 'static,
+{
+    std::fs::create_dir_all(&plugins_dir)?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<Change>();
+    let root = plugins_dir.clone();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else { return };
+        let change = classify(&root, &event);
+        if change != Change::Ignore {
+            let _ = tx.send(change);
+        }
+    })?;
+    watcher.watch(&plugins_dir, RecursiveMode::NonRecursive)?;
+
+    let mut watched = HashSet::new();
+    sync_plugin_watches(&mut watcher, &plugins_dir, &mut watched);
+
+    Ok(tokio::spawn(async move {
+        while let Some(change) = rx.recv().await {
+            if change == Change::PluginDir {
+                sync_plugin_watches(&mut watcher, &plugins_dir, &mut watched);
+            }
+            log::info!("Plugins directory changed on disk, reloading");
+            on_change();
+        }
+    }))
+}
+
+pub(super) fn classify(plugins_dir: &Path, event: &notify::Event) -> Change {
+    let relevant_kind = match event.kind {
+        EventKind::Create(_) | EventKind::Remove(_) => true,
+        EventKind::Modify(ModifyKind::Metadata(_)) => false,
+        EventKind::Modify(_) => true,
+        EventKind::Any | EventKind::Access(_) | EventKind::Other => false,
+    };
+    if !relevant_kind {
+        return Change::Ignore;
+    }
+
+    event
+        .paths
+        .iter()
+        .map(|path| classify_path(plugins_dir, path))
+        .max_by_key(|change| match change {
+            Change::Ignore => 0,
+            Change::Manifest => 1,
+            Change::PluginDir => 2,
+        })
+        .unwrap_or(Change::Ignore)
+}
+
+fn classify_path(plugins_dir: &Path, path: &Path) -> Change {
+    let Ok(relative) = path.strip_prefix(plugins_dir) else {
+        return Change::Ignore;
+    };
+    let parts: Vec<&str> = relative.iter().filter_map(|part| part.to_str()).collect();
+
+    match parts.as_slice() {
+        [plugin] if is_plugin_dir_name(plugin) => Change::PluginDir,
+        [plugin, MANIFEST_FILE] if is_plugin_dir_name(plugin) => Change::Manifest,
+        _ => Change::Ignore,
+    }
+}
+
+fn is_plugin_dir_name(name: &str) -> bool {
+    !name.starts_with('.') && !name.ends_with(".backup")
+}
+
+fn sync_plugin_watches<W: Watcher>(watcher: &mut W, plugins_dir: &Path, watched: &mut HashSet<PathBuf>) {
+    let current: HashSet<PathBuf> = std::fs::read_dir(plugins_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_name().to_str().is_some_and(is_plugin_dir_name))
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for removed in watched.difference(&current) {
+        let _ = watcher.unwatch(removed);
+    }
+    watched.retain(|path| current.contains(path));
+
+    for added in current {
+        if watched.contains(&added) {
+            continue;
+        }
+        match watcher.watch(&added, RecursiveMode::NonRecursive) {
+            Ok(()) => {
+                watched.insert(added);
+            }
+            Err(e) => log::warn!("Failed to watch plugin dir {:?}: {}", added, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, DataChange, MetadataKind, RemoveKind, RenameMode};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn event(kind: EventKind, paths: &[&str]) -> notify::Event {
+        notify::Event {
+            kind,
+            paths: paths.iter().map(PathBuf::from).collect(),
+            attrs: Default::default(),
+        }
+    }
+
+    #[test]
+    fn classify_cases() {
+        let root = Path::new("/plugins");
+        let data = EventKind::Modify(ModifyKind::Data(DataChange::Content));
+        let cases = [
+            (event(data, &["/plugins/demo/plugin.toml"]), Change::Manifest),
+            (event(EventKind::Create(CreateKind::File), &["/plugins/demo/plugin.toml"]), Change::Manifest),
+            (event(EventKind::Remove(RemoveKind::File), &["/plugins/demo/plugin.toml"]), Change::Manifest),
+            (event(EventKind::Create(CreateKind::Folder), &["/plugins/demo"]), Change::PluginDir),
+            (event(EventKind::Remove(RemoveKind::Folder), &["/plugins/demo"]), Change::PluginDir),
+            (
+                event(EventKind::Modify(ModifyKind::Name(RenameMode::Both)), &["/plugins/.tmp", "/plugins/demo"]),
+                Change::PluginDir,
+            ),
+            (event(data, &["/plugins/demo/run.sh"]), Change::Ignore),
+            (event(EventKind::Create(CreateKind::Folder), &["/plugins/demo/target"]), Change::Ignore),
+            (event(data, &["/plugins/demo/target/debug/plugin.toml"]), Change::Ignore),
+            (event(data, &["/plugins/demo/node_modules/x/plugin.toml"]), Change::Ignore),
+            (event(EventKind::Create(CreateKind::Folder), &["/plugins/.staging"]), Change::Ignore),
+            (event(EventKind::Create(CreateKind::Folder), &["/plugins/demo.backup"]), Change::Ignore),
+            (event(data, &["/plugins/.git/plugin.toml"]), Change::Ignore),
+            (event(EventKind::Access(AccessKind::Any), &["/plugins/demo/plugin.toml"]), Change::Ignore),
+            (
+                event(EventKind::Modify(ModifyKind::Metadata(MetadataKind::Any)), &["/plugins/demo/plugin.toml"]),
+                Change::Ignore,
+            ),
+            (event(data, &["/elsewhere/demo/plugin.toml"]), Change::Ignore),
+            (event(data, &[]), Change::Ignore),
+        ];
+
+        for (event, expected) in cases {
+            assert_eq!(classify(root, &event), expected, "event: {:?}", event);
+        }
+    }
+
+    async fn wait_for_count(count: &AtomicUsize, at_least: usize) -> bool {
+        for _ in 0..50 {
+            if count.load(Ordering::SeqCst) >= at_least {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn manifest_changes_trigger_reload_but_build_output_does_not() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let plugins_dir = tmp.path().join("plugins");
+        let existing = plugins_dir.join("existing");
+        std::fs::create_dir_all(&existing).unwrap();
+        std::fs::write(existing.join(MANIFEST_FILE), "[plugin]").unwrap();
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&count);
+        let handle = spawn_plugins_watcher(plugins_dir.clone(), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+        std::fs::create_dir_all(existing.join("target").join("debug")).unwrap();
+        std::fs::write(existing.join("target").join("debug").join("out"), "bin").unwrap();
+        std::fs::write(existing.join("run.sh"), "echo").unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+
+        std::fs::write(existing.join(MANIFEST_FILE), "[plugin]\nname = \"x\"").unwrap();
+        assert!(wait_for_count(&count, 1).await);
+
+        let added = plugins_dir.join("added");
+        std::fs::create_dir_all(&added).unwrap();
+        assert!(wait_for_count(&count, 2).await);
+
+        let before = count.load(Ordering::SeqCst);
+        std::fs::write(added.join(MANIFEST_FILE), "[plugin]").unwrap();
+        assert!(wait_for_count(&count, before + 1).await);
+
+        handle.abort();
+    }
+}
@@ -1,19 +1,23 @@
+mod watcher;
+
 use axum::{
-    extract::State,
+    extract::{Path as UrlPath, State},
     routing::{get, post},
     Json, Router,
     http::StatusCode,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 use tokio::process::Command;
 use tokio::sync::RwLock;
 use regex::Regex;
 
-const CONFIG_FILENAME: &str = "task-runner.json";
+use crate::migration::Migration;
+use crate::paths::is_safe_path_component;
+use crate::plugins::manifest::TaskAction;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ActionConfig {
@@ -41,6 +45,7 @@ pub struct TaskRunnerConfig {
 struct TaskRunnerState {
     config: Arc<RwLock<TaskRunnerConfig>>,
     config_path: PathBuf,
+    plugins_dir: Option<PathBuf>,
 }
 
 #[derive(Serialize)]
@@ -71,43 +76,141 @@ struct ExecuteResponse {
     exit_code: i32,
 }
 
-#[derive(Serialize)]
+#[derive(Debug, Serialize)]
 struct ErrorResponse {
     error: String,
 }
 
+type HandlerError = (StatusCode, Json<ErrorResponse>);
+
+fn error_response(status: StatusCode, error: impl Into<String>) -> HandlerError {
+    (status, Json(ErrorResponse { error: error.into() }))
+}
+
 pub fn router() -> Router {
-    let config_path = dirs::config_dir()
-        .unwrap_or_else(|| PathBuf::from("."))
-        .join("qol-tray")
-        .join(CONFIG_FILENAME);
+    let config_path = crate::paths::task_runner_config_path()
+        .unwrap_or_else(|_| PathBuf::from("task-runner.json"));
 
-    let config = load_config(&config_path).unwrap_or_default();
+    let config = load_config(&config_path);
+
+    let config = Arc::new(RwLock::new(config));
+    if let Err(e) = watcher::spawn_config_watcher(config_path.clone(), config.clone()) {
+        log::warn!("[task-runner] Failed to watch config for changes: {}", e);
+    }
 
     let state = TaskRunnerState {
-        config: Arc::new(RwLock::new(config)),
+        config,
         config_path,
+        plugins_dir: crate::paths::plugins_dir().ok(),
     };
 
     Router::new()
         .route("/actions", get(list_actions))
+        .route(
+            "/actions/{id}",
+            get(get_action).put(put_action).delete(delete_action),
+        )
         .route("/execute", post(execute_action))
         .route("/config", get(get_config))
         .route("/config", axum::routing::put(set_config))
         .with_state(state)
 }
 
-fn load_config(path: &PathBuf) -> Option<TaskRunnerConfig> {
-    let content = std::fs::read_to_string(path).ok()?;
-    serde_json::from_str(&content).ok()
+pub const MIGRATIONS: &[Migration] = &[key_action_list_by_id];
+
+fn load_config(path: &Path) -> TaskRunnerConfig {
+    crate::migration::load_json_or_default(path, MIGRATIONS).unwrap_or_else(|e| {
+        log::warn!("Failed to load task runner config: {}", e);
+        TaskRunnerConfig::default()
+    })
+}
+
+fn key_action_list_by_id(mut value: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+    let Some(list) = value.get_mut("actions").and_then(serde_json::Value::as_array_mut) else {
+        return Ok(value);
+    };
+    let list = std::mem::take(list);
+
+    let mut actions = serde_json::Map::new();
+    for mut action in list {
+        let id = action
+            .as_object_mut()
+            .and_then(|a| a.remove("id"))
+            .and_then(|id| id.as_str().map(str::to_string))
+            .ok_or_else(|| anyhow::anyhow!("Action is missing an id"))?;
+        actions.insert(id, action);
+    }
+    value["actions"] = serde_json::Value::Object(actions);
+    Ok(value)
+}
+
+fn plugin_actions(plugin_id: &str, plugin_dir: &Path, actions: &[TaskAction]) -> HashMap<String, ActionConfig> {
+    actions
+        .iter()
+        .filter_map(|action| {
+            let cwd = match resolve_plugin_cwd(plugin_dir, action.cwd.as_deref()) {
+                Some(cwd) => cwd,
+                None => {
+                    log::warn!("[task-runner] Ignoring {}:{}: cwd escapes plugin dir", plugin_id, action.id);
+                    return None;
+                }
+            };
+            let config = ActionConfig {
+                name: action.name.clone(),
+                description: action.description.clone(),
+                command: action.command.clone(),
+                timeout: action.timeout.unwrap_or_else(default_timeout),
+                cwd: Some(cwd.to_string_lossy().into_owned()),
+            };
+            Some((format!("{}:{}", plugin_id, action.id), config))
+        })
+        .collect()
+}
+
+fn resolve_plugin_cwd(plugin_dir: &Path, cwd: Option<&str>) -> Option<PathBuf> {
+    let Some(cwd) = cwd else {
+        return Some(plugin_dir.to_path_buf());
+    };
+
+    crate::paths::resolve_within(plugin_dir, cwd)
+}
+
+fn load_plugin_actions(plugins_dir: &Path) -> HashMap<String, ActionConfig> {
+    let Ok(entries) = std::fs::read_dir(plugins_dir) else {
+        return HashMap::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let plugin_id = entry.file_name().into_string().ok()?;
+            if !is_safe_path_component(&plugin_id) {
+                return None;
+            }
+            let plugin_dir = entry.path();
+            let content = std::fs::read_to_string(plugin_dir.join("plugin.toml")).ok()?;
+            let manifest: crate::plugins::PluginManifest = toml::from_str(&content).ok()?;
+            Some(plugin_actions(&plugin_id, &plugin_dir, &manifest.task_actions))
+        })
+        .flatten()
+        .collect()
+}
+
+async fn all_actions(state: &TaskRunnerState) -> HashMap<String, ActionConfig> {
+    let mut actions = match &state.plugins_dir {
+        Some(dir) => load_plugin_actions(dir),
+        None => HashMap::new(),
+    };
+    let config = state.config.read().await;
+    actions.extend(config.actions.iter().map(|(id, action)| (id.clone(), action.clone())));
+    actions
 }
 
 async fn list_actions(
     State(state): State<TaskRunnerState>,
 ) -> Json<ActionsResponse> {
-    let config = state.config.read().await;
-    let actions = config
-        .actions
+    let actions = all_actions(&state).await;
+    let actions = actions
         .iter()
         .map(|(id, action)| ActionInfo {
             id: id.clone(),
@@ -123,9 +226,9 @@ async fn execute_action(
     State(state): State<TaskRunnerState>,
     Json(req): Json<ExecuteRequest>,
 ) -> Result<Json<ExecuteResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let config = state.config.read().await;
+    let actions = all_actions(&state).await;
 
-    let action = config.actions.get(&req.action).ok_or_else(|| {
+    let action = actions.get(&req.action).ok_or_else(|| {
         (
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
@@ -186,7 +289,7 @@ async fn execute_action(
     }
 }
 
-fn interpolate(template: &str, params: &HashMap<String, String>) -> String {
+pub fn interpolate(template: &str, params: &HashMap<String, String>) -> String {
     let re = Regex::new(r"\{\{(\w+)\}\}").unwrap();
     re.replace_all(template, |caps: &regex::Captures| {
         let key = &caps[1];
@@ -195,47 +298,113 @@ fn interpolate(template: &str, params: &HashMap<String, String>) -> String {
     .to_string()
 }
 
-async fn get_config(
+fn is_valid_action_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 64
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+async fn get_action(
     State(state): State<TaskRunnerState>,
-) -> Json<TaskRunnerConfig> {
-    let config = state.config.read().await;
-    Json(config.clone())
+    UrlPath(id): UrlPath<String>,
+) -> Result<Json<ActionConfig>, HandlerError> {
+    let actions = all_actions(&state).await;
+    actions
+        .get(&id)
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, format!("Unknown action: {}", id)))
 }
 
-async fn set_config(
+async fn put_action(
     State(state): State<TaskRunnerState>,
-    Json(new_config): Json<TaskRunnerConfig>,
-) -> Result<StatusCode, (StatusCode, Json<ErrorResponse>)> {
-    if let Some(parent) = state.config_path.parent() {
+    UrlPath(id): UrlPath<String>,
+    Json(action): Json<ActionConfig>,
+) -> Result<StatusCode, HandlerError> {
+    if !is_valid_action_id(&id) {
+        return Err(error_response(StatusCode::BAD_REQUEST, format!("Invalid action id: {}", id)));
+    }
+
+    update_actions(&state, |actions| {
+        actions.insert(id.clone(), action);
+        true
+    })
+    .await?;
+
+    log::info!("[task-runner] Action saved: {}", id);
+    Ok(StatusCode::OK)
+}
+
+async fn delete_action(
+    State(state): State<TaskRunnerState>,
+    UrlPath(id): UrlPath<String>,
+) -> Result<StatusCode, HandlerError> {
+    if !is_valid_action_id(&id) {
+        return Err(error_response(StatusCode::BAD_REQUEST, format!("Invalid action id: {}", id)));
+    }
+
+    let removed = update_actions(&state, |actions| actions.remove(&id).is_some()).await?;
+    if !removed {
+        return Err(error_response(StatusCode::NOT_FOUND, format!("Unknown action: {}", id)));
+    }
+
+    log::info!("[task-runner] Action deleted: {}", id);
+    Ok(StatusCode::OK)
+}
+
+async fn update_actions<F>(state: &TaskRunnerState, update: F) -> Result<bool, HandlerError>
+where
+    F: FnOnce(&mut HashMap<String, ActionConfig>) -> bool,
+{
+    let mut config = state.config.write().await;
+    let mut new_config = config.clone();
+    if !update(&mut new_config.actions) {
+        return Ok(false);
+    }
+
+    persist_config(&state.config_path, &new_config)?;
+    *config = new_config;
+    Ok(true)
+}
+
+fn persist_config(path: &Path, config: &TaskRunnerConfig) -> Result<(), HandlerError> {
+    if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| {
-            (
+            error_response(
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Failed to create config dir: {}", e),
-                }),
+                format!("Failed to create config dir: {}", e),
             )
         })?;
     }
 
-    let content = serde_json::to_string_pretty(&new_config).map_err(|e| {
-        (
+    let content = serde_json::to_string_pretty(config).map_err(|e| {
+        error_response(
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("Failed to serialize config: {}", e),
-            }),
+            format!("Failed to serialize config: {}", e),
         )
     })?;
 
-    std::fs::write(&state.config_path, content).map_err(|e| {
-        (
+    crate::paths::atomic_write(path, content).map_err(|e| {
+        error_response(
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("Failed to write config: {}", e),
-            }),
+            format!("Failed to write config: {}", e),
         )
-    })?;
+    })
+}
 
+async fn get_config(
+    State(state): State<TaskRunnerState>,
+) -> Json<TaskRunnerConfig> {
+    let config = state.config.read().await;
+    Json(config.clone())
+}
+
+async fn set_config(
+    State(state): State<TaskRunnerState>,
+    Json(new_config): Json<TaskRunnerConfig>,
+) -> Result<StatusCode, HandlerError> {
     let mut config = state.config.write().await;
+    persist_config(&state.config_path, &new_config)?;
     *config = new_config;
 
     log::info!("[task-runner] Config saved");
@@ -246,6 +415,8 @@ async fn set_config(
 mod tests {
     use super::*;
 
+    type InterpolateCase<'a> = (&'a str, &'a [(&'a str, &'a str)], &'a str);
+
     #[test]
     fn interpolate_single_param() {
         let cases = [
@@ -264,7 +435,7 @@ mod tests {
 
     #[test]
     fn interpolate_multiple_params() {
-        let cases: &[(&str, &[(&str, &str)], &str)] = &[
+        let cases: &[InterpolateCase] = &[
             ("{{a}} {{b}}", &[("a", "x"), ("b", "y")], "x y"),
             ("{{x}}{{y}}{{z}}", &[("x", "1"), ("y", "2"), ("z", "3")], "123"),
             ("git checkout {{branch}} && cd {{dir}}", &[("branch", "main"), ("dir", "/a/b")], "git checkout main && cd /a/b"),
@@ -279,7 +450,7 @@ mod tests {
 
     #[test]
     fn interpolate_missing_params() {
-        let cases: &[(&str, &[(&str, &str)], &str)] = &[
+        let cases: &[InterpolateCase] = &[
             ("{{missing}}", &[], ""),
             ("hello {{name}}", &[], "hello "),
             ("{{a}} {{b}}", &[("a", "x")], "x "),
@@ -381,6 +552,193 @@ mod tests {
         }
     }
 
+    fn task_action(id: &str, cwd: Option<&str>) -> TaskAction {
+        TaskAction {
+            id: id.to_string(),
+            name: id.to_uppercase(),
+            description: String::new(),
+            command: "true".to_string(),
+            timeout: None,
+            cwd: cwd.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn plugin_actions_are_namespaced() {
+        let actions = plugin_actions(
+            "plugin-tools",
+            Path::new("/plugins/plugin-tools"),
+            &[task_action("build", None), task_action("test", None)],
+        );
+
+        let mut ids: Vec<&String> = actions.keys().collect();
+        ids.sort();
+        assert_eq!(ids, vec!["plugin-tools:build", "plugin-tools:test"]);
+        assert_eq!(actions["plugin-tools:build"].name, "BUILD");
+        assert_eq!(actions["plugin-tools:build"].timeout, 60);
+    }
+
+    #[test]
+    fn plugin_action_cwd_resolves_relative_to_plugin_dir() {
+        let plugin_dir = Path::new("/plugins/plugin-tools");
+        let cases = [
+            (None, Some("/plugins/plugin-tools")),
+            (Some("scripts"), Some("/plugins/plugin-tools/scripts")),
+            (Some("./a/b"), Some("/plugins/plugin-tools/./a/b")),
+            (Some("../other"), None),
+            (Some("/etc"), None),
+        ];
+
+        for (cwd, expected) in cases {
+            assert_eq!(
+                resolve_plugin_cwd(plugin_dir, cwd),
+                expected.map(PathBuf::from),
+                "cwd: {:?}",
+                cwd
+            );
+        }
+
+        let actions = plugin_actions("plugin-tools", plugin_dir, &[task_action("bad", Some("../x"))]);
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn load_plugin_actions_reads_manifests() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let plugin_dir = tmp.path().join("plugin-tools");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(
+            plugin_dir.join("plugin.toml"),
+            r#"
+[plugin]
+name = "Tools"
+description = ""
+version = "1.0.0"
+
+[menu]
+label = "Tools"
+items = []
+
+[[task_actions]]
+id = "build"
+name = "Build"
+command = "make"
+cwd = "src"
+"#,
+        )
+        .unwrap();
+
+        let actions = load_plugin_actions(tmp.path());
+
+        let action = &actions["plugin-tools:build"];
+        assert_eq!(action.command, "make");
+        assert_eq!(action.cwd.as_deref(), Some(plugin_dir.join("src").to_str().unwrap()));
+    }
+
+    fn test_state(dir: &Path) -> TaskRunnerState {
+        TaskRunnerState {
+            config: Arc::new(RwLock::new(TaskRunnerConfig::default())),
+            config_path: dir.join("task-runner.json"),
+            plugins_dir: None,
+        }
+    }
+
+    fn action(name: &str) -> ActionConfig {
+        ActionConfig {
+            name: name.to_string(),
+            description: String::new(),
+            command: "true".to_string(),
+            timeout: 60,
+            cwd: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn put_action_inserts_and_replaces_single_action() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let state = test_state(tmp.path());
+        state.config.write().await.actions.insert("other".to_string(), action("Other"));
+
+        let status = put_action(State(state.clone()), UrlPath("build".to_string()), Json(action("Build")))
+            .await
+            .unwrap();
+        put_action(State(state.clone()), UrlPath("build".to_string()), Json(action("Build v2")))
+            .await
+            .unwrap();
+
+        assert_eq!(status, StatusCode::OK);
+        let config = state.config.read().await;
+        assert_eq!(config.actions.len(), 2);
+        assert_eq!(config.actions["build"].name, "Build v2");
+        assert_eq!(load_config(&state.config_path).actions["build"].name, "Build v2");
+    }
+
+    #[tokio::test]
+    async fn delete_action_removes_only_target() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let state = test_state(tmp.path());
+        {
+            let mut config = state.config.write().await;
+            config.actions.insert("build".to_string(), action("Build"));
+            config.actions.insert("test".to_string(), action("Test"));
+        }
+
+        delete_action(State(state.clone()), UrlPath("build".to_string())).await.unwrap();
+        let missing = delete_action(State(state.clone()), UrlPath("build".to_string())).await;
+
+        assert_eq!(missing.unwrap_err().0, StatusCode::NOT_FOUND);
+        let config = state.config.read().await;
+        assert!(!config.actions.contains_key("build"));
+        assert!(config.actions.contains_key("test"));
+        assert_eq!(load_config(&state.config_path).actions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn single_action_endpoints_validate_ids() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let state = test_state(tmp.path());
+
+        let cases = ["", "has space", "plugin:action", "../x"];
+        for id in cases {
+            let put = put_action(State(state.clone()), UrlPath(id.to_string()), Json(action("X"))).await;
+            assert_eq!(put.unwrap_err().0, StatusCode::BAD_REQUEST, "id: {:?}", id);
+        }
+
+        let get = get_action(State(state.clone()), UrlPath("unknown".to_string())).await;
+        assert_eq!(get.unwrap_err().0, StatusCode::NOT_FOUND);
+        assert!(state.config.read().await.actions.is_empty());
+    }
+
+    #[test]
+    fn action_list_config_is_migrated() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("task-runner.json");
+        std::fs::write(
+            &path,
+            r#"{"actions": [{"id": "build", "name": "Build", "command": "make"}]}"#,
+        )
+        .unwrap();
+
+        let config = load_config(&path);
+
+        let action = config.actions.get("build").unwrap();
+        assert_eq!(action.name, "Build");
+        assert_eq!(action.command, "make");
+        assert_eq!(action.timeout, 60);
+    }
+
+    #[test]
+    fn unmigratable_config_is_backed_up() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("task-runner.json");
+        std::fs::write(&path, r#"{"actions": [{"name": "No id", "command": "true"}]}"#).unwrap();
+
+        let config = load_config(&path);
+
+        assert!(config.actions.is_empty());
+        assert!(tmp.path().join("task-runner.json.bak").exists());
+    }
+
     #[test]
     fn config_default_timeout() {
         assert_eq!(default_timeout(), 60);
@@ -0,0 +1,123 @@
+use super::TaskRunnerConfig;
+use anyhow::{Context, Result};
+use notify::{RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
+
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+pub(super) fn spawn_config_watcher(
+    config_path: PathBuf,
+    config: Arc<RwLock<TaskRunnerConfig>>,
+) -> Result<JoinHandle<()>> {
+    let dir = config_path
+        .parent()
+        .context("Config path has no parent directory")?
+        .to_path_buf();
+    let file_name = config_path
+        .file_name()
+        .context("Config path has no file name")?
+        .to_os_string();
+    std::fs::create_dir_all(&dir)?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<()>();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else { return };
+        if event.paths.iter().any(|p| p.file_name() == Some(file_name.as_os_str())) {
+            let _ = tx.send(());
+        }
+    })?;
+    watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+
+    Ok(tokio::spawn(async move {
+        let _watcher = watcher;
+        while rx.recv().await.is_some() {
+            tokio::time::sleep(DEBOUNCE).await;
+            while rx.try_recv().is_ok() {}
+            reload_config(&config_path, &config).await;
+        }
+    }))
+}
+
+async fn reload_config(path: &Path, config: &RwLock<TaskRunnerConfig>) {
+    if !path.exists() {
+        log::info!("[task-runner] Config {:?} removed, keeping last-known actions", path);
+        return;
+    }
+
+    match crate::migration::load_json::<TaskRunnerConfig>(path, super::MIGRATIONS) {
+        Ok(new_config) => {
+            *config.write().await = new_config;
+            log::info!("[task-runner] Reloaded config from {:?}", path);
+        }
+        Err(e) => log::warn!("[task-runner] Ignoring unreadable config {:?}: {:#}", path, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn wait_for<F>(config: &RwLock<TaskRunnerConfig>, predicate: F) -> bool
+    where
+        F: Fn(&TaskRunnerConfig) -> bool,
+    {
+        for _ in 0..50 {
+            if predicate(&*config.read().await) {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn external_write_updates_in_memory_config() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("task-runner.json");
+        let config = Arc::new(RwLock::new(TaskRunnerConfig::default()));
+        let handle = spawn_config_watcher(path.clone(), config.clone()).unwrap();
+
+        std::fs::write(&path, r#"{"actions": {"build": {"name": "Build", "command": "make"}}}"#).unwrap();
+        assert!(wait_for(&config, |c| c.actions.contains_key("build")).await);
+
+        std::fs::remove_file(&path).unwrap();
+        tokio::time::sleep(DEBOUNCE * 3).await;
+        assert!(config.read().await.actions.contains_key("build"));
+
+        std::fs::write(&path, r#"{"actions": {"test": {"name": "Test", "command": "make test"}}}"#).unwrap();
+        assert!(wait_for(&config, |c| c.actions.contains_key("test")).await);
+        assert!(!config.read().await.actions.contains_key("build"));
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn unreadable_write_keeps_last_known_config() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("task-runner.json");
+        let config = RwLock::new(TaskRunnerConfig::default());
+        std::fs::write(&path, r#"{"actions": {"build": {"name": "Build", "command": "make"}}}"#).unwrap();
+        reload_config(&path, &config).await;
+
+        std::fs::write(&path, r#"{"actions": {"#).unwrap();
+        reload_config(&path, &config).await;
+
+        assert!(config.read().await.actions.contains_key("build"));
+    }
+
+    #[tokio::test]
+    async fn old_shaped_config_is_migrated_on_reload() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("task-runner.json");
+        let config = RwLock::new(TaskRunnerConfig::default());
+        std::fs::write(&path, r#"{"actions": [{"id": "build", "name": "Build", "command": "make"}]}"#).unwrap();
+
+        reload_config(&path, &config).await;
+
+        assert!(config.read().await.actions.contains_key("build"));
+    }
+}
@@ -9,45 +9,95 @@ use global_hotkey::{
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Sender};
-use std::sync::OnceLock;
+use std::sync::{Mutex, OnceLock};
 
-pub use types::{HotkeyAction, HotkeyConfig};
-use types::{ScriptInfo, KEY_CODE_MAP, SCRIPT_RUNNERS};
+pub use types::{HotkeyAction, HotkeyConfig, HotkeyStatusReport, MIGRATIONS};
+use types::{HotkeyBinding, HotkeyStatus, RegistrationStatus, ScriptInfo, KEY_CODE_MAP, SCRIPT_RUNNERS};
 
-static RELOAD_SENDER: OnceLock<Sender<()>> = OnceLock::new();
+enum HotkeyCommand {
+    Reload,
+    Suspend,
+    Resume,
+}
 
-pub fn trigger_reload() {
-    if let Some(sender) = RELOAD_SENDER.get() {
-        let _ = sender.send(());
+static COMMAND_SENDER: OnceLock<Sender<HotkeyCommand>> = OnceLock::new();
+static REGISTRATION_STATUS: Mutex<Option<HotkeyStatusReport>> = Mutex::new(None);
+
+pub fn registration_status() -> HotkeyStatusReport {
+    match REGISTRATION_STATUS.lock() {
+        Ok(status) => status.clone().unwrap_or_default(),
+        Err(e) => {
+            log::error!("Hotkey status mutex poisoned: {}", e);
+            HotkeyStatusReport::default()
+        }
+    }
+}
+
+fn set_registration_status(report: HotkeyStatusReport) {
+    match REGISTRATION_STATUS.lock() {
+        Ok(mut status) => *status = Some(report),
+        Err(e) => log::error!("Hotkey status mutex poisoned: {}", e),
     }
 }
 
+fn send_command(command: HotkeyCommand) -> bool {
+    COMMAND_SENDER
+        .get()
+        .is_some_and(|sender| sender.send(command).is_ok())
+}
+
+pub fn trigger_reload() {
+    send_command(HotkeyCommand::Reload);
+}
+
+pub fn suspend() -> bool {
+    send_command(HotkeyCommand::Suspend)
+}
+
+pub fn resume() -> bool {
+    send_command(HotkeyCommand::Resume)
+}
+
 pub struct HotkeyManager {
     manager: Option<GlobalHotKeyManager>,
     registered: Vec<HotKey>,
     bindings: HashMap<u32, HotkeyAction>,
     config_path: PathBuf,
+    suspended: bool,
 }
 
 impl HotkeyManager {
     pub fn new() -> Result<Self> {
-        let config_path = paths::hotkeys_path()?;
-        Ok(Self {
+        Ok(Self::with_config_path(paths::hotkeys_path()?))
+    }
+
+    fn with_config_path(config_path: PathBuf) -> Self {
+        Self {
             manager: None,
             registered: Vec::new(),
             bindings: HashMap::new(),
             config_path,
-        })
+            suspended: false,
+        }
     }
 
-    pub fn load_config(&self) -> Result<HotkeyConfig> {
-        if !self.config_path.exists() {
-            return Ok(HotkeyConfig::default());
-        }
+    pub fn suspend(&mut self) {
+        self.unregister_all();
+        self.suspended = true;
+        set_registration_status(HotkeyStatusReport::suspended());
+        log::info!("Hotkeys suspended");
+    }
+
+    pub fn resume(&mut self) -> Result<()> {
+        self.suspended = false;
+        let config = self.load_config()?;
+        self.register_hotkeys(&config)?;
+        log::info!("Hotkeys resumed");
+        Ok(())
+    }
 
-        let content = std::fs::read_to_string(&self.config_path)?;
-        let config: HotkeyConfig = serde_json::from_str(&content)?;
-        Ok(config)
+    pub fn load_config(&self) -> Result<HotkeyConfig> {
+        crate::migration::load_json_or_default(&self.config_path, types::MIGRATIONS)
     }
 
     pub fn save_config(&self, config: &HotkeyConfig) -> Result<()> {
@@ -55,52 +105,71 @@ impl HotkeyManager {
             std::fs::create_dir_all(parent)?;
         }
         let content = serde_json::to_string_pretty(config)?;
-        std::fs::write(&self.config_path, content)?;
+        paths::atomic_write(&self.config_path, content)?;
         Ok(())
     }
 
     pub fn register_hotkeys(&mut self, config: &HotkeyConfig) -> Result<()> {
+        if self.suspended {
+            log::info!("Hotkeys suspended, skipping registration");
+            return Ok(());
+        }
+
         self.unregister_all();
 
         let new_manager = GlobalHotKeyManager::new()?;
+        let config = normalize_config(config.clone());
 
+        let mut statuses = Vec::with_capacity(config.hotkeys.len());
         for binding in &config.hotkeys {
-            if !binding.enabled {
-                continue;
-            }
+            let status = self.register_binding(&new_manager, binding);
+            statuses.push(HotkeyStatus {
+                id: binding.id.clone(),
+                key: binding.key.clone(),
+                status,
+            });
+        }
 
-            let hotkey = match parse_hotkey(&binding.key) {
-                Some(hk) => hk,
-                None => {
-                    log::warn!("Invalid hotkey string: {}", binding.key);
-                    continue;
-                }
-            };
+        set_registration_status(HotkeyStatusReport::new(statuses));
+        self.manager = Some(new_manager);
+        Ok(())
+    }
 
-            if let Err(e) = new_manager.register(hotkey) {
-                log::error!("Failed to register hotkey {}: {}", binding.key, e);
-                continue;
-            }
+    fn register_binding(
+        &mut self,
+        manager: &GlobalHotKeyManager,
+        binding: &HotkeyBinding,
+    ) -> RegistrationStatus {
+        if !binding.enabled {
+            return RegistrationStatus::Disabled;
+        }
 
-            self.registered.push(hotkey);
-            self.bindings.insert(
-                hotkey.id(),
-                HotkeyAction {
-                    plugin_id: binding.plugin_id.clone(),
-                    action: binding.action.clone(),
-                },
-            );
+        let Some(hotkey) = parse_hotkey(&binding.key) else {
+            log::warn!("Invalid hotkey string: {}", binding.key);
+            return RegistrationStatus::Invalid;
+        };
 
-            log::info!(
-                "Registered hotkey: {} -> {}::{}",
-                binding.key,
-                binding.plugin_id,
-                binding.action
-            );
+        if let Err(e) = manager.register(hotkey) {
+            log::error!("Failed to register hotkey {}: {}", binding.key, e);
+            return RegistrationStatus::Failed(e.to_string());
         }
 
-        self.manager = Some(new_manager);
-        Ok(())
+        self.registered.push(hotkey);
+        self.bindings.insert(
+            hotkey.id(),
+            HotkeyAction {
+                plugin_id: binding.plugin_id.clone(),
+                action: binding.action.clone(),
+            },
+        );
+
+        log::info!(
+            "Registered hotkey: {} -> {}::{}",
+            binding.key,
+            binding.plugin_id,
+            binding.action
+        );
+        RegistrationStatus::Registered
     }
 
     fn unregister_all(&mut self) {
@@ -122,6 +191,89 @@ impl HotkeyManager {
     }
 }
 
+const MODIFIER_ORDER: [&str; 4] = ["ctrl", "alt", "shift", "super"];
+
+const MODIFIER_ALIASES: [(&str, &str); 8] = [
+    ("ctrl", "ctrl"),
+    ("control", "ctrl"),
+    ("alt", "alt"),
+    ("shift", "shift"),
+    ("super", "super"),
+    ("win", "super"),
+    ("meta", "super"),
+    ("cmd", "super"),
+];
+
+fn canonical_modifier(part: &str) -> Option<&'static str> {
+    MODIFIER_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == part)
+        .map(|(_, canonical)| *canonical)
+}
+
+pub fn available_key_names() -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = KEY_CODE_MAP.keys().copied().collect();
+    names.sort_unstable();
+    names
+}
+
+pub fn modifier_aliases() -> Vec<(&'static str, Vec<&'static str>)> {
+    MODIFIER_ORDER
+        .iter()
+        .map(|modifier| {
+            let aliases = MODIFIER_ALIASES
+                .iter()
+                .filter(|(_, canonical)| canonical == modifier)
+                .map(|(alias, _)| *alias)
+                .collect();
+            (*modifier, aliases)
+        })
+        .collect()
+}
+
+pub fn canonicalize_key(s: &str) -> String {
+    let mut modifiers = Vec::new();
+    let mut keys = Vec::new();
+
+    for part in s.split('+').map(|p| p.trim().to_lowercase()) {
+        match canonical_modifier(&part) {
+            Some(modifier) if !modifiers.contains(&modifier) => modifiers.push(modifier),
+            Some(_) => {}
+            None => keys.push(part),
+        }
+    }
+
+    modifiers.sort_by_key(|m| MODIFIER_ORDER.iter().position(|o| o == m));
+    modifiers
+        .into_iter()
+        .map(str::to_string)
+        .chain(keys)
+        .collect::<Vec<_>>()
+        .join("+")
+}
+
+fn normalize_config(config: HotkeyConfig) -> HotkeyConfig {
+    let mut seen_ids = std::collections::HashSet::new();
+    let mut seen_keys = std::collections::HashSet::new();
+    let mut hotkeys = Vec::with_capacity(config.hotkeys.len());
+
+    for mut binding in config.hotkeys {
+        binding.key = canonicalize_key(&binding.key);
+
+        if !seen_ids.insert(binding.id.clone()) {
+            log::warn!("Dropping hotkey {} ({}): duplicate id", binding.id, binding.key);
+            continue;
+        }
+        if binding.enabled && !seen_keys.insert(binding.key.clone()) {
+            log::warn!("Dropping hotkey {} ({}): duplicate key", binding.id, binding.key);
+            continue;
+        }
+        hotkeys.push(binding);
+    }
+
+    HotkeyConfig { hotkeys }
+}
+
 fn parse_hotkey(s: &str) -> Option<HotKey> {
     let parts: Vec<&str> = s.split('+').map(|p| p.trim()).collect();
     if parts.is_empty() {
@@ -149,8 +301,8 @@ fn parse_key_code(s: &str) -> Option<Code> {
 }
 
 pub fn start_hotkey_listener(plugins_dir: PathBuf) -> Result<()> {
-    let (reload_tx, reload_rx) = mpsc::channel::<()>();
-    let _ = RELOAD_SENDER.set(reload_tx);
+    let (command_tx, command_rx) = mpsc::channel::<HotkeyCommand>();
+    let _ = COMMAND_SENDER.set(command_tx);
 
     std::thread::spawn(move || {
         let mut manager = match HotkeyManager::new() {
@@ -169,7 +321,7 @@ pub fn start_hotkey_listener(plugins_dir: PathBuf) -> Result<()> {
 
         let hotkey_receiver = GlobalHotKeyEvent::receiver();
         loop {
-            try_reload_hotkeys(&reload_rx, &mut manager);
+            try_handle_command(&command_rx, &mut manager);
             try_handle_hotkey(hotkey_receiver, &manager, &plugins_dir);
             std::thread::sleep(std::time::Duration::from_millis(50));
         }
@@ -178,11 +330,23 @@ pub fn start_hotkey_listener(plugins_dir: PathBuf) -> Result<()> {
     Ok(())
 }
 
-fn try_reload_hotkeys(reload_rx: &mpsc::Receiver<()>, manager: &mut HotkeyManager) {
-    if reload_rx.try_recv().is_err() {
+fn try_handle_command(command_rx: &mpsc::Receiver<HotkeyCommand>, manager: &mut HotkeyManager) {
+    let Ok(command) = command_rx.try_recv() else {
         return;
+    };
+
+    match command {
+        HotkeyCommand::Reload => reload_hotkeys(manager),
+        HotkeyCommand::Suspend => manager.suspend(),
+        HotkeyCommand::Resume => {
+            if let Err(e) = manager.resume() {
+                log::error!("Failed to resume hotkeys: {}", e);
+            }
+        }
     }
+}
 
+fn reload_hotkeys(manager: &mut HotkeyManager) {
     log::info!("Reloading hotkeys...");
     let config = match manager.load_config() {
         Ok(c) => c,
@@ -234,40 +398,252 @@ fn execute_plugin_action(plugins_dir: &Path, plugin_id: &str, action: &str) {
         return;
     };
 
-    log::info!("Executing: {:?} {}", script.path, action);
-    let mut cmd = std::process::Command::new(script.shell);
-    if let Some(flag) = script.flag {
-        cmd.arg(flag);
-    }
-    let result = cmd
-        .arg(&script.path)
+    let (stdout, stderr) = plugin_output_stdio(plugin_id);
+
+    log::info!("Executing: {:?} {:?} {}", script.program, script.args, action);
+    let result = std::process::Command::new(&script.program)
+        .args(&script.args)
         .arg(action)
         .current_dir(&plugin_dir)
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
+        .stdout(stdout)
+        .stderr(stderr)
         .spawn();
 
     match result {
-        Ok(_) => log::info!("Plugin action started"),
+        Ok(child) => {
+            log::info!("Plugin action started");
+            wait_for_action(child, format!("{}::{}", plugin_id, action));
+        }
         Err(e) => log::error!("Failed to execute plugin action: {}", e),
     }
 }
 
-fn find_plugin_script(plugin_dir: &std::path::Path) -> Option<ScriptInfo> {
+pub(crate) fn plugin_output_stdio(plugin_id: &str) -> (std::process::Stdio, std::process::Stdio) {
+    let capture = crate::settings::AppSettings::load()
+        .map(|s| s.capture_plugin_output)
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to load settings for plugin output capture: {}", e);
+            false
+        });
+    script_stdio(script_output(capture, plugin_id))
+}
+
+#[derive(Debug, PartialEq)]
+enum ScriptOutput {
+    Discard,
+    Log(PathBuf),
+}
+
+fn script_output(capture: bool, plugin_id: &str) -> ScriptOutput {
+    if !capture {
+        return ScriptOutput::Discard;
+    }
+    match paths::plugin_log_path(plugin_id) {
+        Ok(path) => ScriptOutput::Log(path),
+        Err(e) => {
+            log::warn!("Cannot resolve log path for plugin {}: {}", plugin_id, e);
+            ScriptOutput::Discard
+        }
+    }
+}
+
+fn script_stdio(output: ScriptOutput) -> (std::process::Stdio, std::process::Stdio) {
+    use std::process::Stdio;
+
+    let ScriptOutput::Log(path) = output else {
+        return (Stdio::null(), Stdio::null());
+    };
+    match open_log_file(&path) {
+        Ok((stdout, stderr)) => (stdout.into(), stderr.into()),
+        Err(e) => {
+            log::warn!("Failed to open plugin log {:?}: {}", path, e);
+            (Stdio::null(), Stdio::null())
+        }
+    }
+}
+
+fn open_log_file(path: &Path) -> std::io::Result<(std::fs::File, std::fs::File)> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    let stderr = file.try_clone()?;
+    Ok((file, stderr))
+}
+
+fn wait_for_action(mut child: std::process::Child, label: String) {
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) if status.success() => log::info!("Plugin action {} finished", label),
+        Ok(status) => log::warn!("Plugin action {} exited with {}", label, status),
+        Err(e) => log::error!("Failed to wait for plugin action {}: {}", label, e),
+    });
+}
+
+fn find_plugin_script(plugin_dir: &Path) -> Option<ScriptInfo> {
+    let manifest_command = crate::plugins::PluginLoader::load_plugin(plugin_dir)
+        .ok()
+        .and_then(|plugin| plugin.manifest.plugin.run.clone());
+    if let Some(command) = manifest_command {
+        return script_from_command(plugin_dir, &command);
+    }
+
     SCRIPT_RUNNERS.iter().find_map(|(file, shell, flag)| {
         let path = plugin_dir.join(file);
-        path.exists().then_some(ScriptInfo {
-            shell,
-            flag: *flag,
-            path,
-        })
+        if !path.is_file() {
+            return None;
+        }
+        match shell {
+            Some(shell) => Some(ScriptInfo {
+                program: PathBuf::from(shell),
+                args: flag.iter().map(|f| f.into()).chain([path.into_os_string()]).collect(),
+            }),
+            None => is_executable(&path).then_some(ScriptInfo {
+                program: path,
+                args: Vec::new(),
+            }),
+        }
     })
 }
 
+fn script_from_command(plugin_dir: &Path, command: &str) -> Option<ScriptInfo> {
+    let mut parts = command.split_whitespace();
+    let program = parts.next()?;
+    let program = if program.contains('/') || program.contains('\\') {
+        let Some(path) = paths::resolve_within(plugin_dir, program) else {
+            log::warn!("Plugin run command escapes plugin directory: {:?}", command);
+            return None;
+        };
+        path
+    } else {
+        PathBuf::from(program)
+    };
+
+    Some(ScriptInfo {
+        program,
+        args: parts.map(|arg| arg.into()).collect(),
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn suspend_resume_transitions() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let mut manager = HotkeyManager::with_config_path(tmp.path().join("hotkeys.json"));
+        let config: HotkeyConfig = serde_json::from_str(
+            r#"{"hotkeys": [{"id": "h1", "key": "Super+A", "plugin_id": "p", "action": "run", "enabled": true}]}"#,
+        )
+        .unwrap();
+        assert!(!manager.suspended);
+
+        manager.suspend();
+        assert!(manager.suspended);
+        assert!(manager.manager.is_none());
+
+        manager.register_hotkeys(&config).unwrap();
+        assert!(manager.suspended);
+        assert!(manager.manager.is_none());
+        assert!(manager.registered.is_empty());
+
+        manager.resume().unwrap();
+        assert!(!manager.suspended);
+        assert!(manager.manager.is_some());
+    }
+
+    #[test]
+    fn canonicalize_key_cases() {
+        let cases = [
+            ("Shift+Ctrl+R", "ctrl+shift+r"),
+            ("ctrl+shift+r", "ctrl+shift+r"),
+            (" Super + Alt + F1 ", "alt+super+f1"),
+            ("Control+Cmd+Space", "ctrl+super+space"),
+            ("Meta+Win+A", "super+a"),
+            ("R", "r"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(canonicalize_key(input), expected, "input: {:?}", input);
+        }
+        assert_eq!(canonicalize_key("Shift+Ctrl+R"), canonicalize_key("ctrl+shift+r"));
+    }
+
+    #[test]
+    fn normalize_config_dedupes_by_key_and_id() {
+        let config: HotkeyConfig = serde_json::from_str(
+            r#"{"hotkeys": [
+                {"id": "first", "key": "Shift+Ctrl+R", "plugin_id": "p", "action": "run", "enabled": true},
+                {"id": "second", "key": "ctrl+shift+r", "plugin_id": "p", "action": "other", "enabled": true},
+                {"id": "first", "key": "Super+A", "plugin_id": "p", "action": "run", "enabled": true},
+                {"id": "third", "key": "Super+A", "plugin_id": "q", "action": "run", "enabled": false}
+            ]}"#,
+        )
+        .unwrap();
+
+        let normalized = normalize_config(config);
+
+        let result: Vec<(&str, &str)> = normalized
+            .hotkeys
+            .iter()
+            .map(|h| (h.id.as_str(), h.key.as_str()))
+            .collect();
+        assert_eq!(result, vec![("first", "ctrl+shift+r"), ("third", "super+a")]);
+    }
+
+    #[test]
+    fn normalize_config_keeps_enabled_binding_behind_disabled_duplicate() {
+        let config: HotkeyConfig = serde_json::from_str(
+            r#"{"hotkeys": [
+                {"id": "off", "key": "Super+A", "plugin_id": "p", "action": "run", "enabled": false},
+                {"id": "on", "key": "super+a", "plugin_id": "q", "action": "run", "enabled": true},
+                {"id": "shadowed", "key": "A+Super", "plugin_id": "r", "action": "run", "enabled": true}
+            ]}"#,
+        )
+        .unwrap();
+
+        let normalized = normalize_config(config);
+
+        let result: Vec<(&str, bool)> = normalized
+            .hotkeys
+            .iter()
+            .map(|h| (h.id.as_str(), h.enabled))
+            .collect();
+        assert_eq!(result, vec![("off", false), ("on", true)]);
+    }
+
+    #[test]
+    fn load_config_keeps_duplicate_bindings_on_disk() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("hotkeys.json");
+        let stored = r#"{"hotkeys": [
+            {"id": "off", "key": "Super+A", "plugin_id": "p", "action": "run", "enabled": false},
+            {"id": "on", "key": "super+a", "plugin_id": "q", "action": "run", "enabled": true}
+        ]}"#;
+        std::fs::write(&path, stored).unwrap();
+        let manager = HotkeyManager::with_config_path(path);
+
+        let config = manager.load_config().unwrap();
+
+        let result: Vec<(&str, &str)> = config
+            .hotkeys
+            .iter()
+            .map(|h| (h.id.as_str(), h.key.as_str()))
+            .collect();
+        assert_eq!(result, vec![("off", "Super+A"), ("on", "super+a")]);
+    }
+
     #[test]
     fn parse_key_code_cases() {
         let valid = [
@@ -352,6 +728,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn available_key_names_are_sorted_and_parseable() {
+        let names = available_key_names();
+
+        assert!(names.windows(2).all(|w| w[0] < w[1]));
+        for name in &names {
+            assert!(parse_hotkey(name).is_some(), "key: {:?}", name);
+        }
+    }
+
+    #[test]
+    fn modifier_aliases_are_accepted_by_parse_hotkey() {
+        let aliases = modifier_aliases();
+
+        assert_eq!(aliases.iter().map(|(m, _)| *m).collect::<Vec<_>>(), MODIFIER_ORDER);
+        for (modifier, names) in aliases {
+            for name in names {
+                let hotkey = parse_hotkey(&format!("{}+r", name)).unwrap();
+                let expected = parse_hotkey(&format!("{}+r", modifier)).unwrap();
+                assert_eq!(hotkey.mods, expected.mods, "alias: {:?}", name);
+                assert_ne!(hotkey.mods, Modifiers::empty(), "alias: {:?}", name);
+            }
+        }
+    }
+
     #[test]
     fn parse_hotkey_invalid_cases() {
         let cases = [
@@ -419,4 +820,106 @@ mod tests {
             assert_eq!(is_safe_action_id(input), expected, "input: {:?}", input);
         }
     }
+
+    fn write_plugin(dir: &Path, run: Option<&str>, files: &[&str]) {
+        let run_line = run.map(|r| format!("run = \"{}\"\n", r)).unwrap_or_default();
+        std::fs::write(
+            dir.join("plugin.toml"),
+            format!(
+                "[plugin]\nname = \"P\"\ndescription = \"P\"\nversion = \"1.0.0\"\n{}\n[menu]\nlabel = \"P\"\nitems = []\n",
+                run_line
+            ),
+        )
+        .unwrap();
+        for file in files {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_plugin_script_cases() {
+        let cases = [
+            (vec!["run.py"], Some(("python3", "run.py"))),
+            (vec!["run.sh"], Some(("bash", "run.sh"))),
+            (vec!["run.py", "run.sh"], Some(("bash", "run.sh"))),
+            (vec!["run"], None),
+            (vec![], None),
+        ];
+
+        for (files, expected) in cases {
+            let tmp = tempfile::TempDir::new().unwrap();
+            write_plugin(tmp.path(), None, &files);
+
+            let expected = expected.map(|(program, script)| ScriptInfo {
+                program: PathBuf::from(program),
+                args: vec![tmp.path().join(script).into_os_string()],
+            });
+            assert_eq!(find_plugin_script(tmp.path()), expected, "files: {:?}", files);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_plugin_script_runs_executable_directly() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_plugin(tmp.path(), None, &["run"]);
+        let run = tmp.path().join("run");
+        std::fs::set_permissions(&run, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let script = find_plugin_script(tmp.path()).unwrap();
+
+        assert_eq!(script, ScriptInfo { program: run, args: Vec::new() });
+    }
+
+    #[test]
+    fn manifest_run_command_overrides_runners() {
+        let cases: [(&str, Option<(&str, &str)>); 4] = [
+            ("node index.js", Some(("node", "index.js"))),
+            ("bin/tool --quiet", Some(("bin/tool", "--quiet"))),
+            ("../outside", None),
+            ("   ", None),
+        ];
+
+        for (command, expected) in cases {
+            let tmp = tempfile::TempDir::new().unwrap();
+            write_plugin(tmp.path(), Some(command), &["run.sh"]);
+
+            let expected = expected.map(|(program, arg)| ScriptInfo {
+                program: match program.contains('/') {
+                    true => tmp.path().join(program),
+                    false => PathBuf::from(program),
+                },
+                args: vec![arg.into()],
+            });
+            assert_eq!(find_plugin_script(tmp.path()), expected, "command: {:?}", command);
+        }
+    }
+
+    #[test]
+    fn script_output_follows_capture_flag() {
+        assert_eq!(script_output(false, "demo"), ScriptOutput::Discard);
+
+        let ScriptOutput::Log(path) = script_output(true, "demo") else {
+            panic!("capture should log to a file");
+        };
+        assert!(path.ends_with("logs/demo.log"), "path: {:?}", path);
+    }
+
+    #[test]
+    fn open_log_file_appends_and_creates_parent() {
+        use std::io::Write;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("logs").join("demo.log");
+
+        for line in ["first\n", "second\n"] {
+            let (mut stdout, _) = open_log_file(&path).unwrap();
+            stdout.write_all(line.as_bytes()).unwrap();
+        }
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "first\nsecond\n");
+    }
 }
@@ -2,8 +2,11 @@ use global_hotkey::hotkey::Code;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ffi::OsString;
 use std::path::PathBuf;
 
+use crate::migration::Migration;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HotkeyConfig {
     #[serde(default)]
@@ -20,25 +23,90 @@ pub struct HotkeyBinding {
     pub enabled: bool,
 }
 
+pub const MIGRATIONS: &[Migration] = &[wrap_bare_binding_list];
+
+fn wrap_bare_binding_list(value: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+    match value {
+        serde_json::Value::Array(bindings) => Ok(serde_json::json!({ "hotkeys": bindings })),
+        other => Ok(other),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "error", rename_all = "snake_case")]
+pub enum RegistrationStatus {
+    Registered,
+    Failed(String),
+    Invalid,
+    Disabled,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HotkeyStatus {
+    pub id: String,
+    pub key: String,
+    #[serde(flatten)]
+    pub status: RegistrationStatus,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HotkeyStatusReport {
+    pub suspended: bool,
+    pub registered: usize,
+    pub failed: usize,
+    pub invalid: usize,
+    pub disabled: usize,
+    pub hotkeys: Vec<HotkeyStatus>,
+}
+
+impl HotkeyStatusReport {
+    pub fn new(hotkeys: Vec<HotkeyStatus>) -> Self {
+        let mut report = Self::default();
+        for hotkey in &hotkeys {
+            match hotkey.status {
+                RegistrationStatus::Registered => report.registered += 1,
+                RegistrationStatus::Failed(_) => report.failed += 1,
+                RegistrationStatus::Invalid => report.invalid += 1,
+                RegistrationStatus::Disabled => report.disabled += 1,
+            }
+        }
+        report.hotkeys = hotkeys;
+        report
+    }
+
+    pub fn suspended() -> Self {
+        Self {
+            suspended: true,
+            ..Self::default()
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct HotkeyAction {
     pub plugin_id: String,
     pub action: String,
 }
 
+#[derive(Debug, PartialEq)]
 pub struct ScriptInfo {
-    pub shell: &'static str,
-    pub flag: Option<&'static str>,
-    pub path: PathBuf,
+    pub program: PathBuf,
+    pub args: Vec<OsString>,
 }
 
-pub const SCRIPT_RUNNERS: &[(&str, &str, Option<&str>)] = &[
+pub const SCRIPT_RUNNERS: &[(&str, Option<&str>, Option<&str>)] = &[
+    #[cfg(windows)]
+    ("run.bat", Some("cmd"), Some("/c")),
     #[cfg(windows)]
-    ("run.bat", "cmd", Some("/c")),
+    ("run.ps1", Some("powershell"), Some("-File")),
     #[cfg(windows)]
-    ("run.ps1", "powershell", Some("-File")),
+    ("run.py", Some("python"), None),
+    #[cfg(not(windows))]
+    ("run.sh", Some("bash"), None),
+    #[cfg(not(windows))]
+    ("run.py", Some("python3"), None),
     #[cfg(not(windows))]
-    ("run.sh", "bash", None),
+    ("run", None, None),
 ];
 
 pub static KEY_CODE_MAP: Lazy<HashMap<&'static str, Code>> = Lazy::new(|| {
@@ -118,3 +186,65 @@ pub static KEY_CODE_MAP: Lazy<HashMap<&'static str, Code>> = Lazy::new(|| {
         ("pause", Code::Pause),
     ])
 });
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(id: &str, status: RegistrationStatus) -> HotkeyStatus {
+        HotkeyStatus {
+            id: id.to_string(),
+            key: format!("Super+{}", id),
+            status,
+        }
+    }
+
+    #[test]
+    fn status_report_counts_each_outcome() {
+        let report = HotkeyStatusReport::new(vec![
+            status("a", RegistrationStatus::Registered),
+            status("b", RegistrationStatus::Failed("already grabbed".to_string())),
+            status("c", RegistrationStatus::Registered),
+            status("d", RegistrationStatus::Invalid),
+            status("e", RegistrationStatus::Disabled),
+        ]);
+
+        assert_eq!(report.registered, 2);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.invalid, 1);
+        assert_eq!(report.disabled, 1);
+        assert_eq!(report.hotkeys.len(), 5);
+    }
+
+    #[test]
+    fn status_serializes_flat() {
+        let cases = [
+            (RegistrationStatus::Registered, serde_json::json!({"id": "a", "key": "Super+a", "status": "registered"})),
+            (
+                RegistrationStatus::Failed("taken".to_string()),
+                serde_json::json!({"id": "a", "key": "Super+a", "status": "failed", "error": "taken"}),
+            ),
+        ];
+
+        for (registration, expected) in cases {
+            assert_eq!(serde_json::to_value(status("a", registration)).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn bare_binding_list_is_migrated() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("hotkeys.json");
+        std::fs::write(
+            &path,
+            r#"[{"id": "h1", "key": "Super+A", "plugin_id": "p", "action": "run", "enabled": true}]"#,
+        )
+        .unwrap();
+
+        let config: HotkeyConfig = crate::migration::load_json_or_default(&path, MIGRATIONS).unwrap();
+
+        assert_eq!(config.hotkeys.len(), 1);
+        assert_eq!(config.hotkeys[0].id, "h1");
+        assert!(config.hotkeys[0].enabled);
+    }
+}
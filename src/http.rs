@@ -0,0 +1,131 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+pub const USER_AGENT: &str = "qol-tray";
+pub const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+const OFFLINE_ENV: &str = "QOL_OFFLINE";
+
+static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+pub fn offline() -> bool {
+    let configured = crate::settings::AppSettings::load()
+        .map(|s| s.offline)
+        .unwrap_or(false);
+    resolve_offline(std::env::var(OFFLINE_ENV).ok().as_deref(), configured)
+}
+
+fn resolve_offline(env_value: Option<&str>, configured: bool) -> bool {
+    match env_value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+        Some("1" | "true" | "yes" | "on") => true,
+        Some("0" | "false" | "no" | "off") => false,
+        _ => configured,
+    }
+}
+
+pub fn client() -> &'static reqwest::Client {
+    CLIENT.get_or_init(|| build_client(CONNECT_TIMEOUT, REQUEST_TIMEOUT))
+}
+
+fn build_client(connect_timeout: Duration, request_timeout: Duration) -> reqwest::Client {
+    client_builder(connect_timeout, request_timeout)
+        .build()
+        .unwrap_or_else(|e| {
+            log::error!("Failed to build HTTP client, using defaults: {}", e);
+            reqwest::Client::new()
+        })
+}
+
+fn client_builder(connect_timeout: Duration, request_timeout: Duration) -> reqwest::ClientBuilder {
+    reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .connect_timeout(connect_timeout)
+        .timeout(request_timeout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::HeaderMap, routing::get, Router};
+    use std::time::Instant;
+
+    #[test]
+    fn resolve_offline_cases() {
+        let cases = [
+            (None, false, false),
+            (None, true, true),
+            (Some("1"), false, true),
+            (Some(" ON "), false, true),
+            (Some("0"), true, false),
+            (Some("off"), true, false),
+            (Some(""), true, true),
+            (Some("maybe"), false, false),
+        ];
+
+        for (env_value, configured, expected) in cases {
+            assert_eq!(resolve_offline(env_value, configured), expected, "env={:?} configured={}", env_value, configured);
+        }
+    }
+
+    #[test]
+    fn client_is_built_once() {
+        assert!(std::ptr::eq(client(), client()));
+    }
+
+    #[tokio::test]
+    async fn client_sends_user_agent() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = Router::new().route(
+            "/",
+            get(|headers: HeaderMap| async move {
+                headers
+                    .get(axum::http::header::USER_AGENT)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string()
+            }),
+        );
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let body = client()
+            .get(format!("http://{}/", addr))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+
+        assert_eq!(body, USER_AGENT);
+    }
+
+    #[tokio::test]
+    async fn stalled_response_times_out_within_request_timeout() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                held.push(stream);
+            }
+        });
+        let client = build_client(Duration::from_millis(200), Duration::from_millis(300));
+
+        let start = Instant::now();
+        let err = client.get(format!("http://{}/", addr)).send().await.unwrap_err();
+
+        assert!(err.is_timeout(), "{:?}", err);
+        assert!(start.elapsed() < Duration::from_secs(2), "took {:?}", start.elapsed());
+    }
+
+    #[test]
+    fn client_builder_applies_connect_and_request_timeouts() {
+        let builder = format!("{:?}", client_builder(Duration::from_millis(200), Duration::from_secs(30)));
+
+        assert!(builder.contains("connect_timeout: 200ms"), "{}", builder);
+        assert!(builder.contains("timeout: 30s"), "{}", builder);
+    }
+}
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::Path;
+
+pub struct InstanceLock {
+    _file: File,
+}
+
+impl InstanceLock {
+    pub fn acquire(path: &Path) -> Result<Option<Self>> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+
+        match file.try_lock() {
+            Ok(()) => Ok(Some(Self { _file: file })),
+            Err(TryLockError::WouldBlock) => Ok(None),
+            Err(TryLockError::Error(e)) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn second_acquisition_blocked_until_released() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("qol-tray.lock");
+
+        let first = InstanceLock::acquire(&path).unwrap();
+        assert!(first.is_some());
+        assert!(InstanceLock::acquire(&path).unwrap().is_none());
+
+        drop(first);
+        assert!(InstanceLock::acquire(&path).unwrap().is_some());
+    }
+
+    #[test]
+    fn acquire_creates_missing_parent_dir() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("nested").join("qol-tray.lock");
+
+        let lock = InstanceLock::acquire(&path).unwrap();
+
+        assert!(lock.is_some());
+        assert!(path.exists());
+    }
+}
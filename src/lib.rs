@@ -1,11 +1,16 @@
+pub mod cli;
 pub mod daemon;
 pub mod features;
-#[cfg(feature = "dev")]
 pub mod dev;
 pub mod hotkeys;
+pub mod http;
+pub mod instance;
 pub mod menu;
+pub mod migration;
+pub mod notifications;
 pub mod paths;
 pub mod plugins;
+pub mod settings;
 pub mod tray;
 pub mod updates;
 pub mod version;
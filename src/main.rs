@@ -1,30 +1,68 @@
+mod cli;
 mod daemon;
-#[cfg(feature = "dev")]
 mod dev;
 mod features;
 mod hotkeys;
+mod http;
+mod instance;
 mod menu;
+mod migration;
+mod notifications;
 mod paths;
 mod plugins;
+mod settings;
 mod tray;
 mod updates;
 mod version;
 
 use anyhow::Result;
+use clap::Parser;
 use daemon::Daemon;
 use features::FeatureRegistry;
-use plugins::{PluginLoader, PluginManager};
+use instance::InstanceLock;
+use plugins::{PluginConfigManager, PluginLoader, PluginManager};
+use settings::AppSettings;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::broadcast;
 use tray::TrayManager;
 
 fn main() -> Result<()> {
+    let cli = cli::Cli::parse();
+    if let Some(command) = cli.command {
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+        return cli::run(command);
+    }
+
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let _instance = match paths::instance_lock_path().and_then(|p| InstanceLock::acquire(&p)) {
+        Ok(Some(lock)) => Some(lock),
+        Ok(None) => return focus_running_instance(),
+        Err(e) => {
+            log::warn!("Failed to acquire instance lock: {}", e);
+            None
+        }
+    };
+
     log::info!("Starting QoL Tray daemon...");
     tray::platform::run_app(app_init)
 }
 
+fn focus_running_instance() -> Result<()> {
+    let unix_socket = cfg!(unix)
+        && settings::AppSettings::load().map(|s| s.unix_socket).unwrap_or_else(|e| {
+            log::warn!("Failed to load settings: {}", e);
+            false
+        });
+    if unix_socket {
+        log::warn!("QoL Tray is already running and only serves its unix socket, so there is no plugin store URL to open");
+        return Ok(());
+    }
+    log::info!("QoL Tray is already running, opening the plugin store");
+    features::plugin_store::open_store()
+}
+
 fn app_init() -> Result<(TrayManager, Arc<Mutex<PluginManager>>)> {
     let rt = tokio::runtime::Builder::new_multi_thread()
         .enable_all()
@@ -33,12 +71,21 @@ fn app_init() -> Result<(TrayManager, Arc<Mutex<PluginManager>>)> {
     let (shutdown_tx, shutdown_rx, update_available, plugin_manager, feature_registry) =
         rt.block_on(async_init())?;
 
+    let runtime = rt.handle().clone();
+
     // Keep tokio runtime alive in background
     std::thread::spawn(move || {
         rt.block_on(std::future::pending::<()>());
     });
 
-    let tray = TrayManager::new(feature_registry, shutdown_tx, shutdown_rx, update_available)?;
+    let plugin_count = plugin_manager
+        .lock()
+        .map(|manager| manager.plugins().count())
+        .unwrap_or_default();
+    let tray = {
+        let _runtime = runtime.enter();
+        TrayManager::new(feature_registry, shutdown_tx, shutdown_rx, update_available, plugin_count)?
+    };
 
     log::info!("QoL Tray daemon started successfully");
     Ok((tray, plugin_manager))
@@ -55,6 +102,10 @@ async fn async_init() -> Result<(
 
     let (shutdown_tx, shutdown_rx) = broadcast::channel::<()>(1);
 
+    if dev::tools_enabled() {
+        auto_link_discovered_plugins();
+    }
+
     let mut plugin_manager = PluginManager::new();
     plugin_manager.load_plugins()?;
     let plugin_manager = Arc::new(Mutex::new(plugin_manager));
@@ -68,12 +119,15 @@ async fn async_init() -> Result<(
     features::plugin_store::PluginStore::start_server(plugin_manager.clone(), &daemon).await?;
 
     if let Ok(plugins_dir) = PluginLoader::default_plugin_dir() {
+        prune_config_backups(&plugins_dir);
+
         if let Err(e) = hotkeys::start_hotkey_listener(plugins_dir.clone()) {
             log::warn!("Failed to start hotkey listener: {}", e);
         }
 
-        #[cfg(feature = "dev")]
-        daemon.start_discovery(plugins_dir);
+        if dev::tools_enabled() {
+            daemon.start_discovery(plugins_dir, false);
+        }
     }
 
     Ok((
@@ -85,16 +139,71 @@ async fn async_init() -> Result<(
     ))
 }
 
+fn prune_config_backups(plugins_dir: &std::path::Path) {
+    let settings = AppSettings::load().unwrap_or_else(|e| {
+        log::warn!("Failed to load settings: {}", e);
+        AppSettings::default()
+    });
+    let Some(days) = settings.prune_config_backups_after_days else {
+        return;
+    };
+
+    let max_age = Duration::from_secs(days * 24 * 60 * 60);
+    match PluginConfigManager::new().and_then(|m| m.prune_stale_backups(plugins_dir, max_age)) {
+        Ok(pruned) if !pruned.is_empty() => log::info!("Pruned stale config backups: {:?}", pruned),
+        Ok(_) => {}
+        Err(e) => log::warn!("Failed to prune config backups: {}", e),
+    }
+}
+
+fn auto_link_discovered_plugins() {
+    let config = dev::DevConfig::load().unwrap_or_else(|e| {
+        log::warn!("Failed to load dev config: {}", e);
+        dev::DevConfig::default()
+    });
+    if !config.auto_link_discovered {
+        return;
+    }
+    let Ok(plugins_dir) = PluginLoader::default_plugin_dir() else {
+        return;
+    };
+
+    let discovery = dev::discover_plugins(&config, &plugins_dir);
+    let linked = dev::auto_link(&discovery.plugins, &plugins_dir);
+    if !linked.is_empty() {
+        log::info!("Auto-linked {} discovered plugin(s)", linked.len());
+    }
+}
+
 async fn check_for_updates() -> bool {
-    match tokio::time::timeout(Duration::from_secs(2), updates::check_for_updates()).await {
-        Ok(Ok(has_update)) => has_update,
-        Ok(Err(e)) => {
+    if updates::checks_disabled() {
+        log::debug!("Update checks disabled");
+        return false;
+    }
+
+    let cached = updates::cached_update().is_some();
+    let check = tokio::spawn(updates::check_for_updates());
+
+    match tokio::time::timeout(Duration::from_secs(2), check).await {
+        Ok(Ok(Ok(has_update))) => {
+            if has_update {
+                notifications::notify(notifications::Notice::UpdateAvailable {
+                    version: updates::latest_version(),
+                });
+            }
+            has_update
+        }
+        Ok(Ok(Err(e))) => {
             log::debug!("Update check failed: {}", e);
-            false
+            cached
+        }
+        Ok(Err(e)) => {
+            log::debug!("Update check task failed: {}", e);
+            cached
         }
         Err(_) => {
-            log::debug!("Update check timed out");
-            false
+            log::debug!("Update check timed out, continuing in background");
+            cached
         }
     }
 }
@@ -1,10 +1,16 @@
 use super::router::{EventRouter, EventRoute, EventPattern, EventHandler, HandlerResult};
-use crate::plugins::MenuItem as PluginMenuItem;
+use crate::plugins::{get_config_value, ActionType, MenuItem as PluginMenuItem, PluginConfigManager, PluginLoader};
 use crate::features::FeatureRegistry;
-use crate::updates;
-use anyhow::Result;
+use crate::tray::icon::load_menu_icon;
+use crate::{paths, updates};
+use crate::features::task_runner::interpolate;
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::sync::Arc;
-use tray_icon::menu::{Menu, MenuItem, CheckMenuItem, Submenu, PredefinedMenuItem};
+use tray_icon::menu::{Menu, MenuItem, CheckMenuItem, IconMenuItem, IsMenuItem, Submenu, PredefinedMenuItem};
 
 pub fn build_menu(
     feature_registry: Arc<FeatureRegistry>,
@@ -18,8 +24,30 @@ pub fn build_menu(
         if items.is_empty() { continue; }
 
         let feature_id = format!("feature_{}", idx);
-        append_feature_items(&menu, &items, &feature_id);
+        let config = feature.plugin_id().and_then(load_plugin_config);
+        let plugin_dir = feature.plugin_id().and_then(|id| paths::plugins_dir().ok().map(|d| d.join(id)));
+        let mut context = FeatureMenu::new(&feature_id, config.as_ref(), plugin_dir);
+        append_feature_items(&menu, &items, &mut context);
+        if let Some(item) = context.open_ui_item() {
+            let _ = menu.append(&item);
+        }
+
+        if let Some(plugin_id) = feature.plugin_id() {
+            for (event_id, config_key, default) in context.toggles {
+                let route = create_toggle_route(feature_registry.clone(), idx, plugin_id, event_id, config_key, default);
+                all_routes.push(route);
+            }
+            for event_id in context.settings {
+                all_routes.push(create_settings_route(plugin_id, event_id));
+            }
+            if let Some(plugin_dir) = &context.plugin_dir {
+                for (event_id, command) in context.customs {
+                    all_routes.push(create_custom_route(plugin_id, plugin_dir.clone(), event_id, command));
+                }
+            }
+        }
 
+        all_routes.extend(feature.routes(&feature_id)?);
         let route = create_feature_route(feature_registry.clone(), idx, &feature_id);
         all_routes.push(route);
     }
@@ -36,30 +64,129 @@ pub fn build_menu(
     Ok((menu, router))
 }
 
-fn append_feature_items(menu: &Menu, items: &[PluginMenuItem], feature_id: &str) {
+struct FeatureMenu<'a> {
+    feature_id: &'a str,
+    config: Option<&'a Value>,
+    plugin_dir: Option<PathBuf>,
+    toggles: Vec<(String, String, bool)>,
+    settings: Vec<String>,
+    customs: Vec<(String, String)>,
+}
+
+impl<'a> FeatureMenu<'a> {
+    fn new(feature_id: &'a str, config: Option<&'a Value>, plugin_dir: Option<PathBuf>) -> Self {
+        Self {
+            feature_id,
+            config,
+            plugin_dir,
+            toggles: Vec::new(),
+            settings: Vec::new(),
+            customs: Vec::new(),
+        }
+    }
+
+    fn action(&mut self, id: &str, label: &str, action: ActionType, command: Option<&str>, enabled_when: Option<&str>, icon: Option<&str>) -> Box<dyn IsMenuItem> {
+        let full_id = format!("{}::{}", self.feature_id, id);
+        match (action, command) {
+            (ActionType::Settings, _) => self.settings.push(full_id.clone()),
+            (ActionType::Custom, Some(command)) => self.customs.push((full_id.clone(), command.to_string())),
+            (ActionType::Custom, None) => log::warn!("Custom action {} has no command", full_id),
+            _ => {}
+        }
+        let enabled = action_enabled(self.config, enabled_when);
+        let icon = resolve_icon_path(self.plugin_dir.as_deref(), icon).and_then(|path| load_menu_icon(&path));
+        match icon {
+            Some(icon) => Box::new(IconMenuItem::with_id(&full_id, label, enabled, Some(icon), None)),
+            None => Box::new(MenuItem::with_id(&full_id, label, enabled, None)),
+        }
+    }
+
+    fn open_ui_item(&mut self) -> Option<MenuItem> {
+        if !self.plugin_dir.as_deref().is_some_and(wants_open_ui_item) {
+            return None;
+        }
+        let full_id = format!("{}::{}", self.feature_id, OPEN_UI_ITEM_ID);
+        self.settings.push(full_id.clone());
+        Some(MenuItem::with_id(&full_id, "⚙ Open UI", true, None))
+    }
+
+    fn checkbox(&mut self, id: &str, label: &str, checked: bool, action: ActionType, config_key: Option<&str>) -> CheckMenuItem {
+        let full_id = format!("{}::{}", self.feature_id, id);
+        if let (ActionType::ToggleConfig, Some(key)) = (action, config_key) {
+            self.toggles.push((full_id.clone(), key.to_string(), checked));
+        }
+        let checked = checkbox_checked(self.config, config_key, checked);
+        CheckMenuItem::with_id(&full_id, label, true, checked, None)
+    }
+}
+
+const OPEN_UI_ITEM_ID: &str = "open_ui";
+
+fn wants_open_ui_item(plugin_dir: &Path) -> bool {
+    if !plugin_dir.join("ui").join("index.html").is_file() {
+        return false;
+    }
+    let hidden = PluginLoader::load_plugin(plugin_dir)
+        .ok()
+        .and_then(|plugin| plugin.manifest.ui.clone())
+        .is_some_and(|ui| ui.hide_menu_item);
+    !hidden
+}
+
+fn load_plugin_config(plugin_id: &str) -> Option<Value> {
+    match PluginConfigManager::new().and_then(|m| m.get_config(plugin_id)) {
+        Ok(config) => config,
+        Err(e) => {
+            log::warn!("Failed to load config for {}: {}", plugin_id, e);
+            None
+        }
+    }
+}
+
+fn checkbox_checked(config: Option<&Value>, config_key: Option<&str>, default: bool) -> bool {
+    config
+        .zip(config_key)
+        .and_then(|(config, key)| get_config_value(config, key))
+        .and_then(Value::as_bool)
+        .unwrap_or(default)
+}
+
+fn resolve_icon_path(plugin_dir: Option<&Path>, icon: Option<&str>) -> Option<PathBuf> {
+    let (plugin_dir, icon) = plugin_dir.zip(icon)?;
+    let path = paths::resolve_within(plugin_dir, icon);
+    if path.is_none() {
+        log::warn!("Ignoring menu icon outside plugin directory: {}", icon);
+    }
+    path
+}
+
+fn action_enabled(config: Option<&Value>, enabled_when: Option<&str>) -> bool {
+    checkbox_checked(config, enabled_when, true)
+}
+
+fn append_feature_items(menu: &Menu, items: &[PluginMenuItem], context: &mut FeatureMenu) {
     for item in items {
-        append_menu_item_to_menu(menu, item, feature_id);
+        append_menu_item_to_menu(menu, item, context);
     }
 }
 
-fn append_menu_item_to_menu(menu: &Menu, item: &PluginMenuItem, feature_id: &str) {
+fn append_menu_item_to_menu(menu: &Menu, item: &PluginMenuItem, context: &mut FeatureMenu) {
     match item {
         PluginMenuItem::Submenu { id, label, items: sub_items } => {
-            let full_id = format!("{}::{}", feature_id, id);
+            let full_id = format!("{}::{}", context.feature_id, id);
             log::debug!("Creating submenu with ID: {}", full_id);
             let submenu = Submenu::with_id(&full_id, label, true);
             for sub in sub_items {
-                add_menu_item(&submenu, sub, feature_id);
+                add_menu_item(&submenu, sub, context);
             }
             let _ = menu.append(&submenu);
         }
-        PluginMenuItem::Action { id, label, .. } => {
-            let full_id = format!("{}::{}", feature_id, id);
-            let _ = menu.append(&MenuItem::with_id(&full_id, label, true, None));
+        PluginMenuItem::Action { id, label, action, enabled_when, icon, command, .. } => {
+            let item = context.action(id, label, *action, command.as_deref(), enabled_when.as_deref(), icon.as_deref());
+            let _ = menu.append(item.as_ref());
         }
-        PluginMenuItem::Checkbox { id, label, checked, .. } => {
-            let full_id = format!("{}::{}", feature_id, id);
-            let _ = menu.append(&CheckMenuItem::with_id(&full_id, label, true, *checked, None));
+        PluginMenuItem::Checkbox { id, label, checked, action, config_key } => {
+            let _ = menu.append(&context.checkbox(id, label, *checked, *action, config_key.as_deref()));
         }
         PluginMenuItem::Separator => {
             let _ = menu.append(&PredefinedMenuItem::separator());
@@ -83,6 +210,95 @@ fn create_feature_route(
     }
 }
 
+fn create_toggle_route(
+    feature_registry: Arc<FeatureRegistry>,
+    idx: usize,
+    plugin_id: &str,
+    event_id: String,
+    config_key: String,
+    default: bool,
+) -> EventRoute {
+    let plugin_id = plugin_id.to_string();
+    EventRoute {
+        pattern: EventPattern::Exact(event_id),
+        handler: EventHandler::Sync(Box::new(move |event_id| {
+            match crate::features::plugin_store::toggle_plugin_config(&plugin_id, &config_key, default) {
+                Ok(value) => log::info!("Set {}.{} = {}", plugin_id, config_key, value),
+                Err(e) => log::error!("Failed to toggle {}.{}: {}", plugin_id, config_key, e),
+            }
+            if let Some(feature) = feature_registry.features().get(idx) {
+                feature.handle_event(event_id)?;
+            }
+            Ok(HandlerResult::Continue)
+        })),
+    }
+}
+
+fn create_settings_route(plugin_id: &str, event_id: String) -> EventRoute {
+    let plugin_id = plugin_id.to_string();
+    EventRoute {
+        pattern: EventPattern::Exact(event_id),
+        handler: EventHandler::Sync(Box::new(move |_| {
+            crate::features::plugin_store::open_plugin_settings(&plugin_id)?;
+            Ok(HandlerResult::Continue)
+        })),
+    }
+}
+
+fn create_custom_route(plugin_id: &str, plugin_dir: PathBuf, event_id: String, command: String) -> EventRoute {
+    let plugin_id = plugin_id.to_string();
+    EventRoute {
+        pattern: EventPattern::Exact(event_id),
+        handler: EventHandler::Sync(Box::new(move |event_id| {
+            let mut child = custom_command(&plugin_id, &plugin_dir, &command)
+                .spawn()
+                .with_context(|| format!("Failed to run custom action {}", event_id))?;
+            let label = event_id.to_string();
+            std::thread::spawn(move || match child.wait() {
+                Ok(status) if status.success() => log::info!("Custom action {} finished", label),
+                Ok(status) => log::warn!("Custom action {} exited with {}", label, status),
+                Err(e) => log::error!("Failed to wait for custom action {}: {}", label, e),
+            });
+            Ok(HandlerResult::Continue)
+        })),
+    }
+}
+
+fn custom_command(plugin_id: &str, plugin_dir: &Path, template: &str) -> Command {
+    let params = HashMap::from([
+        ("plugin_id".to_string(), shell_quote(plugin_id)),
+        ("plugin_dir".to_string(), shell_quote(&plugin_dir.to_string_lossy())),
+    ]);
+    let (stdout, stderr) = crate::hotkeys::plugin_output_stdio(plugin_id);
+    let mut cmd = shell_command(&interpolate(template, &params));
+    cmd.current_dir(plugin_dir).stdout(stdout).stderr(stderr);
+    cmd
+}
+
+#[cfg(not(windows))]
+fn shell_command(script: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(script);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell_command(script: &str) -> Command {
+    use std::os::windows::process::CommandExt;
+
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").raw_arg(script);
+    cmd
+}
+
+fn shell_quote(value: &str) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", value.replace('"', ""))
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
 fn create_update_route(menu: &Menu) -> EventRoute {
     let version_label = updates::latest_version()
         .map(|v| format!("⬆ Update to v{}", v))
@@ -91,26 +307,18 @@ fn create_update_route(menu: &Menu) -> EventRoute {
 
     EventRoute {
         pattern: EventPattern::Exact("__update__".to_string()),
-        handler: EventHandler::Sync(Box::new(|_| {
-            log::info!("Starting update download and install");
-            spawn_update_task();
-            Ok(HandlerResult::Continue)
+        handler: EventHandler::Async(Box::new(|_| {
+            Box::pin(async {
+                log::info!("Starting update download and install");
+                if let Err(e) = updates::download_and_install().await {
+                    log::error!("Update failed: {}", e);
+                }
+                Ok(HandlerResult::Continue)
+            })
         })),
     }
 }
 
-fn spawn_update_task() {
-    std::thread::spawn(|| {
-        let rt = match tokio::runtime::Runtime::new() {
-            Ok(rt) => rt,
-            Err(e) => { log::error!("Failed to create runtime for update: {}", e); return; }
-        };
-        if let Err(e) = rt.block_on(updates::download_and_install()) {
-            log::error!("Update failed: {}", e);
-        }
-    });
-}
-
 fn create_quit_route(menu: &Menu) -> EventRoute {
     let _ = menu.append(&MenuItem::with_id("__quit__", "Quit", true, None));
 
@@ -123,26 +331,157 @@ fn create_quit_route(menu: &Menu) -> EventRoute {
     }
 }
 
-fn add_menu_item(parent: &Submenu, item: &PluginMenuItem, prefix_id: &str) {
+fn add_menu_item(parent: &Submenu, item: &PluginMenuItem, context: &mut FeatureMenu) {
     match item {
-        PluginMenuItem::Action { id, label, .. } => {
-            let full_id = format!("{}::{}", prefix_id, id);
-            let _ = parent.append(&MenuItem::with_id(&full_id, label, true, None));
+        PluginMenuItem::Action { id, label, action, enabled_when, icon, command, .. } => {
+            let item = context.action(id, label, *action, command.as_deref(), enabled_when.as_deref(), icon.as_deref());
+            let _ = parent.append(item.as_ref());
         }
-        PluginMenuItem::Checkbox { id, label, checked, .. } => {
-            let full_id = format!("{}::{}", prefix_id, id);
-            let _ = parent.append(&CheckMenuItem::with_id(&full_id, label, true, *checked, None));
+        PluginMenuItem::Checkbox { id, label, checked, action, config_key } => {
+            let _ = parent.append(&context.checkbox(id, label, *checked, *action, config_key.as_deref()));
         }
         PluginMenuItem::Separator => {
             let _ = parent.append(&PredefinedMenuItem::separator());
         }
         PluginMenuItem::Submenu { id, label, items } => {
-            let full_id = format!("{}::{}", prefix_id, id);
+            let full_id = format!("{}::{}", context.feature_id, id);
             let submenu = Submenu::with_id(&full_id, label, true);
             for sub in items {
-                add_menu_item(&submenu, sub, prefix_id);
+                add_menu_item(&submenu, sub, context);
             }
             let _ = parent.append(&submenu);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn checkbox_checked_cases() {
+        let config = json!({"feature": {"enabled": true, "label": "on"}});
+
+        let cases = [
+            (Some(&config), Some("feature.enabled"), false, true),
+            (Some(&config), Some("feature.missing"), false, false),
+            (Some(&config), Some("feature.missing"), true, true),
+            (Some(&config), Some("feature.label"), false, false),
+            (Some(&config), None, true, true),
+            (None, Some("feature.enabled"), false, false),
+        ];
+
+        for (config, key, default, expected) in cases {
+            assert_eq!(checkbox_checked(config, key, default), expected, "key: {:?}", key);
+        }
+    }
+
+    #[test]
+    fn action_enabled_cases() {
+        let config = json!({"recording": {"active": false, "paused": true}});
+
+        let cases = [
+            (Some(&config), Some("recording.active"), false),
+            (Some(&config), Some("recording.paused"), true),
+            (Some(&config), Some("recording.missing"), true),
+            (Some(&config), None, true),
+            (None, Some("recording.active"), true),
+        ];
+
+        for (config, key, expected) in cases {
+            assert_eq!(action_enabled(config, key), expected, "key: {:?}", key);
+        }
+    }
+
+    #[test]
+    fn wants_open_ui_item_cases() {
+        let manifest = "[plugin]\nname = \"Demo\"\ndescription = \"Demo\"\nversion = \"1.0.0\"\n\n[menu]\nlabel = \"Demo\"\nitems = []\n";
+        let cases = [
+            (true, "", true),
+            (false, "", false),
+            (true, "\n[ui]\nhide_menu_item = true\n", false),
+            (true, "\n[ui]\nhide_menu_item = false\n", true),
+            (true, "\n[ui]\ncsp = \"default-src 'self'\"\n", true),
+        ];
+
+        for (has_ui, extra, expected) in cases {
+            let tmp = tempfile::TempDir::new().unwrap();
+            std::fs::write(tmp.path().join("plugin.toml"), format!("{}{}", manifest, extra)).unwrap();
+            if has_ui {
+                std::fs::create_dir(tmp.path().join("ui")).unwrap();
+                std::fs::write(tmp.path().join("ui").join("index.html"), "<html></html>").unwrap();
+            }
+
+            assert_eq!(wants_open_ui_item(tmp.path()), expected, "has_ui: {} extra: {:?}", has_ui, extra);
+        }
+    }
+
+    #[test]
+    fn custom_command_interpolates_plugin_placeholders() {
+        let plugin_dir = Path::new("/plugins/demo");
+
+        let cmd = custom_command("plugin-demo", plugin_dir, "notify {{plugin_id}} {{plugin_dir}}/x {{other}}");
+
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(cmd.get_program(), "sh");
+        assert_eq!(args, ["-c", "notify plugin-demo /plugins/demo/x "]);
+        assert_eq!(cmd.get_current_dir(), Some(plugin_dir));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn custom_route_dispatches_command() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let plugin_dir = tmp.path().join("my plugin; touch injected");
+        std::fs::create_dir(&plugin_dir).unwrap();
+        let marker = plugin_dir.join("ran");
+        let route = create_custom_route(
+            "plugin-demo",
+            plugin_dir.clone(),
+            "feature_0::custom".to_string(),
+            "echo {{plugin_id}} > {{plugin_dir}}/ran".to_string(),
+        );
+        let router = EventRouter::new(vec![route]);
+
+        assert_eq!(router.route("feature_0::custom").unwrap(), HandlerResult::Continue);
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while std::fs::read_to_string(&marker).unwrap_or_default().trim() != "plugin-demo" {
+            assert!(std::time::Instant::now() < deadline, "custom command did not run");
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert!(!plugin_dir.join("injected").exists());
+        assert!(!tmp.path().join("injected").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shell_quote_cases() {
+        let cases = [
+            ("plugin-demo", "'plugin-demo'"),
+            ("/plugins/my plugin", "'/plugins/my plugin'"),
+            ("it's; rm -rf x", "'it'\\''s; rm -rf x'"),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(shell_quote(value), expected, "value: {}", value);
+        }
+    }
+
+    #[test]
+    fn resolve_icon_path_cases() {
+        let plugin_dir = Path::new("/plugins/demo");
+
+        let cases = [
+            (Some(plugin_dir), Some("icons/run.png"), Some(plugin_dir.join("icons/run.png"))),
+            (Some(plugin_dir), Some("../escape.png"), None),
+            (Some(plugin_dir), None, None),
+            (None, Some("icons/run.png"), None),
+        ];
+
+        for (dir, icon, expected) in cases {
+            assert_eq!(resolve_icon_path(dir, icon), expected, "icon: {:?}", icon);
+        }
+    }
+}
@@ -1,2 +1,14 @@
 pub mod router;
 pub mod builder;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static REBUILD_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+pub fn request_rebuild() {
+    REBUILD_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+pub fn take_rebuild_request() -> bool {
+    REBUILD_REQUESTED.swap(false, Ordering::SeqCst)
+}
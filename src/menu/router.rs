@@ -1,4 +1,8 @@
 use anyhow::Result;
+use regex::Regex;
+use std::future::Future;
+use std::pin::Pin;
+use tokio::runtime::Handle;
 
 pub struct EventRoute {
     pub pattern: EventPattern,
@@ -8,21 +12,38 @@ pub struct EventRoute {
 pub enum EventPattern {
     Exact(String),
     Prefix(String),
+    Glob(Regex),
 }
 
 impl EventPattern {
+    pub fn glob(pattern: &str) -> Result<Self> {
+        let translated: String = pattern
+            .split('*')
+            .map(|part| part.split('?').map(regex::escape).collect::<Vec<_>>().join("."))
+            .collect::<Vec<_>>()
+            .join(".*");
+        Ok(EventPattern::Glob(Regex::new(&format!("^{}$", translated))?))
+    }
+
     pub fn matches(&self, event_id: &str) -> bool {
         match self {
             EventPattern::Exact(s) => s == event_id,
             EventPattern::Prefix(p) => event_id.starts_with(p),
+            EventPattern::Glob(re) => re.is_match(event_id),
         }
     }
 }
 
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+pub type SyncHandler = Box<dyn Fn(&str) -> Result<HandlerResult> + Send + Sync>;
+pub type AsyncHandler = Box<dyn Fn(&str) -> BoxFuture<Result<HandlerResult>> + Send + Sync>;
+
 pub enum EventHandler {
-    Sync(Box<dyn Fn(&str) -> Result<HandlerResult> + Send + Sync>),
+    Sync(SyncHandler),
+    Async(AsyncHandler),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum HandlerResult {
     Continue,
     Quit,
@@ -30,22 +51,261 @@ pub enum HandlerResult {
 
 pub struct EventRouter {
     routes: Vec<EventRoute>,
+    runtime: Option<Handle>,
+    broadcast: bool,
 }
 
 impl EventRouter {
     pub fn new(routes: Vec<EventRoute>) -> Self {
-        Self { routes }
+        Self {
+            routes,
+            runtime: Handle::try_current().ok(),
+            broadcast: false,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn broadcast(mut self) -> Self {
+        self.broadcast = true;
+        self
+    }
+
+    fn matching(&self, event_id: &str) -> Vec<&EventHandler> {
+        let matches = self.routes.iter().filter(|route| route.pattern.matches(event_id));
+        let handlers: Vec<&EventHandler> = match self.broadcast {
+            true => matches.map(|route| &route.handler).collect(),
+            false => matches.take(1).map(|route| &route.handler).collect(),
+        };
+        if handlers.is_empty() {
+            log::warn!("No route found for event: {}", event_id);
+        }
+        handlers
     }
 
     pub fn route(&self, event_id: &str) -> Result<HandlerResult> {
-        for route in &self.routes {
-            if route.pattern.matches(event_id) {
-                let EventHandler::Sync(f) = &route.handler;
-                return f(event_id);
+        let results = self
+            .matching(event_id)
+            .into_iter()
+            .map(|handler| self.dispatch(handler, event_id))
+            .collect();
+        combine_results(event_id, results)
+    }
+
+    fn dispatch(&self, handler: &EventHandler, event_id: &str) -> Result<HandlerResult> {
+        match handler {
+            EventHandler::Sync(f) => f(event_id),
+            EventHandler::Async(f) => {
+                let Some(runtime) = &self.runtime else {
+                    anyhow::bail!("No async runtime available for event: {}", event_id);
+                };
+                let future = f(event_id);
+                let event_id = event_id.to_string();
+                runtime.spawn(async move {
+                    if let Err(e) = future.await {
+                        log::error!("Error handling menu event {}: {}", event_id, e);
+                    }
+                });
+                Ok(HandlerResult::Continue)
             }
         }
+    }
+
+    #[allow(dead_code)]
+    pub async fn route_async(&self, event_id: &str) -> Result<HandlerResult> {
+        let mut results = Vec::new();
+        for handler in self.matching(event_id) {
+            let result = match (handler, &self.runtime) {
+                (EventHandler::Sync(f), _) => f(event_id),
+                (EventHandler::Async(f), Some(runtime)) => runtime.spawn(f(event_id)).await.unwrap_or_else(|e| Err(e.into())),
+                (EventHandler::Async(f), None) => f(event_id).await,
+            };
+            results.push(result);
+        }
+        combine_results(event_id, results)
+    }
+}
+
+fn combine_results(event_id: &str, results: Vec<Result<HandlerResult>>) -> Result<HandlerResult> {
+    let mut outcome = HandlerResult::Continue;
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(HandlerResult::Quit) => outcome = HandlerResult::Quit,
+            Ok(HandlerResult::Continue) => {}
+            Err(e) => errors.push(e),
+        }
+    }
+
+    match errors.len() {
+        0 => Ok(outcome),
+        1 => Err(errors.remove(0)),
+        count => {
+            let messages: Vec<String> = errors.iter().map(|e| format!("{:#}", e)).collect();
+            anyhow::bail!("{} handlers failed for event {}: {}", count, event_id, messages.join("; "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn async_route(id: &str, result: fn() -> HandlerResult, calls: Arc<AtomicUsize>) -> EventRoute {
+        EventRoute {
+            pattern: EventPattern::Exact(id.to_string()),
+            handler: EventHandler::Async(Box::new(move |_| {
+                let calls = calls.clone();
+                Box::pin(async move {
+                    tokio::task::yield_now().await;
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    Ok(result())
+                })
+            })),
+        }
+    }
+
+    #[test]
+    fn pattern_matches_cases() {
+        let cases = [
+            (EventPattern::Exact("__quit__".into()), "__quit__", true),
+            (EventPattern::Exact("__quit__".into()), "__quit__x", false),
+            (EventPattern::Prefix("feature_0::".into()), "feature_0::run", true),
+            (EventPattern::Prefix("feature_0::".into()), "feature_1::run", false),
+            (EventPattern::glob("*::quit").unwrap(), "feature_0::quit", true),
+            (EventPattern::glob("*::quit").unwrap(), "feature_0::run", false),
+            (EventPattern::glob("*::quit").unwrap(), "feature_0::quit::now", false),
+            (EventPattern::glob("feature_?::*").unwrap(), "feature_3::settings", true),
+            (EventPattern::glob("feature_?::*").unwrap(), "feature_12::settings", false),
+            (EventPattern::glob("a.b*").unwrap(), "a.bc", true),
+            (EventPattern::glob("a.b*").unwrap(), "axbc", false),
+        ];
+
+        for (pattern, event_id, expected) in cases {
+            assert_eq!(pattern.matches(event_id), expected, "event: {}", event_id);
+        }
+    }
+
+    #[tokio::test]
+    async fn route_async_awaits_async_handlers() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = EventRouter::new(vec![
+            async_route("__quit__", || HandlerResult::Quit, calls.clone()),
+            async_route("__other__", || HandlerResult::Continue, calls.clone()),
+            EventRoute {
+                pattern: EventPattern::Prefix("feature_0::".to_string()),
+                handler: EventHandler::Sync(Box::new(|_| Ok(HandlerResult::Quit))),
+            },
+        ]);
+
+        let cases = [
+            ("__quit__", HandlerResult::Quit, 1),
+            ("__other__", HandlerResult::Continue, 2),
+            ("feature_0::run", HandlerResult::Quit, 2),
+            ("unknown", HandlerResult::Continue, 2),
+        ];
+
+        for (event_id, expected, expected_calls) in cases {
+            assert_eq!(router.route_async(event_id).await.unwrap(), expected, "event: {}", event_id);
+            assert_eq!(calls.load(Ordering::SeqCst), expected_calls, "event: {}", event_id);
+        }
+    }
+
+    #[tokio::test]
+    async fn route_async_returns_async_handler_results_to_caller() {
+        let router = EventRouter::new(vec![
+            async_route("__quit__", || HandlerResult::Quit, Arc::default()),
+            EventRoute {
+                pattern: EventPattern::Exact("__fail__".to_string()),
+                handler: EventHandler::Async(Box::new(|_| Box::pin(async { Err::<HandlerResult, _>(anyhow::anyhow!("async failed")) }))),
+            },
+        ]);
+
+        assert_eq!(router.route_async("__quit__").await.unwrap(), HandlerResult::Quit);
+        assert_eq!(router.route_async("__fail__").await.unwrap_err().to_string(), "async failed");
+    }
+
+    #[test]
+    fn route_spawns_async_handlers_on_shared_runtime() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = {
+            let _guard = runtime.enter();
+            EventRouter::new(vec![async_route("__update__", || HandlerResult::Continue, calls.clone())])
+        };
+
+        assert_eq!(router.route("__update__").unwrap(), HandlerResult::Continue);
+
+        runtime.block_on(async {
+            while calls.load(Ordering::SeqCst) == 0 {
+                tokio::task::yield_now().await;
+            }
+        });
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    fn sync_route(pattern: EventPattern, result: Result<HandlerResult, &'static str>, calls: Arc<AtomicUsize>) -> EventRoute {
+        EventRoute {
+            pattern,
+            handler: EventHandler::Sync(Box::new(move |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                result.map_err(|message| anyhow::anyhow!(message))
+            })),
+        }
+    }
+
+    fn overlapping_routes(calls: &Arc<AtomicUsize>) -> Vec<EventRoute> {
+        vec![
+            sync_route(EventPattern::Exact("feature_0::run".into()), Err("first failed"), calls.clone()),
+            sync_route(EventPattern::Prefix("feature_0::".into()), Ok(HandlerResult::Continue), calls.clone()),
+            sync_route(EventPattern::glob("*::run").unwrap(), Err("second failed"), calls.clone()),
+            sync_route(EventPattern::Exact("other".into()), Ok(HandlerResult::Quit), calls.clone()),
+        ]
+    }
+
+    #[test]
+    fn first_match_stops_at_first_route() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = EventRouter::new(overlapping_routes(&calls));
+
+        let err = router.route("feature_0::run").unwrap_err();
+
+        assert_eq!(err.to_string(), "first failed");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn broadcast_runs_all_matches_and_collects_errors() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = EventRouter::new(overlapping_routes(&calls)).broadcast();
+
+        let err = router.route("feature_0::run").unwrap_err().to_string();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert!(err.contains("2 handlers failed"), "error: {}", err);
+        assert!(err.contains("first failed") && err.contains("second failed"), "error: {}", err);
+    }
+
+    #[tokio::test]
+    async fn broadcast_preserves_quit_from_any_handler() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = EventRouter::new(vec![
+            sync_route(EventPattern::Prefix("feature_0::".into()), Ok(HandlerResult::Continue), calls.clone()),
+            sync_route(EventPattern::glob("*::quit").unwrap(), Ok(HandlerResult::Quit), calls.clone()),
+        ])
+        .broadcast();
+
+        assert_eq!(router.route("feature_0::quit").unwrap(), HandlerResult::Quit);
+        assert_eq!(router.route_async("feature_0::quit").await.unwrap(), HandlerResult::Quit);
+        assert_eq!(router.route("feature_0::run").unwrap(), HandlerResult::Continue);
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn route_rejects_async_handlers_without_runtime() {
+        let router = EventRouter::new(vec![async_route("__update__", || HandlerResult::Continue, Arc::default())]);
 
-        log::warn!("No route found for event: {}", event_id);
-        Ok(HandlerResult::Continue)
+        assert!(router.route("__update__").is_err());
     }
 }
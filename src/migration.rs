@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+pub type Migration = fn(Value) -> Result<Value>;
+
+pub fn load_json<T: DeserializeOwned>(path: &Path, migrations: &[Migration]) -> Result<T> {
+    let content = std::fs::read_to_string(path)?;
+    parse_or_migrate(path, &content, migrations)
+}
+
+pub fn load_json_or_default<T>(path: &Path, migrations: &[Migration]) -> Result<T>
+where
+    T: DeserializeOwned + Default,
+{
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    match parse_or_migrate(path, &content, migrations) {
+        Ok(config) => Ok(config),
+        Err(e) => {
+            let backup = backup_path(path);
+            log::warn!("Config {:?} is unreadable ({:#}), backing up to {:?}", path, e, backup);
+            std::fs::copy(path, &backup).with_context(|| format!("Failed to back up {:?}", path))?;
+            Ok(T::default())
+        }
+    }
+}
+
+fn parse_or_migrate<T: DeserializeOwned>(path: &Path, content: &str, migrations: &[Migration]) -> Result<T> {
+    let parse_error = match serde_json::from_str(content) {
+        Ok(config) => return Ok(config),
+        Err(e) => e,
+    };
+
+    let value = migrate(content, migrations).with_context(|| format!("{}; migration failed", parse_error))?;
+    let config = serde_json::from_value(value.clone()).with_context(|| format!("{}; migration failed", parse_error))?;
+    match persist(path, &value) {
+        Ok(()) => log::info!("Migrated config {:?}", path),
+        Err(e) => log::warn!("Migrated config {:?} but failed to save it: {}", path, e),
+    }
+    Ok(config)
+}
+
+fn migrate(content: &str, migrations: &[Migration]) -> Result<Value> {
+    let mut value: Value = serde_json::from_str(content)?;
+    let version = value
+        .get("version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as usize;
+
+    for migration in migrations.iter().skip(version) {
+        value = migration(value)?;
+    }
+
+    if let Value::Object(map) = &mut value {
+        map.insert("version".to_string(), migrations.len().into());
+    }
+    Ok(value)
+}
+
+fn persist(path: &Path, value: &Value) -> Result<()> {
+    crate::paths::atomic_write(path, serde_json::to_string_pretty(value)?)
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    struct Config {
+        items: Vec<String>,
+    }
+
+    fn wrap_items(value: Value) -> Result<Value> {
+        match value {
+            Value::Array(items) => Ok(json!({ "items": items })),
+            other => Ok(other),
+        }
+    }
+
+    fn fail_migration(_: Value) -> Result<Value> {
+        anyhow::bail!("migration should not run")
+    }
+
+    const MIGRATIONS: &[Migration] = &[wrap_items];
+
+    #[test]
+    fn current_config_loads_without_migration() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.json");
+        std::fs::write(&path, r#"{"items": ["a"]}"#).unwrap();
+
+        let config: Config = load_json_or_default(&path, MIGRATIONS).unwrap();
+
+        assert_eq!(config.items, vec!["a"]);
+    }
+
+    #[test]
+    fn old_shaped_config_is_migrated() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.json");
+        std::fs::write(&path, r#"["a", "b"]"#).unwrap();
+
+        let config: Config = load_json_or_default(&path, MIGRATIONS).unwrap();
+
+        assert_eq!(config.items, vec!["a", "b"]);
+        assert!(!backup_path(&path).exists());
+    }
+
+    #[test]
+    fn migrated_config_is_saved_with_current_version() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.json");
+        std::fs::write(&path, r#"["a", "b"]"#).unwrap();
+
+        let _: Config = load_json_or_default(&path, MIGRATIONS).unwrap();
+        let saved: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let reloaded: Config = load_json(&path, &[fail_migration]).unwrap();
+
+        assert_eq!(saved, json!({ "items": ["a", "b"], "version": 1 }));
+        assert_eq!(reloaded.items, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn config_at_current_version_skips_migrations() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.json");
+        let content = r#"{"items": "a", "version": 1}"#;
+        std::fs::write(&path, content).unwrap();
+
+        let err = load_json::<Config>(&path, &[fail_migration]).unwrap_err();
+
+        assert!(!format!("{:#}", err).contains("should not run"), "error: {:#}", err);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+    }
+
+    #[test]
+    fn unmigratable_config_is_preserved_as_bak() {
+        let cases = [r#"{"items": 42}"#, "not json {{{"];
+
+        for content in cases {
+            let tmp = tempfile::TempDir::new().unwrap();
+            let path = tmp.path().join("config.json");
+            std::fs::write(&path, content).unwrap();
+
+            let config: Config = load_json_or_default(&path, MIGRATIONS).unwrap();
+
+            assert_eq!(config, Config::default(), "content: {}", content);
+            assert_eq!(std::fs::read_to_string(backup_path(&path)).unwrap(), content);
+            assert_eq!(backup_path(&path).file_name().unwrap(), "config.json.bak");
+        }
+    }
+
+    #[test]
+    fn load_json_reports_unreadable_config_without_backup() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.json");
+        std::fs::write(&path, r#"["a"]"#).unwrap();
+
+        let migrated: Config = load_json(&path, MIGRATIONS).unwrap();
+        std::fs::write(&path, "not json {{{").unwrap();
+        let unreadable = load_json::<Config>(&path, MIGRATIONS);
+
+        assert_eq!(migrated.items, vec!["a"]);
+        assert!(unreadable.is_err());
+        assert!(!backup_path(&path).exists());
+    }
+
+    #[test]
+    fn missing_config_returns_default() {
+        let tmp = tempfile::TempDir::new().unwrap();
+
+        let config: Config = load_json_or_default(&tmp.path().join("missing.json"), MIGRATIONS).unwrap();
+
+        assert_eq!(config, Config::default());
+    }
+}
@@ -0,0 +1,77 @@
+use crate::settings::AppSettings;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Notice {
+    UpdateAvailable { version: Option<String> },
+    PluginInstalled { id: String },
+    PluginUpdated { id: String },
+    PluginUninstalled { id: String },
+}
+
+impl Notice {
+    pub fn message(&self) -> (String, String) {
+        match self {
+            Notice::UpdateAvailable { version: Some(version) } => (
+                "Update available".to_string(),
+                format!("QoL Tray v{} is ready to install", version),
+            ),
+            Notice::UpdateAvailable { version: None } => (
+                "Update available".to_string(),
+                "A new version of QoL Tray is ready to install".to_string(),
+            ),
+            Notice::PluginInstalled { id } => ("Plugin installed".to_string(), format!("{} is ready to use", id)),
+            Notice::PluginUpdated { id } => ("Plugin updated".to_string(), format!("{} was updated", id)),
+            Notice::PluginUninstalled { id } => ("Plugin uninstalled".to_string(), format!("{} was removed", id)),
+        }
+    }
+}
+
+pub fn notify(notice: Notice) {
+    let enabled = AppSettings::load().map(|s| s.notifications).unwrap_or_else(|e| {
+        log::warn!("Failed to load settings: {}", e);
+        true
+    });
+    if !enabled {
+        return;
+    }
+
+    let (summary, body) = notice.message();
+    std::thread::spawn(move || {
+        let result = notify_rust::Notification::new()
+            .appname("QoL Tray")
+            .summary(&summary)
+            .body(&body)
+            .show();
+        if let Err(e) = result {
+            log::debug!("Failed to show notification: {}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_cases() {
+        let cases = [
+            (
+                Notice::UpdateAvailable { version: Some("1.2.3".to_string()) },
+                "Update available",
+                "QoL Tray v1.2.3 is ready to install",
+            ),
+            (
+                Notice::UpdateAvailable { version: None },
+                "Update available",
+                "A new version of QoL Tray is ready to install",
+            ),
+            (Notice::PluginInstalled { id: "demo".to_string() }, "Plugin installed", "demo is ready to use"),
+            (Notice::PluginUpdated { id: "demo".to_string() }, "Plugin updated", "demo was updated"),
+            (Notice::PluginUninstalled { id: "demo".to_string() }, "Plugin uninstalled", "demo was removed"),
+        ];
+
+        for (notice, summary, body) in cases {
+            assert_eq!(notice.message(), (summary.to_string(), body.to_string()), "{:?}", notice);
+        }
+    }
+}
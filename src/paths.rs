@@ -1,5 +1,9 @@
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 pub fn is_safe_path_component(s: &str) -> bool {
     !s.is_empty()
@@ -10,10 +14,26 @@ pub fn is_safe_path_component(s: &str) -> bool {
         && s != "."
 }
 
+pub fn resolve_within(base: &Path, relative: &str) -> Option<PathBuf> {
+    let relative = Path::new(relative);
+    let stays_inside = relative
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_) | std::path::Component::CurDir));
+    stays_inside.then(|| base.join(relative))
+}
+
+pub const CONFIG_DIR_ENV: &str = "QOL_CONFIG_DIR";
+
 pub fn config_dir() -> Result<PathBuf> {
-    dirs::config_dir()
+    resolve_config_dir(std::env::var_os(CONFIG_DIR_ENV), dirs::config_dir())
         .context("Could not determine config directory")
-        .map(|p| p.join("qol-tray"))
+}
+
+fn resolve_config_dir(override_dir: Option<std::ffi::OsString>, platform_dir: Option<PathBuf>) -> Option<PathBuf> {
+    match override_dir.filter(|dir| !dir.is_empty()) {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => platform_dir.map(|p| p.join("qol-tray")),
+    }
 }
 
 pub fn plugins_dir() -> Result<PathBuf> {
@@ -28,6 +48,26 @@ pub fn plugin_configs_path() -> Result<PathBuf> {
     config_dir().map(|p| p.join("plugin-configs.json"))
 }
 
+pub fn task_runner_config_path() -> Result<PathBuf> {
+    config_dir().map(|p| p.join("task-runner.json"))
+}
+
+pub fn settings_path() -> Result<PathBuf> {
+    config_dir().map(|p| p.join("settings.json"))
+}
+
+pub fn socket_path() -> Result<PathBuf> {
+    config_dir().map(|p| p.join("qol.sock"))
+}
+
+pub fn plugin_log_path(plugin_id: &str) -> Result<PathBuf> {
+    config_dir().map(|p| p.join("logs").join(format!("{}.log", plugin_id)))
+}
+
+pub fn instance_lock_path() -> Result<PathBuf> {
+    config_dir().map(|p| p.join(".lock"))
+}
+
 pub fn github_token_path() -> Result<PathBuf> {
     config_dir().map(|p| p.join(".github-token"))
 }
@@ -36,20 +76,121 @@ pub fn plugin_cache_path() -> Result<PathBuf> {
     config_dir().map(|p| p.join(".plugin-cache.json"))
 }
 
-#[cfg(feature = "dev")]
+pub fn update_cache_path() -> Result<PathBuf> {
+    config_dir().map(|p| p.join(".update-cache.json"))
+}
+
+pub fn cover_cache_dir() -> Result<PathBuf> {
+    config_dir().map(|p| p.join("cover-cache"))
+}
+
 pub fn dev_config_path() -> Result<PathBuf> {
     config_dir().map(|p| p.join("dev.json"))
 }
 
+pub fn atomic_write(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    atomic_write_via(path, &atomic_temp_path(path)?, contents.as_ref(), None)
+}
+
+pub fn atomic_write_private(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    atomic_write_via(path, &atomic_temp_path(path)?, contents.as_ref(), Some(0o600))
+}
+
+fn atomic_write_via(path: &Path, tmp_path: &Path, contents: &[u8], mode: Option<u32>) -> Result<()> {
+    let result = write_and_sync(tmp_path, contents, mode)
+        .and_then(|()| match mode {
+            Some(_) => Ok(()),
+            None => copy_permissions(path, tmp_path),
+        })
+        .and_then(|()| std::fs::rename(tmp_path, path));
+
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(tmp_path);
+        return Err(e).with_context(|| format!("Failed to write {:?}", path));
+    }
+    Ok(())
+}
+
+fn atomic_temp_path(path: &Path) -> Result<PathBuf> {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .with_context(|| format!("Invalid file path: {:?}", path))?;
+    let sequence = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    Ok(path.with_file_name(format!(".{}.{}.{}.tmp", file_name, std::process::id(), sequence)))
+}
+
+fn write_and_sync(path: &Path, contents: &[u8], mode: Option<u32>) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(mode);
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+
+    let mut file = options.open(path)?;
+    file.write_all(contents)?;
+    file.sync_all()
+}
+
+fn copy_permissions(from: &Path, to: &Path) -> std::io::Result<()> {
+    match std::fs::metadata(from) {
+        Ok(metadata) => std::fs::set_permissions(to, metadata.permissions()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
 pub fn open_url(url: &str) -> Result<()> {
     open::that(url)?;
     Ok(())
 }
 
+pub fn open_path(path: &Path) -> Result<()> {
+    open::that(path).with_context(|| format!("Failed to open {:?}", path))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn resolve_within_cases() {
+        let base = Path::new("/plugins/demo");
+        let cases = [
+            ("icons/run.png", Some(base.join("icons/run.png"))),
+            ("./run.png", Some(base.join("./run.png"))),
+            ("../other/run.png", None),
+            ("icons/../../run.png", None),
+            ("/etc/passwd", None),
+        ];
+
+        for (relative, expected) in cases {
+            assert_eq!(resolve_within(base, relative), expected, "relative: {}", relative);
+        }
+    }
+
+    #[test]
+    fn resolve_config_dir_cases() {
+        let platform = Some(PathBuf::from("/home/user/.config"));
+
+        let cases = [
+            (Some("/tmp/profile"), platform.clone(), Some(PathBuf::from("/tmp/profile"))),
+            (Some("/tmp/profile"), None, Some(PathBuf::from("/tmp/profile"))),
+            (None, platform.clone(), Some(PathBuf::from("/home/user/.config/qol-tray"))),
+            (Some(""), platform.clone(), Some(PathBuf::from("/home/user/.config/qol-tray"))),
+            (None, None, None),
+        ];
+
+        for (override_dir, platform_dir, expected) in cases {
+            let override_dir = override_dir.map(std::ffi::OsString::from);
+            assert_eq!(resolve_config_dir(override_dir.clone(), platform_dir), expected, "override: {:?}", override_dir);
+        }
+    }
+
     #[test]
     fn paths_have_correct_suffixes() {
         let cases: Vec<(Result<PathBuf>, &str)> = vec![
@@ -57,8 +198,14 @@ mod tests {
             (plugins_dir(), "qol-tray/plugins"),
             (hotkeys_path(), "hotkeys.json"),
             (plugin_configs_path(), "plugin-configs.json"),
+            (settings_path(), "settings.json"),
+            (task_runner_config_path(), "task-runner.json"),
+            (instance_lock_path(), ".lock"),
+            (plugin_log_path("demo"), "logs/demo.log"),
+            (socket_path(), "qol.sock"),
             (github_token_path(), ".github-token"),
             (plugin_cache_path(), ".plugin-cache.json"),
+            (update_cache_path(), ".update-cache.json"),
         ];
 
         for (result, expected_suffix) in cases {
@@ -67,6 +214,102 @@ mod tests {
         }
     }
 
+    #[test]
+    fn atomic_write_replaces_existing_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.json");
+        std::fs::write(&path, "{\"old\": true}").unwrap();
+
+        atomic_write(&path, "{\"new\": true}").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"new\": true}");
+        assert_eq!(std::fs::read_dir(tmp.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn failed_atomic_write_keeps_existing_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.json");
+        std::fs::write(&path, "{\"valid\": true}").unwrap();
+        let tmp_path = atomic_temp_path(&path).unwrap();
+        std::fs::create_dir(&tmp_path).unwrap();
+
+        let result = atomic_write_via(&path, &tmp_path, b"{\"trunc", None);
+
+        assert!(result.is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"valid\": true}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn atomic_write_keeps_existing_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("settings.json");
+        std::fs::write(&path, "{}").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+        atomic_write(&path, "{\"a\": 1}").unwrap();
+
+        assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "{\"a\": 1}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn atomic_write_private_restricts_to_owner() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let cases = [None, Some(0o644)];
+
+        for existing_mode in cases {
+            let tmp = tempfile::TempDir::new().unwrap();
+            let path = tmp.path().join(".github-token");
+            if let Some(mode) = existing_mode {
+                std::fs::write(&path, "old").unwrap();
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+            }
+
+            atomic_write_private(&path, "token").unwrap();
+
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode, 0o600, "existing mode: {:?}", existing_mode);
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), "token");
+        }
+    }
+
+    #[test]
+    fn atomic_temp_paths_are_unique_per_call() {
+        let path = Path::new("/config/config.json");
+
+        assert_ne!(atomic_temp_path(path).unwrap(), atomic_temp_path(path).unwrap());
+    }
+
+    #[test]
+    fn concurrent_atomic_writes_do_not_collide() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("config.json");
+
+        let writers: Vec<_> = (0..8)
+            .map(|writer| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..25 {
+                        atomic_write(&path, format!("{{\"writer\": {}}}", writer)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("{\"writer\": "), "content: {}", content);
+        assert_eq!(std::fs::read_dir(tmp.path()).unwrap().count(), 1);
+    }
+
     #[test]
     fn is_safe_path_component_cases() {
         let valid = [
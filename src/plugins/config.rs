@@ -3,9 +3,12 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct PluginConfigs {
+    #[serde(default, rename = "_updated_at", skip_serializing_if = "HashMap::is_empty")]
+    pub updated_at: HashMap<String, u64>,
     #[serde(flatten)]
     pub configs: HashMap<String, serde_json::Value>,
 }
@@ -16,6 +19,18 @@ pub struct PluginConfigManager {
 
 use crate::paths::is_safe_path_component;
 
+impl PluginConfigs {
+    pub fn record(&mut self, plugin_id: &str, config: serde_json::Value) {
+        self.configs.insert(plugin_id.to_string(), config);
+        self.updated_at.insert(plugin_id.to_string(), unix_now());
+    }
+
+    fn remove(&mut self, plugin_id: &str) -> bool {
+        self.updated_at.remove(plugin_id);
+        self.configs.remove(plugin_id).is_some()
+    }
+}
+
 impl PluginConfigManager {
     pub fn new() -> Result<Self> {
         let config_path = paths::plugin_configs_path()?;
@@ -30,19 +45,13 @@ impl PluginConfigManager {
     }
 
     pub fn load_configs(&self) -> Result<PluginConfigs> {
-        if !self.config_path.exists() {
-            return Ok(PluginConfigs::default());
-        }
-
-        let content = std::fs::read_to_string(&self.config_path)?;
-        let configs: PluginConfigs = serde_json::from_str(&content)?;
-        Ok(configs)
+        crate::migration::load_json_or_default(&self.config_path, &[])
     }
 
     pub fn save_configs(&self, configs: &PluginConfigs) -> Result<()> {
         ensure_parent_dir(&self.config_path)?;
         let content = serde_json::to_string_pretty(configs)?;
-        std::fs::write(&self.config_path, content)?;
+        paths::atomic_write(&self.config_path, content)?;
         Ok(())
     }
 
@@ -74,18 +83,180 @@ impl PluginConfigManager {
         write_plugin_config(plugin_id, &config)?;
 
         let mut configs = self.load_configs()?;
-        configs.configs.insert(plugin_id.to_string(), config);
+        configs.record(plugin_id, config);
         self.save_configs(&configs)?;
 
         Ok(())
     }
+
+    pub fn delete_config(&self, plugin_id: &str, plugin_dir: &Path) -> Result<bool> {
+        let live_path = plugin_dir.join("config.json");
+        let had_live = live_path.exists();
+        if had_live {
+            std::fs::remove_file(&live_path)?;
+        }
+
+        let mut configs = self.load_configs()?;
+        let had_backup = configs.remove(plugin_id);
+        if had_backup {
+            self.save_configs(&configs)?;
+        }
+
+        Ok(had_live || had_backup)
+    }
+
+    pub fn reset_config(&self, plugin_id: &str, plugin_dir: &Path) -> Result<bool> {
+        self.delete_config(plugin_id, plugin_dir)?;
+
+        let default_path = plugin_dir.join("config.default.json");
+        if !default_path.exists() {
+            return Ok(false);
+        }
+
+        let content = std::fs::read_to_string(&default_path)?;
+        let config: serde_json::Value = serde_json::from_str(&content)?;
+        paths::atomic_write(&plugin_dir.join("config.json"), serde_json::to_string_pretty(&config)?)?;
+        Ok(true)
+    }
+
+    pub fn prune_stale_backups(&self, plugins_dir: &Path, max_age: Duration) -> Result<Vec<String>> {
+        let mut configs = self.load_configs()?;
+        let now = unix_now();
+
+        let untracked: Vec<String> = configs
+            .configs
+            .keys()
+            .filter(|id| !configs.updated_at.contains_key(*id))
+            .cloned()
+            .collect();
+        for id in &untracked {
+            configs.updated_at.insert(id.clone(), now);
+        }
+
+        let stale = select_stale_backups(&configs, now, max_age, |id| plugins_dir.join(id).exists());
+        for id in &stale {
+            configs.remove(id);
+        }
+
+        if !stale.is_empty() || !untracked.is_empty() {
+            self.save_configs(&configs)?;
+        }
+
+        Ok(stale)
+    }
+
+    pub fn toggle_config_value(&self, plugin_id: &str, key: &str, default: bool) -> Result<bool> {
+        let mut config = self.get_config(plugin_id)?.unwrap_or_else(|| serde_json::json!({}));
+        let toggled = toggle_bool(&mut config, key, default)?;
+        self.set_config(plugin_id, config)?;
+        Ok(toggled)
+    }
+
+    pub fn backup_live_config(&self, plugin_id: &str, plugin_dir: &Path) -> Result<bool> {
+        let live_path = plugin_dir.join("config.json");
+        if !live_path.exists() {
+            return Ok(false);
+        }
+
+        let content = std::fs::read_to_string(&live_path)?;
+        let config: serde_json::Value = serde_json::from_str(&content)?;
+
+        let mut configs = self.load_configs()?;
+        configs.record(plugin_id, config);
+        self.save_configs(&configs)?;
+
+        log::info!("Backed up live config for plugin: {}", plugin_id);
+        Ok(true)
+    }
+}
+
+pub fn get_config_value<'a>(config: &'a serde_json::Value, key: &str) -> Option<&'a serde_json::Value> {
+    key.split('.').try_fold(config, |value, part| value.get(part))
+}
+
+pub fn set_config_value(config: &mut serde_json::Value, key: &str, new_value: serde_json::Value) -> Result<()> {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let Some(last) = parts.pop() else {
+        anyhow::bail!("Empty config key");
+    };
+
+    let mut current = config;
+    for part in parts {
+        let object = current
+            .as_object_mut()
+            .ok_or_else(|| anyhow::anyhow!("Config path '{}' is not an object", key))?;
+        current = object
+            .entry(part.to_string())
+            .or_insert_with(|| serde_json::json!({}));
+    }
+
+    let object = current
+        .as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("Config path '{}' is not an object", key))?;
+    object.insert(last.to_string(), new_value);
+    Ok(())
+}
+
+pub fn merge_config(config: &mut serde_json::Value, patch: serde_json::Value) {
+    let serde_json::Value::Object(patch) = patch else {
+        *config = patch;
+        return;
+    };
+
+    if !config.is_object() {
+        *config = serde_json::json!({});
+    }
+    let Some(object) = config.as_object_mut() else {
+        return;
+    };
+
+    for (key, value) in patch {
+        if value.is_null() {
+            object.remove(&key);
+        } else {
+            merge_config(object.entry(key).or_insert(serde_json::Value::Null), value);
+        }
+    }
+}
+
+pub fn toggle_bool(config: &mut serde_json::Value, key: &str, default: bool) -> Result<bool> {
+    let toggled = !get_config_value(config, key).and_then(|v| v.as_bool()).unwrap_or(default);
+    set_config_value(config, key, serde_json::Value::Bool(toggled))?;
+    Ok(toggled)
+}
+
+fn select_stale_backups<F>(configs: &PluginConfigs, now: u64, max_age: Duration, is_installed: F) -> Vec<String>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut stale: Vec<String> = configs
+        .configs
+        .keys()
+        .filter(|id| !is_installed(id))
+        .filter(|id| {
+            configs
+                .updated_at
+                .get(*id)
+                .is_some_and(|updated| now.saturating_sub(*updated) > max_age.as_secs())
+        })
+        .cloned()
+        .collect();
+    stale.sort();
+    stale
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
 }
 
 fn write_plugin_config(plugin_id: &str, config: &serde_json::Value) -> Result<()> {
     let plugin_path = PluginConfigManager::plugin_config_path(plugin_id)?;
     ensure_parent_dir(&plugin_path)?;
     let content = serde_json::to_string_pretty(config)?;
-    std::fs::write(&plugin_path, content)?;
+    paths::atomic_write(&plugin_path, content)?;
     Ok(())
 }
 
@@ -247,6 +418,178 @@ mod tests {
         );
     }
 
+    #[test]
+    fn backup_live_config_replaces_stale_backup() {
+        // Arrange
+        let (manager, _temp_base, temp_plugins) = setup_test_env();
+        let plugin_dir = temp_plugins.path().join("test-plugin");
+        fs::create_dir_all(&plugin_dir).unwrap();
+        let mut configs = PluginConfigs::default();
+        configs.configs.insert("test-plugin".to_string(), json!({"theme": "old"}));
+        configs.configs.insert("other".to_string(), json!({"keep": true}));
+        manager.save_configs(&configs).unwrap();
+        fs::write(plugin_dir.join("config.json"), json!({"theme": "new"}).to_string()).unwrap();
+
+        // Act
+        let backed_up = manager.backup_live_config("test-plugin", &plugin_dir).unwrap();
+
+        // Assert
+        assert!(backed_up);
+        let result = manager.load_configs().unwrap();
+        assert_eq!(result.configs.get("test-plugin").unwrap(), &json!({"theme": "new"}));
+        assert_eq!(result.configs.get("other").unwrap(), &json!({"keep": true}));
+    }
+
+    #[test]
+    fn backup_live_config_keeps_backup_when_no_live_config() {
+        // Arrange
+        let (manager, _temp_base, temp_plugins) = setup_test_env();
+        let mut configs = PluginConfigs::default();
+        configs.configs.insert("test-plugin".to_string(), json!({"theme": "old"}));
+        manager.save_configs(&configs).unwrap();
+
+        // Act
+        let backed_up = manager.backup_live_config("test-plugin", temp_plugins.path()).unwrap();
+
+        // Assert
+        assert!(!backed_up);
+        let result = manager.load_configs().unwrap();
+        assert_eq!(result.configs.get("test-plugin").unwrap(), &json!({"theme": "old"}));
+    }
+
+    #[test]
+    fn delete_config_removes_live_config_and_backup() {
+        // Arrange
+        let (manager, _temp_base, temp_plugins) = setup_test_env();
+        let plugin_dir = temp_plugins.path().join("test-plugin");
+        fs::create_dir_all(&plugin_dir).unwrap();
+        fs::write(plugin_dir.join("config.json"), json!({"theme": "dark"}).to_string()).unwrap();
+        let mut configs = PluginConfigs::default();
+        configs.record("test-plugin", json!({"theme": "dark"}));
+        configs.record("other", json!({"keep": true}));
+        manager.save_configs(&configs).unwrap();
+
+        // Act
+        let deleted = manager.delete_config("test-plugin", &plugin_dir).unwrap();
+
+        // Assert
+        assert!(deleted);
+        assert!(!plugin_dir.join("config.json").exists());
+        let result = manager.load_configs().unwrap();
+        assert!(!result.configs.contains_key("test-plugin"));
+        assert!(!result.updated_at.contains_key("test-plugin"));
+        assert!(result.configs.contains_key("other"));
+    }
+
+    #[test]
+    fn reset_config_removes_live_config_and_backup() {
+        // Arrange
+        let (manager, _temp_base, temp_plugins) = setup_test_env();
+        let plugin_dir = temp_plugins.path().join("test-plugin");
+        fs::create_dir_all(&plugin_dir).unwrap();
+        fs::write(plugin_dir.join("config.json"), json!({"theme": "broken"}).to_string()).unwrap();
+        let mut configs = PluginConfigs::default();
+        configs.record("test-plugin", json!({"theme": "broken"}));
+        configs.record("other", json!({"keep": true}));
+        manager.save_configs(&configs).unwrap();
+
+        // Act
+        let restored_default = manager.reset_config("test-plugin", &plugin_dir).unwrap();
+
+        // Assert
+        assert!(!restored_default);
+        assert!(!plugin_dir.join("config.json").exists());
+        let result = manager.load_configs().unwrap();
+        assert!(!result.configs.contains_key("test-plugin"));
+        assert!(result.configs.contains_key("other"));
+    }
+
+    #[test]
+    fn reset_config_restores_shipped_default() {
+        // Arrange
+        let (manager, _temp_base, temp_plugins) = setup_test_env();
+        let plugin_dir = temp_plugins.path().join("test-plugin");
+        fs::create_dir_all(&plugin_dir).unwrap();
+        fs::write(plugin_dir.join("config.json"), json!({"theme": "broken"}).to_string()).unwrap();
+        fs::write(plugin_dir.join("config.default.json"), json!({"theme": "light"}).to_string()).unwrap();
+        let mut configs = PluginConfigs::default();
+        configs.record("test-plugin", json!({"theme": "broken"}));
+        manager.save_configs(&configs).unwrap();
+
+        // Act
+        let restored_default = manager.reset_config("test-plugin", &plugin_dir).unwrap();
+
+        // Assert
+        assert!(restored_default);
+        let content = fs::read_to_string(plugin_dir.join("config.json")).unwrap();
+        let live: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(live, json!({"theme": "light"}));
+        assert!(plugin_dir.join("config.default.json").exists());
+        let result = manager.load_configs().unwrap();
+        assert!(!result.configs.contains_key("test-plugin"));
+    }
+
+    #[test]
+    fn reset_config_rejects_invalid_default() {
+        // Arrange
+        let (manager, _temp_base, temp_plugins) = setup_test_env();
+        let plugin_dir = temp_plugins.path().join("test-plugin");
+        fs::create_dir_all(&plugin_dir).unwrap();
+        fs::write(plugin_dir.join("config.default.json"), "{not json").unwrap();
+
+        // Act
+        let result = manager.reset_config("test-plugin", &plugin_dir);
+
+        // Assert
+        assert!(result.is_err());
+        assert!(!plugin_dir.join("config.json").exists());
+    }
+
+    #[test]
+    fn delete_config_reports_missing_config() {
+        // Arrange
+        let (manager, _temp_base, temp_plugins) = setup_test_env();
+
+        // Act
+        let deleted = manager.delete_config("test-plugin", temp_plugins.path()).unwrap();
+
+        // Assert
+        assert!(!deleted);
+    }
+
+    #[test]
+    fn select_stale_backups_cases() {
+        let day = 24 * 60 * 60;
+        let now = 100 * day;
+        let mut configs = PluginConfigs::default();
+        for (id, age_days) in [("old-removed", 40), ("fresh-removed", 5), ("old-installed", 40), ("untracked", 0)] {
+            configs.configs.insert(id.to_string(), json!({}));
+            if id != "untracked" {
+                configs.updated_at.insert(id.to_string(), now - age_days * day);
+            }
+        }
+
+        let stale = select_stale_backups(&configs, now, Duration::from_secs(30 * day), |id| id == "old-installed");
+
+        assert_eq!(stale, vec!["old-removed".to_string()]);
+    }
+
+    #[test]
+    fn prune_stale_backups_stamps_untracked_entries() {
+        // Arrange
+        let (manager, _temp_base, temp_plugins) = setup_test_env();
+        fs::write(&manager.config_path, json!({"legacy": {"a": 1}}).to_string()).unwrap();
+
+        // Act
+        let pruned = manager.prune_stale_backups(temp_plugins.path(), Duration::from_secs(0)).unwrap();
+
+        // Assert
+        assert!(pruned.is_empty());
+        let result = manager.load_configs().unwrap();
+        assert!(result.configs.contains_key("legacy"));
+        assert!(result.updated_at.contains_key("legacy"));
+    }
+
     #[test]
     fn plugin_config_path_cases() {
         let valid = ["plugin-test", "my_plugin", "a"];
@@ -259,4 +602,81 @@ mod tests {
             assert!(PluginConfigManager::plugin_config_path(id).is_err(), "should fail: {:?}", id);
         }
     }
+
+    #[test]
+    fn get_config_value_cases() {
+        let config = json!({"feature": {"enabled": true, "nested": {"flag": false}}, "top": 3});
+
+        let cases = [
+            ("feature.enabled", Some(json!(true))),
+            ("feature.nested.flag", Some(json!(false))),
+            ("top", Some(json!(3))),
+            ("feature.missing", None),
+            ("top.child", None),
+        ];
+
+        for (key, expected) in cases {
+            assert_eq!(get_config_value(&config, key).cloned(), expected, "key: {}", key);
+        }
+    }
+
+    #[test]
+    fn set_config_value_creates_intermediate_objects() {
+        let mut config = json!({"other": 1});
+
+        set_config_value(&mut config, "feature.nested.enabled", json!(true)).unwrap();
+
+        assert_eq!(config, json!({"other": 1, "feature": {"nested": {"enabled": true}}}));
+    }
+
+    #[test]
+    fn set_config_value_rejects_non_object_parent() {
+        let mut config = json!({"feature": 5});
+
+        assert!(set_config_value(&mut config, "feature.enabled", json!(true)).is_err());
+        assert_eq!(config, json!({"feature": 5}));
+    }
+
+    #[test]
+    fn merge_config_cases() {
+        let cases = [
+            (json!({"a": 1, "b": 2}), json!({"b": 3}), json!({"a": 1, "b": 3})),
+            (json!({"a": {"x": 1, "y": 2}}), json!({"a": {"y": 3, "z": 4}}), json!({"a": {"x": 1, "y": 3, "z": 4}})),
+            (json!({"a": {"b": {"c": 1, "d": 2}}}), json!({"a": {"b": {"d": 5}}}), json!({"a": {"b": {"c": 1, "d": 5}}})),
+            (json!({"list": [1, 2, 3]}), json!({"list": [4]}), json!({"list": [4]})),
+            (json!({"a": 1, "b": 2}), json!({"b": null}), json!({"a": 1})),
+            (json!({"a": {"x": 1, "y": 2}}), json!({"a": {"x": null}}), json!({"a": {"y": 2}})),
+            (json!({"a": 1}), json!({"missing": null}), json!({"a": 1})),
+            (json!({"a": "scalar"}), json!({"a": {"x": 1}}), json!({"a": {"x": 1}})),
+            (json!({"a": {"x": 1}}), json!({"a": "scalar"}), json!({"a": "scalar"})),
+            (json!({"a": 1}), json!({"b": {"c": null, "d": 1}}), json!({"a": 1, "b": {"d": 1}})),
+            (json!({"a": 1}), json!({}), json!({"a": 1})),
+        ];
+
+        for (base, patch, expected) in cases {
+            let mut config = base.clone();
+            merge_config(&mut config, patch.clone());
+            assert_eq!(config, expected, "base: {} patch: {}", base, patch);
+        }
+    }
+
+    #[test]
+    fn toggle_bool_cases() {
+        let cases = [
+            (json!({"feature": {"enabled": true}}), false, false),
+            (json!({"feature": {"enabled": false}}), true, true),
+            (json!({"feature": {}}), false, true),
+            (json!({"feature": {}}), true, false),
+            (json!({}), false, true),
+            (json!({}), true, false),
+            (json!({"feature": {"enabled": "yes"}}), false, true),
+        ];
+
+        for (mut config, default, expected) in cases {
+            let toggled = toggle_bool(&mut config, "feature.enabled", default).unwrap();
+
+            assert_eq!(toggled, expected, "default: {}", default);
+            assert_eq!(config["feature"]["enabled"], json!(expected));
+        }
+    }
 }
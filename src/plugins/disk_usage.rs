@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use walkdir::WalkDir;
+
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+
+    WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !entry.file_type().is_dir())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+#[derive(Clone, Default)]
+pub struct DiskUsageCache {
+    entries: Arc<Mutex<HashMap<PathBuf, (Instant, u64)>>>,
+}
+
+impl DiskUsageCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn size_of(&self, path: &Path) -> u64 {
+        self.size_at(path, Instant::now())
+    }
+
+    fn size_at(&self, path: &Path, now: Instant) -> u64 {
+        let Ok(mut entries) = self.entries.lock() else {
+            log::error!("Disk usage cache mutex poisoned");
+            return dir_size(path);
+        };
+        if let Some(&(computed_at, size)) = entries.get(path) {
+            if now.saturating_duration_since(computed_at) < CACHE_TTL {
+                return size;
+            }
+        }
+
+        let size = dir_size(path);
+        entries.insert(path.to_path_buf(), (now, size));
+        size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(path: &Path, len: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![0u8; len]).unwrap();
+    }
+
+    #[test]
+    fn dir_size_sums_nested_files() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_file(&tmp.path().join("plugin.toml"), 100);
+        write_file(&tmp.path().join("bin/tool"), 2048);
+        write_file(&tmp.path().join("ui/assets/app.js"), 512);
+        std::fs::create_dir(tmp.path().join("empty")).unwrap();
+
+        assert_eq!(dir_size(tmp.path()), 100 + 2048 + 512);
+    }
+
+    #[test]
+    fn dir_size_cases() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_file(&tmp.path().join("file.bin"), 10);
+
+        let cases = [
+            (tmp.path().join("file.bin"), 10),
+            (tmp.path().join("missing"), 0),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(dir_size(&path), expected, "path: {:?}", path);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dir_size_does_not_follow_symlinks() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let source = tmp.path().join("source");
+        write_file(&source.join("big.bin"), 4096);
+
+        let plugins = tmp.path().join("plugins");
+        std::fs::create_dir(&plugins).unwrap();
+        let linked_plugin = plugins.join("linked");
+        std::os::unix::fs::symlink(&source, &linked_plugin).unwrap();
+
+        let installed = plugins.join("installed");
+        write_file(&installed.join("plugin.toml"), 50);
+        std::os::unix::fs::symlink(&source, installed.join("shared")).unwrap();
+
+        let link_len = std::fs::symlink_metadata(&linked_plugin).unwrap().len();
+        assert_eq!(dir_size(&linked_plugin), link_len);
+        assert_eq!(dir_size(&installed), 50 + link_len);
+    }
+
+    #[test]
+    fn cache_reuses_size_until_ttl_expires() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_file(&tmp.path().join("a.bin"), 10);
+        let cache = DiskUsageCache::new();
+        let start = Instant::now();
+
+        assert_eq!(cache.size_at(tmp.path(), start), 10);
+        write_file(&tmp.path().join("b.bin"), 20);
+
+        assert_eq!(cache.size_at(tmp.path(), start + CACHE_TTL / 2), 10);
+        assert_eq!(cache.size_at(tmp.path(), start + CACHE_TTL), 30);
+    }
+}
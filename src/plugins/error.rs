@@ -0,0 +1,60 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum PluginError {
+    NotFound(String),
+    AlreadyInstalled(String),
+    InvalidPath(String),
+    ManifestInvalid(String),
+    DaemonStartFailed(String),
+    NoDaemon(String),
+    Io { context: String, source: std::io::Error },
+}
+
+impl PluginError {
+    pub fn io(context: impl Into<String>) -> impl FnOnce(std::io::Error) -> Self {
+        let context = context.into();
+        move |source| Self::Io { context, source }
+    }
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(message)
+            | Self::AlreadyInstalled(message)
+            | Self::InvalidPath(message)
+            | Self::ManifestInvalid(message)
+            | Self::DaemonStartFailed(message)
+            | Self::NoDaemon(message) => f.write_str(message),
+            Self::Io { context, source } => write!(f, "{}: {}", context, source),
+        }
+    }
+}
+
+impl std::error::Error for PluginError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn io_errors_keep_context_and_source() {
+        let error = PluginError::io("Failed to create symlink")(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "denied",
+        ));
+
+        assert_eq!(error.to_string(), "Failed to create symlink: denied");
+        assert!(error.source().is_some());
+        assert!(PluginError::NotFound("gone".into()).source().is_none());
+    }
+}
@@ -1,11 +1,22 @@
-use super::{Plugin, PluginManifest};
+use super::{Plugin, PluginError, PluginManifest};
+use super::manifest::KNOWN_PLATFORMS;
 use crate::paths;
 use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 
 pub struct PluginLoader;
 
+#[derive(Debug, Serialize)]
+pub struct PluginProblem {
+    pub id: String,
+    pub path: PathBuf,
+    pub error: String,
+}
+
 impl PluginLoader {
     pub fn default_plugin_dir() -> Result<PathBuf> {
         paths::plugins_dir()
@@ -31,13 +42,7 @@ impl PluginLoader {
             return Ok(Vec::new());
         }
 
-        let entries = fs::read_dir(dir).context("Failed to read plugins directory")?;
-        let paths: Vec<PathBuf> = entries
-            .filter_map(|e| e.ok())
-            .map(|e| e.path())
-            .filter(|p| p.is_dir())
-            .filter(|p| !p.extension().is_some_and(|ext| ext == "backup"))
-            .collect();
+        let paths = Self::plugin_dirs(dir)?;
 
         let plugins: Vec<Plugin> = paths
             .iter()
@@ -48,6 +53,37 @@ impl PluginLoader {
         Ok(plugins)
     }
 
+    pub fn find_problems(dir: &Path) -> Result<Vec<PluginProblem>> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut problems: Vec<PluginProblem> = Self::plugin_dirs(dir)?
+            .into_iter()
+            .filter_map(|path| {
+                let error = Self::load_plugin(&path).err()?;
+                let id = path.file_name()?.to_string_lossy().into_owned();
+                Some(PluginProblem {
+                    id,
+                    path,
+                    error: error.to_string(),
+                })
+            })
+            .collect();
+        problems.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(problems)
+    }
+
+    fn plugin_dirs(dir: &Path) -> Result<Vec<PathBuf>> {
+        let entries = fs::read_dir(dir).context("Failed to read plugins directory")?;
+        Ok(entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir() || dangling_link_target(p).is_some())
+            .filter(|p| p.extension().is_none_or(|ext| ext != "backup"))
+            .collect())
+    }
+
     fn try_load_plugin(path: &Path) -> Option<Plugin> {
         match Self::load_plugin(path) {
             Ok(plugin) => {
@@ -69,26 +105,87 @@ impl PluginLoader {
         }
     }
 
-    pub fn load_plugin(path: &Path) -> Result<Plugin> {
+    pub fn load_plugin(path: &Path) -> Result<Plugin, PluginError> {
+        if let Some(target) = dangling_link_target(path) {
+            return Err(PluginError::NotFound(format!(
+                "Broken plugin link: target {:?} does not exist",
+                target
+            )));
+        }
+
         let manifest_path = path.join("plugin.toml");
 
         if !manifest_path.exists() {
-            anyhow::bail!("No plugin.toml found in {:?}", path);
+            return Err(PluginError::ManifestInvalid(format!("No plugin.toml found in {:?}", path)));
         }
 
         let manifest_content = fs::read_to_string(&manifest_path)
-            .context("Failed to read plugin.toml")?;
+            .map_err(PluginError::io("Failed to read plugin.toml"))?;
 
         let manifest: PluginManifest = toml::from_str(&manifest_content)
-            .context("Failed to parse plugin.toml")?;
+            .map_err(|e| PluginError::ManifestInvalid(format!("Failed to parse plugin.toml: {}", e)))?;
 
         let id = path.file_name()
             .and_then(|n| n.to_str())
-            .context("Invalid plugin directory name")?
+            .ok_or_else(|| PluginError::InvalidPath(format!("Invalid plugin directory name: {:?}", path)))?
             .to_string();
 
-        Ok(Plugin::new(id, manifest, path.to_path_buf()))
+        for platform in manifest.plugin.unknown_platforms() {
+            log::warn!(
+                "Plugin {} lists unknown platform '{}' (expected one of: {})",
+                id,
+                platform,
+                KNOWN_PLATFORMS.join(", ")
+            );
+        }
+
+        let fingerprint = plugin_fingerprint(path, &manifest, &manifest_content);
+        Ok(Plugin::new(id, manifest, path.to_path_buf(), fingerprint))
+    }
+}
+
+fn plugin_fingerprint(path: &Path, manifest: &PluginManifest, manifest_content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf()).hash(&mut hasher);
+    manifest_content.hash(&mut hasher);
+    git_head(path).hash(&mut hasher);
+    if let Some(daemon) = &manifest.daemon {
+        file_stamp(&path.join(&daemon.command)).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn git_head(path: &Path) -> Option<String> {
+    let git_dir = path.join(".git");
+    let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+    let head = head.trim();
+    let Some(reference) = head.strip_prefix("ref: ") else {
+        return Some(head.to_string());
+    };
+    fs::read_to_string(git_dir.join(reference))
+        .ok()
+        .map(|sha| sha.trim().to_string())
+        .or_else(|| packed_ref(&git_dir, reference))
+}
+
+fn packed_ref(git_dir: &Path, reference: &str) -> Option<String> {
+    fs::read_to_string(git_dir.join("packed-refs"))
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_suffix(reference)?.strip_suffix(' ').map(str::to_string))
+}
+
+fn file_stamp(path: &Path) -> Option<(u64, Option<std::time::SystemTime>)> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.len(), metadata.modified().ok()))
+}
+
+fn dangling_link_target(path: &Path) -> Option<PathBuf> {
+    let is_symlink = fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink());
+    if !is_symlink || path.exists() {
+        return None;
     }
+    fs::read_link(path).ok()
 }
 
 #[cfg(test)]
@@ -159,6 +256,54 @@ items = []
         assert!(PluginLoader::load_plugin(temp_dir.path()).is_err());
     }
 
+    #[test]
+    fn find_problems_reports_dirs_that_fail_to_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let valid = temp_dir.path().join("valid-plugin");
+        let broken = temp_dir.path().join("broken-plugin");
+        let empty = temp_dir.path().join("empty-plugin");
+        for dir in [&valid, &broken, &empty] {
+            fs::create_dir(dir).unwrap();
+        }
+        fs::write(valid.join("plugin.toml"), VALID_MANIFEST).unwrap();
+        fs::write(broken.join("plugin.toml"), "[plugin]\nname = ").unwrap();
+
+        let problems = PluginLoader::find_problems(temp_dir.path()).unwrap();
+
+        let ids: Vec<&str> = problems.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["broken-plugin", "empty-plugin"]);
+        assert!(problems[0].error.contains("Failed to parse plugin.toml"), "error: {}", problems[0].error);
+        assert!(problems[1].error.contains("No plugin.toml"), "error: {}", problems[1].error);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn dangling_symlink_is_reported_not_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        let plugins_dir = temp_dir.path().join("plugins");
+        fs::create_dir(&plugins_dir).unwrap();
+        let missing_target = temp_dir.path().join("deleted-checkout");
+        std::os::unix::fs::symlink(&missing_target, plugins_dir.join("linked-plugin")).unwrap();
+
+        let plugins = PluginLoader::load_from_dir(&plugins_dir).unwrap();
+        let problems = PluginLoader::find_problems(&plugins_dir).unwrap();
+
+        assert!(plugins.is_empty());
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].id, "linked-plugin");
+        assert!(problems[0].error.contains("Broken plugin link"), "error: {}", problems[0].error);
+        assert!(problems[0].error.contains("deleted-checkout"), "error: {}", problems[0].error);
+    }
+
+    #[test]
+    fn find_problems_empty_for_missing_dir() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let problems = PluginLoader::find_problems(&temp_dir.path().join("missing")).unwrap();
+
+        assert!(problems.is_empty());
+    }
+
     #[test]
     fn load_plugin_extracts_id_from_directory_name() {
         let temp_dir = TempDir::new().unwrap();
@@ -171,6 +316,77 @@ items = []
         assert_eq!(plugin.id, "my-custom-plugin");
     }
 
+    #[test]
+    fn fingerprint_changes_only_when_manifest_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let plugin_dir = temp_dir.path().join("plugin-a");
+        fs::create_dir(&plugin_dir).unwrap();
+        fs::write(plugin_dir.join("plugin.toml"), VALID_MANIFEST).unwrap();
+
+        let first = PluginLoader::load_plugin(&plugin_dir).unwrap().fingerprint;
+        fs::write(plugin_dir.join("run.sh"), "#!/bin/sh").unwrap();
+        assert_eq!(PluginLoader::load_plugin(&plugin_dir).unwrap().fingerprint, first);
+
+        fs::write(plugin_dir.join("plugin.toml"), VALID_MANIFEST.replace("1.0.0", "1.1.0")).unwrap();
+        assert_ne!(PluginLoader::load_plugin(&plugin_dir).unwrap().fingerprint, first);
+    }
+
+    #[test]
+    fn fingerprint_changes_when_daemon_binary_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let plugin_dir = temp_dir.path().join("plugin-a");
+        fs::create_dir(&plugin_dir).unwrap();
+        let manifest = format!("{}\n[daemon]\nenabled = true\ncommand = \"run.sh\"\n", VALID_MANIFEST);
+        fs::write(plugin_dir.join("plugin.toml"), manifest).unwrap();
+        fs::write(plugin_dir.join("run.sh"), "#!/bin/sh").unwrap();
+
+        let first = PluginLoader::load_plugin(&plugin_dir).unwrap().fingerprint;
+        assert_eq!(PluginLoader::load_plugin(&plugin_dir).unwrap().fingerprint, first);
+
+        fs::write(plugin_dir.join("run.sh"), "#!/bin/sh\nexec sleep 60").unwrap();
+        assert_ne!(PluginLoader::load_plugin(&plugin_dir).unwrap().fingerprint, first);
+    }
+
+    #[test]
+    fn fingerprint_changes_when_git_head_moves() {
+        let temp_dir = TempDir::new().unwrap();
+        let plugin_dir = temp_dir.path().join("plugin-a");
+        fs::create_dir_all(plugin_dir.join(".git/refs/heads")).unwrap();
+        fs::write(plugin_dir.join("plugin.toml"), VALID_MANIFEST).unwrap();
+        fs::write(plugin_dir.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(plugin_dir.join(".git/packed-refs"), "# pack-refs\naaaa refs/heads/main\n").unwrap();
+
+        let packed = PluginLoader::load_plugin(&plugin_dir).unwrap().fingerprint;
+
+        fs::write(plugin_dir.join(".git/refs/heads/main"), "bbbb\n").unwrap();
+        let loose = PluginLoader::load_plugin(&plugin_dir).unwrap().fingerprint;
+        assert_ne!(loose, packed);
+
+        fs::write(plugin_dir.join(".git/HEAD"), "cccc\n").unwrap();
+        assert_ne!(PluginLoader::load_plugin(&plugin_dir).unwrap().fingerprint, loose);
+    }
+
+    #[test]
+    fn git_head_cases() {
+        let temp_dir = TempDir::new().unwrap();
+        let git_dir = temp_dir.path().join(".git");
+        fs::create_dir_all(git_dir.join("refs/heads")).unwrap();
+        fs::write(git_dir.join("packed-refs"), "aaaa refs/heads/packed\nbbbb refs/heads/main-old\n").unwrap();
+        fs::write(git_dir.join("refs/heads/main"), "cccc\n").unwrap();
+
+        let cases = [
+            ("ref: refs/heads/main\n", Some("cccc")),
+            ("ref: refs/heads/packed\n", Some("aaaa")),
+            ("ref: refs/heads/old\n", None),
+            ("dddd\n", Some("dddd")),
+        ];
+
+        for (head, expected) in cases {
+            fs::write(git_dir.join("HEAD"), head).unwrap();
+            assert_eq!(git_head(temp_dir.path()).as_deref(), expected, "head: {:?}", head);
+        }
+    }
+
     #[test]
     fn load_plugin_parses_manifest_fields() {
         let temp_dir = TempDir::new().unwrap();
@@ -1,4 +1,4 @@
-use super::{Plugin, PluginLoader};
+use super::{Plugin, PluginError, PluginLoader};
 use crate::paths;
 use anyhow::Result;
 use std::collections::HashMap;
@@ -7,6 +7,39 @@ pub struct PluginManager {
     plugins: HashMap<String, Plugin>,
 }
 
+#[derive(Debug, Default, PartialEq)]
+pub struct ReloadPlan {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+    pub kept: Vec<String>,
+}
+
+impl ReloadPlan {
+    pub fn between(current: &HashMap<String, u64>, next: &HashMap<String, u64>) -> Self {
+        let mut plan = Self::default();
+
+        for (id, fingerprint) in next {
+            match current.get(id) {
+                None => plan.added.push(id.clone()),
+                Some(existing) if existing != fingerprint => plan.changed.push(id.clone()),
+                Some(_) => plan.kept.push(id.clone()),
+            }
+        }
+        plan.removed = current
+            .keys()
+            .filter(|id| !next.contains_key(*id))
+            .cloned()
+            .collect();
+
+        plan.added.sort();
+        plan.removed.sort();
+        plan.changed.sort();
+        plan.kept.sort();
+        plan
+    }
+}
+
 impl PluginManager {
     pub fn new() -> Self {
         Self {
@@ -35,7 +68,42 @@ impl PluginManager {
     }
 
     pub fn reload_plugins(&mut self) -> Result<()> {
-        log::info!("Reloading all plugins...");
+        let mut next: HashMap<String, Plugin> = PluginLoader::load_all()?
+            .into_iter()
+            .map(|plugin| (plugin.id.clone(), plugin))
+            .collect();
+        let plan = ReloadPlan::between(&fingerprints(&self.plugins), &fingerprints(&next));
+        log::info!(
+            "Reloading plugins: {} added, {} removed, {} changed, {} unchanged",
+            plan.added.len(),
+            plan.removed.len(),
+            plan.changed.len(),
+            plan.kept.len()
+        );
+
+        for id in plan.removed.iter().chain(&plan.changed) {
+            if let Some(mut plugin) = self.plugins.remove(id) {
+                if let Err(e) = plugin.stop_daemon() {
+                    log::error!("Failed to stop daemon for plugin {}: {}", plugin.id, e);
+                }
+            }
+        }
+
+        for id in plan.added.iter().chain(&plan.changed) {
+            let Some(mut plugin) = next.remove(id) else { continue };
+            if let Err(e) = plugin.start_daemon() {
+                log::error!("Failed to start daemon for plugin {}: {}", plugin.id, e);
+            }
+            self.plugins.insert(plugin.id.clone(), plugin);
+        }
+
+        let pids: Vec<u32> = self.plugins.values().filter_map(Plugin::daemon_pid).collect();
+        save_daemon_pids(&pids);
+        Ok(())
+    }
+
+    pub fn restart_plugins(&mut self) -> Result<()> {
+        log::info!("Restarting all plugins...");
         for plugin in self.plugins.values_mut() {
             if let Err(e) = plugin.stop_daemon() {
                 log::error!("Failed to stop daemon for plugin {}: {}", plugin.id, e);
@@ -45,6 +113,30 @@ impl PluginManager {
         self.load_plugins()
     }
 
+    pub fn restart_daemon(&mut self, id: &str) -> Result<u32, PluginError> {
+        let pid = self.restart_single_daemon(id)?;
+        let pids: Vec<u32> = self.plugins.values().filter_map(Plugin::daemon_pid).collect();
+        save_daemon_pids(&pids);
+        Ok(pid)
+    }
+
+    fn restart_single_daemon(&mut self, id: &str) -> Result<u32, PluginError> {
+        let plugin = self
+            .plugins
+            .get_mut(id)
+            .ok_or_else(|| PluginError::NotFound(format!("Plugin not loaded: {}", id)))?;
+        if !plugin.manifest.daemon.as_ref().is_some_and(|daemon| daemon.enabled) {
+            return Err(PluginError::NoDaemon(format!("Plugin {} has no daemon", id)));
+        }
+
+        log::info!("Restarting daemon for plugin: {}", id);
+        plugin.stop_daemon()?;
+        plugin.start_daemon()?;
+        plugin
+            .daemon_pid()
+            .ok_or_else(|| PluginError::DaemonStartFailed(format!("Daemon for {} did not start", id)))
+    }
+
     pub fn plugins(&self) -> impl Iterator<Item = &Plugin> {
         self.plugins.values()
     }
@@ -56,6 +148,13 @@ impl Default for PluginManager {
     }
 }
 
+fn fingerprints(plugins: &HashMap<String, Plugin>) -> HashMap<String, u64> {
+    plugins
+        .iter()
+        .map(|(id, plugin)| (id.clone(), plugin.fingerprint))
+        .collect()
+}
+
 fn daemon_pids_path() -> Option<std::path::PathBuf> {
     paths::config_dir().ok().map(|p| p.join(".daemon-pids"))
 }
@@ -90,3 +189,108 @@ fn save_daemon_pids(pids: &[u32]) {
     let content = pids.iter().map(|p| p.to_string()).collect::<Vec<_>>().join("\n");
     let _ = std::fs::write(&path, content);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(entries: &[(&str, u64)]) -> HashMap<String, u64> {
+        entries.iter().map(|(id, fp)| (id.to_string(), *fp)).collect()
+    }
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[cfg(unix)]
+    fn plugin(dir: &std::path::Path, id: &str, daemon: Option<&str>) -> Plugin {
+        use std::os::unix::fs::PermissionsExt;
+
+        let plugin_dir = dir.join(id);
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        let daemon_section = match daemon {
+            Some(script) => {
+                let path = plugin_dir.join("daemon.sh");
+                std::fs::write(&path, script).unwrap();
+                std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+                "[daemon]\nenabled = true\ncommand = \"daemon.sh\"\n"
+            }
+            None => "",
+        };
+        let manifest = toml::from_str(&format!(
+            "[plugin]\nname = \"{id}\"\ndescription = \"\"\nversion = \"1.0.0\"\n\n[menu]\nlabel = \"{id}\"\nitems = []\n\n{daemon_section}"
+        ))
+        .unwrap();
+        Plugin::new(id.to_string(), manifest, plugin_dir, 0)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn restart_daemon_only_touches_the_selected_plugin() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let script = "#!/bin/sh\nexec sleep 30\n";
+        let mut manager = PluginManager::new();
+        for plugin in [
+            plugin(tmp.path(), "target", Some(script)),
+            plugin(tmp.path(), "other", Some(script)),
+            plugin(tmp.path(), "plain", None),
+        ] {
+            manager.plugins.insert(plugin.id.clone(), plugin);
+        }
+        for plugin in manager.plugins.values_mut() {
+            plugin.start_daemon().unwrap();
+        }
+        let pid_of = |manager: &PluginManager, id: &str| manager.plugins[id].daemon_pid();
+        let target_before = pid_of(&manager, "target").unwrap();
+        let other_before = pid_of(&manager, "other").unwrap();
+
+        let restarted = manager.restart_single_daemon("target").unwrap();
+
+        assert_ne!(restarted, target_before);
+        assert_eq!(pid_of(&manager, "target"), Some(restarted));
+        assert_eq!(pid_of(&manager, "other"), Some(other_before));
+        assert!(matches!(manager.restart_single_daemon("plain"), Err(PluginError::NoDaemon(_))));
+        assert!(matches!(manager.restart_single_daemon("missing"), Err(PluginError::NotFound(_))));
+        assert_eq!(pid_of(&manager, "other"), Some(other_before));
+
+        for plugin in manager.plugins.values_mut() {
+            plugin.stop_daemon().unwrap();
+        }
+    }
+
+    #[test]
+    fn reload_plan_cases() {
+        let cases = vec![
+            (set(&[]), set(&[]), ReloadPlan::default()),
+            (
+                set(&[]),
+                set(&[("a", 1), ("b", 2)]),
+                ReloadPlan { added: ids(&["a", "b"]), ..Default::default() },
+            ),
+            (
+                set(&[("a", 1), ("b", 2)]),
+                set(&[]),
+                ReloadPlan { removed: ids(&["a", "b"]), ..Default::default() },
+            ),
+            (
+                set(&[("a", 1), ("b", 2)]),
+                set(&[("a", 1), ("b", 2)]),
+                ReloadPlan { kept: ids(&["a", "b"]), ..Default::default() },
+            ),
+            (
+                set(&[("kept", 1), ("changed", 2), ("removed", 3)]),
+                set(&[("kept", 1), ("changed", 20), ("added", 4)]),
+                ReloadPlan {
+                    added: ids(&["added"]),
+                    removed: ids(&["removed"]),
+                    changed: ids(&["changed"]),
+                    kept: ids(&["kept"]),
+                },
+            ),
+        ];
+
+        for (current, next, expected) in cases {
+            assert_eq!(ReloadPlan::between(&current, &next), expected, "current={:?} next={:?}", current, next);
+        }
+    }
+}
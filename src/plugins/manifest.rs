@@ -1,5 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+use super::schema::ConfigSchema;
+
+pub const KNOWN_PLATFORMS: &[&str] = &[
+    "linux", "macos", "windows", "ios", "android", "freebsd", "dragonfly", "netbsd", "openbsd",
+    "solaris", "illumos",
+];
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PluginManifest {
     pub plugin: PluginInfo,
@@ -8,6 +15,33 @@ pub struct PluginManifest {
     pub daemon: Option<DaemonConfig>,
     #[serde(default)]
     pub dependencies: Option<Dependencies>,
+    #[serde(default)]
+    pub ui: Option<UiConfig>,
+    #[serde(default)]
+    pub config_schema: Option<ConfigSchema>,
+    #[serde(default)]
+    pub task_actions: Vec<TaskAction>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TaskAction {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub command: String,
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct UiConfig {
+    #[serde(default)]
+    pub csp: Option<String>,
+    #[serde(default)]
+    pub hide_menu_item: bool,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -21,6 +55,8 @@ pub struct BinaryDependency {
     pub name: String,
     pub repo: String,
     pub pattern: String,
+    #[serde(default)]
+    pub sha256: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -31,13 +67,26 @@ pub struct PluginInfo {
     #[serde(default)]
     pub author: Option<String>,
     #[serde(default)]
+    pub homepage: Option<String>,
+    #[serde(default)]
     pub platforms: Option<Vec<String>>,
+    #[serde(default)]
+    pub run: Option<String>,
 }
 
 impl PluginInfo {
     pub fn supports_current_platform(&self) -> bool {
         supports_current_platform(&self.platforms)
     }
+
+    pub fn unknown_platforms(&self) -> Vec<&str> {
+        self.platforms
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|p| !KNOWN_PLATFORMS.contains(p))
+            .collect()
+    }
 }
 
 pub fn supports_current_platform(platforms: &Option<Vec<String>>) -> bool {
@@ -64,6 +113,12 @@ pub enum MenuItem {
         action: ActionType,
         #[serde(default)]
         config_key: Option<String>,
+        #[serde(default)]
+        enabled_when: Option<String>,
+        #[serde(default)]
+        icon: Option<String>,
+        #[serde(default)]
+        command: Option<String>,
     },
     Checkbox {
         id: String,
@@ -89,12 +144,37 @@ pub enum ActionType {
     Settings,
     #[serde(rename = "toggle-config")]
     ToggleConfig,
+    Custom,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DaemonConfig {
     pub enabled: bool,
     pub command: String,
+    #[serde(default)]
+    pub ready_check: Option<ReadyCheck>,
+    #[serde(default)]
+    pub ready_timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub stop_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub stop_signal: StopSignal,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StopSignal {
+    #[default]
+    Term,
+    Int,
+    Kill,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadyCheck {
+    Port(u16),
+    Socket(String),
 }
 
 #[cfg(test)]
@@ -107,7 +187,9 @@ mod tests {
             description: "Test".to_string(),
             version: "1.0.0".to_string(),
             author: None,
+            homepage: None,
             platforms: platforms.map(|p| p.into_iter().map(String::from).collect()),
+            run: None,
         }
     }
 
@@ -132,6 +214,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn unknown_platforms_cases() {
+        let cases: &[(Option<Vec<&str>>, Vec<&str>)] = &[
+            (None, vec![]),
+            (Some(vec!["windows"]), vec![]),
+            (Some(vec!["win"]), vec!["win"]),
+            (Some(vec!["linux", "macos", "freebsd"]), vec![]),
+            (Some(vec!["linux", "osx", "Windows"]), vec!["osx", "Windows"]),
+        ];
+
+        for (platforms, expected) in cases {
+            let info = make_plugin_info(platforms.clone());
+            assert_eq!(info.unknown_platforms(), *expected, "platforms: {:?}", platforms);
+        }
+    }
+
     #[test]
     fn parse_action_menu_item() {
         let toml = r#"
@@ -142,16 +240,79 @@ mod tests {
         "#;
         let item: MenuItem = toml::from_str(toml).unwrap();
         match item {
-            MenuItem::Action { id, label, action, config_key } => {
+            MenuItem::Action { id, label, action, config_key, enabled_when, icon, command } => {
                 assert_eq!(id, "run");
                 assert_eq!(label, "Run Script");
                 assert_eq!(action, ActionType::Run);
                 assert!(config_key.is_none());
+                assert!(enabled_when.is_none());
+                assert!(icon.is_none());
+                assert!(command.is_none());
+            }
+            _ => panic!("Expected Action"),
+        }
+    }
+
+    #[test]
+    fn parse_action_menu_item_with_enabled_when() {
+        let toml = r#"
+            type = "action"
+            id = "stop"
+            label = "Stop Recording"
+            action = "run"
+            enabled_when = "recording.active"
+        "#;
+        let item: MenuItem = toml::from_str(toml).unwrap();
+        match item {
+            MenuItem::Action { enabled_when, .. } => {
+                assert_eq!(enabled_when, Some("recording.active".to_string()));
+            }
+            _ => panic!("Expected Action"),
+        }
+    }
+
+    #[test]
+    fn parse_custom_action_menu_item() {
+        let toml = r#"
+            type = "action"
+            id = "open-logs"
+            label = "Open Logs"
+            action = "custom"
+            command = "xdg-open {{plugin_dir}}/logs"
+        "#;
+        let item: MenuItem = toml::from_str(toml).unwrap();
+        match item {
+            MenuItem::Action { action, command, .. } => {
+                assert_eq!(action, ActionType::Custom);
+                assert_eq!(command.as_deref(), Some("xdg-open {{plugin_dir}}/logs"));
             }
             _ => panic!("Expected Action"),
         }
     }
 
+    #[test]
+    fn parse_binary_dependency_checksum() {
+        let toml = r#"
+            [[binaries]]
+            name = "tool"
+            repo = "qol-tools/tool"
+            pattern = "tool-{os}-{arch}"
+            sha256 = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+
+            [[binaries]]
+            name = "other"
+            repo = "qol-tools/other"
+            pattern = "other-{os}"
+        "#;
+        let deps: Dependencies = toml::from_str(toml).unwrap();
+
+        assert_eq!(
+            deps.binaries[0].sha256.as_deref(),
+            Some("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+        assert!(deps.binaries[1].sha256.is_none());
+    }
+
     #[test]
     fn parse_checkbox_menu_item() {
         let toml = r#"
@@ -210,6 +371,7 @@ mod tests {
             ("run", ActionType::Run),
             ("settings", ActionType::Settings),
             ("toggle-config", ActionType::ToggleConfig),
+            ("custom", ActionType::Custom),
         ];
 
         for (input, expected) in cases {
@@ -255,6 +417,55 @@ mod tests {
         let daemon = manifest.daemon.unwrap();
         assert!(daemon.enabled);
         assert_eq!(daemon.command, "daemon.sh");
+        assert!(daemon.ready_check.is_none());
+        assert_eq!(daemon.stop_timeout_secs, None);
+        assert_eq!(daemon.stop_signal, StopSignal::Term);
+    }
+
+    #[test]
+    fn parse_daemon_stop_options_cases() {
+        let cases = [
+            ("", None, Some(StopSignal::Term)),
+            ("stop_timeout_secs = 10", Some(10), Some(StopSignal::Term)),
+            (r#"stop_signal = "int""#, None, Some(StopSignal::Int)),
+            ("stop_signal = \"kill\"\nstop_timeout_secs = 0", Some(0), Some(StopSignal::Kill)),
+            (r#"stop_signal = "hup""#, None, None),
+        ];
+
+        for (extra, expected_timeout, expected_signal) in cases {
+            let toml = format!(
+                "[plugin]\nname = \"D\"\ndescription = \"\"\nversion = \"1.0.0\"\n\n[menu]\nlabel = \"D\"\nitems = []\n\n[daemon]\nenabled = true\ncommand = \"d.sh\"\n{}\n",
+                extra
+            );
+            let parsed = toml::from_str::<PluginManifest>(&toml);
+            let Some(expected_signal) = expected_signal else {
+                assert!(parsed.is_err(), "extra: {}", extra);
+                continue;
+            };
+            let daemon = parsed.unwrap().daemon.unwrap();
+            assert_eq!(daemon.stop_timeout_secs, expected_timeout, "extra: {}", extra);
+            assert_eq!(daemon.stop_signal, expected_signal, "extra: {}", extra);
+        }
+    }
+
+    #[test]
+    fn parse_daemon_ready_check_cases() {
+        let cases = [
+            ("ready_check = { port = 8123 }", Some(ReadyCheck::Port(8123)), None),
+            (r#"ready_check = { socket = "daemon.sock" }"#, Some(ReadyCheck::Socket("daemon.sock".to_string())), None),
+            ("ready_check = { port = 8123 }\nready_timeout_ms = 2000", Some(ReadyCheck::Port(8123)), Some(2000)),
+            ("", None, None),
+        ];
+
+        for (extra, expected_check, expected_timeout) in cases {
+            let toml = format!(
+                "[plugin]\nname = \"D\"\ndescription = \"\"\nversion = \"1.0.0\"\n\n[menu]\nlabel = \"D\"\nitems = []\n\n[daemon]\nenabled = true\ncommand = \"d.sh\"\n{}\n",
+                extra
+            );
+            let daemon = toml::from_str::<PluginManifest>(&toml).unwrap().daemon.unwrap();
+            assert_eq!(daemon.ready_check, expected_check, "extra: {}", extra);
+            assert_eq!(daemon.ready_timeout_ms, expected_timeout, "extra: {}", extra);
+        }
     }
 
     #[test]
@@ -275,9 +486,90 @@ mod tests {
         assert!(manifest.plugin.author.is_none());
         assert!(manifest.plugin.platforms.is_none());
         assert!(manifest.daemon.is_none());
+        assert!(manifest.ui.is_none());
+        assert!(manifest.config_schema.is_none());
+        assert!(manifest.task_actions.is_empty());
         assert!(manifest.menu.items.is_empty());
     }
 
+    #[test]
+    fn parse_ui_csp() {
+        let toml = r#"
+            [plugin]
+            name = "UI"
+            description = ""
+            version = "0.0.1"
+
+            [menu]
+            label = "UI"
+            items = []
+
+            [ui]
+            csp = "default-src 'self' https://cdn.example.com"
+        "#;
+
+        let manifest: PluginManifest = toml::from_str(toml).unwrap();
+        let csp = manifest.ui.and_then(|ui| ui.csp);
+        assert_eq!(csp.as_deref(), Some("default-src 'self' https://cdn.example.com"));
+    }
+
+    #[test]
+    fn parse_config_schema() {
+        let toml = r#"
+            [plugin]
+            name = "Schema"
+            description = ""
+            version = "0.0.1"
+
+            [menu]
+            label = "S"
+            items = []
+
+            [config_schema]
+            enabled = { type = "boolean", required = true }
+            label = { type = "string" }
+        "#;
+
+        let manifest: PluginManifest = toml::from_str(toml).unwrap();
+        let schema = manifest.config_schema.unwrap();
+        assert_eq!(schema.fields.len(), 2);
+        assert!(schema.fields["enabled"].required);
+        assert!(!schema.fields["label"].required);
+        assert_eq!(schema.fields["label"].field_type, crate::plugins::schema::FieldType::String);
+    }
+
+    #[test]
+    fn parse_task_actions() {
+        let toml = r#"
+            [plugin]
+            name = "Tasks"
+            description = ""
+            version = "0.0.1"
+
+            [menu]
+            label = "T"
+            items = []
+
+            [[task_actions]]
+            id = "build"
+            name = "Build"
+            command = "make"
+            timeout = 300
+            cwd = "src"
+
+            [[task_actions]]
+            id = "clean"
+            name = "Clean"
+            command = "make clean"
+        "#;
+
+        let manifest: PluginManifest = toml::from_str(toml).unwrap();
+        assert_eq!(manifest.task_actions.len(), 2);
+        assert_eq!(manifest.task_actions[0].timeout, Some(300));
+        assert_eq!(manifest.task_actions[0].cwd.as_deref(), Some("src"));
+        assert!(manifest.task_actions[1].timeout.is_none());
+    }
+
     #[test]
     fn checkbox_defaults_to_unchecked() {
         let toml = r#"
@@ -2,35 +2,50 @@ pub mod manifest;
 pub mod loader;
 pub mod manager;
 pub mod config;
+pub mod schema;
+pub mod disk_usage;
+pub mod process_stats;
+pub mod error;
+pub mod validate;
 
-pub use manifest::{PluginManifest, MenuItem, ActionType};
+pub use manifest::{PluginManifest, MenuItem, ActionType, ReadyCheck, StopSignal};
 pub use loader::PluginLoader;
 pub use manager::PluginManager;
-pub use config::PluginConfigManager;
+pub use config::{get_config_value, merge_config, PluginConfigManager};
+pub use disk_usage::DiskUsageCache;
+pub use error::PluginError;
 
-use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+const DEFAULT_READY_TIMEOUT: Duration = Duration::from_secs(5);
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(25);
+const CRASH_CHECK_DELAY: Duration = Duration::from_millis(100);
+const DEFAULT_STOP_TIMEOUT: Duration = Duration::from_secs(2);
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(50);
 
 #[derive(Debug)]
 pub struct Plugin {
     pub id: String,
     pub manifest: PluginManifest,
     pub path: PathBuf,
+    pub fingerprint: u64,
     daemon_process: Option<Child>,
 }
 
 impl Plugin {
-    pub fn new(id: String, manifest: PluginManifest, path: PathBuf) -> Self {
+    pub fn new(id: String, manifest: PluginManifest, path: PathBuf, fingerprint: u64) -> Self {
         Self {
             id,
             manifest,
             path,
+            fingerprint,
             daemon_process: None,
         }
     }
 
-    pub fn start_daemon(&mut self) -> Result<()> {
+    pub fn start_daemon(&mut self) -> Result<(), PluginError> {
         let Some(daemon_config) = &self.manifest.daemon else {
             return Ok(());
         };
@@ -41,31 +56,43 @@ impl Plugin {
 
         let daemon_path = self.path.join(&daemon_config.command);
         if !daemon_path.exists() {
-            anyhow::bail!("Daemon executable not found: {:?}", daemon_path);
+            return Err(PluginError::DaemonStartFailed(format!(
+                "Daemon executable not found: {:?}",
+                daemon_path
+            )));
         }
 
+        ensure_executable(&daemon_path)?;
+
         log::info!("Starting daemon for plugin: {}", self.id);
         let mut child = Command::new(&daemon_path)
             .current_dir(&self.path)
             .stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::piped())
-            .spawn()?;
-
-        std::thread::sleep(std::time::Duration::from_millis(100));
-
-        match child.try_wait()? {
-            Some(status) if !status.success() => {
-                let stderr = child.stderr.take()
-                    .map(|mut s| {
-                        let mut buf = String::new();
-                        std::io::Read::read_to_string(&mut s, &mut buf).ok();
-                        buf
-                    })
-                    .unwrap_or_default();
-                anyhow::bail!("Daemon exited immediately with {}: {}", status, stderr.trim());
+            .spawn()
+            .map_err(|e| PluginError::DaemonStartFailed(format!("Failed to spawn daemon {:?}: {}", daemon_path, e)))?;
+
+        match &daemon_config.ready_check {
+            Some(check) => {
+                let timeout = daemon_config
+                    .ready_timeout_ms
+                    .map(Duration::from_millis)
+                    .unwrap_or(DEFAULT_READY_TIMEOUT);
+                if let Err(e) = wait_until_ready(&mut child, check, &self.path, timeout) {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(e);
+                }
+            }
+            None => {
+                std::thread::sleep(CRASH_CHECK_DELAY);
+                if let Some(status) = child.try_wait().map_err(PluginError::io("Failed to check daemon status"))? {
+                    if !status.success() {
+                        return Err(exited_early(&mut child, status));
+                    }
+                }
             }
-            _ => {}
         }
 
         self.daemon_process = Some(child);
@@ -76,42 +103,374 @@ impl Plugin {
         self.daemon_process.as_ref().map(|c| c.id())
     }
 
-    pub fn stop_daemon(&mut self) -> Result<()> {
+    pub fn stop_daemon(&mut self) -> Result<(), PluginError> {
         let Some(mut child) = self.daemon_process.take() else {
             return Ok(());
         };
 
         log::info!("Stopping daemon for plugin: {}", self.id);
 
-        #[cfg(unix)]
-        unsafe {
-            libc::kill(child.id() as i32, libc::SIGTERM);
+        let (signal, timeout) = match &self.manifest.daemon {
+            Some(config) => (
+                config.stop_signal,
+                config.stop_timeout_secs.map(Duration::from_secs).unwrap_or(DEFAULT_STOP_TIMEOUT),
+            ),
+            None => (StopSignal::default(), DEFAULT_STOP_TIMEOUT),
+        };
+        stop_child(&mut child, &self.id, signal, timeout)
+    }
+}
+
+fn stop_child(child: &mut Child, id: &str, signal: StopSignal, timeout: Duration) -> Result<(), PluginError> {
+    send_stop_signal(child, signal);
+
+    let start = Instant::now();
+    loop {
+        match child.try_wait().map_err(PluginError::io("Failed to check daemon status"))? {
+            Some(_) => return Ok(()),
+            None if start.elapsed() >= timeout => {
+                log::warn!("Daemon for {} didn't exit gracefully, forcing kill", id);
+                child.kill().map_err(PluginError::io("Failed to kill daemon"))?;
+                child.wait().map_err(PluginError::io("Failed to wait for daemon"))?;
+                return Ok(());
+            }
+            None => std::thread::sleep(STOP_POLL_INTERVAL),
         }
-        #[cfg(not(unix))]
-        {
-            let _ = child.kill();
+    }
+}
+
+#[cfg(unix)]
+fn send_stop_signal(child: &mut Child, signal: StopSignal) {
+    let signal = match signal {
+        StopSignal::Term => libc::SIGTERM,
+        StopSignal::Int => libc::SIGINT,
+        StopSignal::Kill => libc::SIGKILL,
+    };
+    unsafe {
+        libc::kill(child.id() as i32, signal);
+    }
+}
+
+#[cfg(not(unix))]
+fn send_stop_signal(child: &mut Child, _signal: StopSignal) {
+    let _ = child.kill();
+}
+
+fn wait_until_ready(child: &mut Child, check: &ReadyCheck, plugin_dir: &Path, timeout: Duration) -> Result<(), PluginError> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait().map_err(PluginError::io("Failed to check daemon status"))? {
+            return Err(exited_early(child, status));
+        }
+        if is_ready(check, plugin_dir) {
+            return Ok(());
         }
+        if start.elapsed() >= timeout {
+            return Err(PluginError::DaemonStartFailed(format!(
+                "Daemon did not become ready within {}ms ({:?})",
+                timeout.as_millis(),
+                check
+            )));
+        }
+        std::thread::sleep(READY_POLL_INTERVAL);
+    }
+}
 
-        let start = std::time::Instant::now();
-        let timeout = std::time::Duration::from_secs(2);
-
-        loop {
-            match child.try_wait()? {
-                Some(_) => return Ok(()),
-                None if start.elapsed() >= timeout => {
-                    log::warn!("Daemon for {} didn't exit gracefully, forcing kill", self.id);
-                    child.kill()?;
-                    child.wait()?;
-                    return Ok(());
-                }
-                None => std::thread::sleep(std::time::Duration::from_millis(50)),
-            }
+fn is_ready(check: &ReadyCheck, plugin_dir: &Path) -> bool {
+    match check {
+        ReadyCheck::Port(port) => {
+            let addr = std::net::SocketAddr::from(([127, 0, 0, 1], *port));
+            std::net::TcpStream::connect_timeout(&addr, READY_POLL_INTERVAL).is_ok()
         }
+        ReadyCheck::Socket(path) => plugin_dir.join(path).exists(),
     }
 }
 
+fn exited_early(child: &mut Child, status: std::process::ExitStatus) -> PluginError {
+    let stderr = child.stderr.take()
+        .map(|mut s| {
+            let mut buf = String::new();
+            std::io::Read::read_to_string(&mut s, &mut buf).ok();
+            buf
+        })
+        .unwrap_or_default();
+    PluginError::DaemonStartFailed(format!(
+        "Daemon exited immediately with {}: {}",
+        status,
+        stderr.trim()
+    ))
+}
+
+#[cfg(unix)]
+fn ensure_executable(path: &Path) -> Result<(), PluginError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = std::fs::metadata(path).map_err(PluginError::io("Failed to read daemon metadata"))?;
+    let mode = metadata.permissions().mode();
+    if mode & 0o111 != 0 {
+        return Ok(());
+    }
+
+    if !metadata.is_file() || !looks_executable(path) {
+        return Err(PluginError::DaemonStartFailed(format!(
+            "Daemon {:?} is not executable; run `chmod +x` on it",
+            path
+        )));
+    }
+
+    let executable = mode | ((mode & 0o444) >> 2);
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(executable)).map_err(|e| {
+        PluginError::DaemonStartFailed(format!(
+            "Daemon {:?} is not executable and could not be made executable ({}); run `chmod +x` on it",
+            path, e
+        ))
+    })?;
+    log::warn!("Daemon {:?} was missing its execute bit, restored it", path);
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn ensure_executable(_path: &Path) -> Result<(), PluginError> {
+    Ok(())
+}
+
+#[cfg(unix)]
+fn looks_executable(path: &Path) -> bool {
+    const MAGICS: [&[u8]; 6] = [
+        b"#!",
+        b"\x7fELF",
+        &[0xfe, 0xed, 0xfa, 0xce],
+        &[0xfe, 0xed, 0xfa, 0xcf],
+        &[0xce, 0xfa, 0xed, 0xfe],
+        &[0xcf, 0xfa, 0xed, 0xfe],
+    ];
+
+    let mut header = [0u8; 4];
+    let read = std::fs::File::open(path)
+        .and_then(|mut file| std::io::Read::read(&mut file, &mut header))
+        .unwrap_or(0);
+    MAGICS.iter().any(|magic| header[..read].starts_with(magic))
+}
+
 impl Drop for Plugin {
     fn drop(&mut self) {
         let _ = self.stop_daemon();
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn daemon_plugin(dir: &Path, command: &str) -> Plugin {
+        daemon_plugin_with(dir, command, "")
+    }
+
+    fn daemon_plugin_with(dir: &Path, command: &str, extra: &str) -> Plugin {
+        let manifest: PluginManifest = toml::from_str(&format!(
+            r#"[plugin]
+name = "Demo"
+description = "Demo"
+version = "1.0.0"
+
+[menu]
+label = "Demo"
+items = []
+
+[daemon]
+enabled = true
+command = "{}"
+{}
+"#,
+            command, extra
+        ))
+        .unwrap();
+        Plugin::new("demo".to_string(), manifest, dir.to_path_buf(), 0)
+    }
+
+    fn write_file(path: &Path, content: &[u8], mode: u32) {
+        std::fs::write(path, content).unwrap();
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).unwrap();
+    }
+
+    #[test]
+    fn start_daemon_restores_execute_bit_on_scripts() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let script = tmp.path().join("daemon.sh");
+        write_file(&script, b"#!/bin/sh\nsleep 5\n", 0o644);
+        let mut plugin = daemon_plugin(tmp.path(), "daemon.sh");
+
+        plugin.start_daemon().unwrap();
+
+        assert!(plugin.daemon_pid().is_some());
+        assert_eq!(std::fs::metadata(&script).unwrap().permissions().mode() & 0o777, 0o755);
+        plugin.stop_daemon().unwrap();
+    }
+
+    #[test]
+    fn start_daemon_reports_non_executable_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let daemon = tmp.path().join("daemon");
+        write_file(&daemon, b"not a script", 0o644);
+        let mut plugin = daemon_plugin(tmp.path(), "daemon");
+
+        let err = plugin.start_daemon().unwrap_err().to_string();
+
+        assert!(err.contains("is not executable"), "{}", err);
+        assert!(plugin.daemon_pid().is_none());
+        assert_eq!(std::fs::metadata(&daemon).unwrap().permissions().mode() & 0o777, 0o644);
+    }
+
+    fn sleeping_child() -> Child {
+        Command::new("sleep").arg("5").stderr(Stdio::piped()).spawn().unwrap()
+    }
+
+    #[test]
+    fn port_probe_succeeds_once_listener_appears() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let mut child = sleeping_child();
+        let listener = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(150));
+            let listener = std::net::TcpListener::bind(("127.0.0.1", port)).unwrap();
+            let _ = listener.accept();
+        });
+
+        assert!(!is_ready(&ReadyCheck::Port(port), Path::new("/")));
+        let result = wait_until_ready(&mut child, &ReadyCheck::Port(port), Path::new("/"), Duration::from_secs(5));
+
+        assert!(result.is_ok(), "{:?}", result);
+        child.kill().unwrap();
+        child.wait().unwrap();
+        listener.join().unwrap();
+    }
+
+    #[test]
+    fn ready_check_cases() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("ready.sock"), "").unwrap();
+
+        let cases = [
+            (ReadyCheck::Socket("ready.sock".to_string()), Duration::from_millis(100), true),
+            (ReadyCheck::Socket("missing.sock".to_string()), Duration::from_millis(100), false),
+        ];
+
+        for (check, timeout, expected) in cases {
+            let mut child = sleeping_child();
+            let result = wait_until_ready(&mut child, &check, tmp.path(), timeout);
+            assert_eq!(result.is_ok(), expected, "{:?}: {:?}", check, result);
+            child.kill().unwrap();
+            child.wait().unwrap();
+        }
+    }
+
+    #[test]
+    fn start_daemon_fails_when_daemon_never_becomes_ready() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_file(&tmp.path().join("daemon.sh"), b"#!/bin/sh\nsleep 5\n", 0o755);
+        let mut plugin = daemon_plugin_with(
+            tmp.path(),
+            "daemon.sh",
+            "ready_check = { socket = \"never.sock\" }\nready_timeout_ms = 100",
+        );
+
+        let err = plugin.start_daemon().unwrap_err().to_string();
+
+        assert!(err.contains("did not become ready"), "{}", err);
+        assert!(plugin.daemon_pid().is_none());
+    }
+
+    #[test]
+    fn start_daemon_reports_crash_before_ready() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_file(&tmp.path().join("daemon.sh"), b"#!/bin/sh\necho boom >&2\nexit 3\n", 0o755);
+        let mut plugin = daemon_plugin_with(tmp.path(), "daemon.sh", "ready_check = { port = 1 }");
+
+        let err = plugin.start_daemon().unwrap_err().to_string();
+
+        assert!(err.contains("exited immediately"), "{}", err);
+        assert!(err.contains("boom"), "{}", err);
+    }
+
+    #[test]
+    fn start_daemon_waits_for_socket_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_file(&tmp.path().join("daemon.sh"), b"#!/bin/sh\nsleep 0.1\ntouch ready.sock\nsleep 5\n", 0o755);
+        let mut plugin = daemon_plugin_with(tmp.path(), "daemon.sh", "ready_check = { socket = \"ready.sock\" }");
+
+        plugin.start_daemon().unwrap();
+
+        assert!(tmp.path().join("ready.sock").exists());
+        plugin.stop_daemon().unwrap();
+    }
+
+    fn stop_child_elapsed(script: &str, signal: StopSignal, timeout: Duration) -> Duration {
+        let mut child = Command::new("sh").arg("-c").arg(script).spawn().unwrap();
+        std::thread::sleep(Duration::from_millis(100));
+        let start = Instant::now();
+        stop_child(&mut child, "test", signal, timeout).unwrap();
+        assert!(child.try_wait().unwrap().is_some());
+        start.elapsed()
+    }
+
+    #[test]
+    fn stop_child_signal_cases() {
+        let cases = [
+            ("exec sleep 5", StopSignal::Term),
+            ("exec sleep 5", StopSignal::Int),
+            ("trap '' TERM INT; sleep 5", StopSignal::Kill),
+        ];
+
+        for (script, signal) in cases {
+            let elapsed = stop_child_elapsed(script, signal, Duration::from_secs(3));
+            assert!(elapsed < Duration::from_secs(2), "{:?} took {:?}", signal, elapsed);
+        }
+    }
+
+    #[test]
+    fn stop_child_force_kills_after_timeout() {
+        let cases = [
+            ("trap '' TERM; while true; do sleep 0.05; done", StopSignal::Term),
+            ("trap '' INT; while true; do sleep 0.05; done", StopSignal::Int),
+        ];
+
+        for (script, signal) in cases {
+            let elapsed = stop_child_elapsed(script, signal, Duration::from_millis(200));
+            assert!(elapsed >= Duration::from_millis(200), "{:?} took {:?}", signal, elapsed);
+            assert!(elapsed < Duration::from_secs(2), "{:?} took {:?}", signal, elapsed);
+        }
+    }
+
+    #[test]
+    fn stop_daemon_reads_timeout_from_manifest() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        write_file(&tmp.path().join("daemon.sh"), b"#!/bin/sh\ntrap '' TERM\nwhile true; do sleep 0.05; done\n", 0o755);
+        let mut plugin = daemon_plugin_with(tmp.path(), "daemon.sh", "stop_timeout_secs = 0");
+        plugin.start_daemon().unwrap();
+
+        let start = Instant::now();
+        plugin.stop_daemon().unwrap();
+
+        assert!(start.elapsed() < DEFAULT_STOP_TIMEOUT, "took {:?}", start.elapsed());
+        assert!(plugin.daemon_pid().is_none());
+    }
+
+    #[test]
+    fn looks_executable_cases() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let cases: [(&[u8], bool); 6] = [
+            (b"#!/usr/bin/env python3\n", true),
+            (b"\x7fELF\x02\x01", true),
+            (&[0xcf, 0xfa, 0xed, 0xfe, 0x07], true),
+            (b"plain text", false),
+            (b"#", false),
+            (b"", false),
+        ];
+
+        for (i, (content, expected)) in cases.iter().enumerate() {
+            let path = tmp.path().join(format!("file-{}", i));
+            std::fs::write(&path, content).unwrap();
+            assert_eq!(looks_executable(&path), *expected, "content: {:?}", content);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use serde::Serialize;
+use std::time::Duration;
+
+pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DaemonStats {
+    Running { pid: u32, cpu_percent: f64, rss_bytes: u64 },
+    NotRunning,
+    #[cfg_attr(target_os = "linux", allow(dead_code))]
+    Unsupported,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuSample {
+    pub ticks: u64,
+}
+
+pub fn parse_stat(line: &str) -> Option<CpuSample> {
+    let after_comm = &line[line.rfind(')')? + 1..];
+    let fields: Vec<&str> = after_comm.split_whitespace().collect();
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(CpuSample { ticks: utime + stime })
+}
+
+pub fn parse_statm_rss_pages(line: &str) -> Option<u64> {
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+pub fn cpu_percent(before: CpuSample, after: CpuSample, elapsed: Duration, ticks_per_sec: u64) -> f64 {
+    let seconds = elapsed.as_secs_f64();
+    if seconds <= 0.0 || ticks_per_sec == 0 {
+        return 0.0;
+    }
+    let used = after.ticks.saturating_sub(before.ticks) as f64 / ticks_per_sec as f64;
+    used / seconds * 100.0
+}
+
+#[cfg(target_os = "linux")]
+pub async fn sample(pid: u32) -> DaemonStats {
+    let Some(before) = read_cpu(pid) else {
+        return DaemonStats::NotRunning;
+    };
+    let started = std::time::Instant::now();
+    tokio::time::sleep(SAMPLE_INTERVAL).await;
+    let Some(after) = read_cpu(pid) else {
+        return DaemonStats::NotRunning;
+    };
+    let elapsed = started.elapsed();
+
+    let Some(rss_pages) = std::fs::read_to_string(format!("/proc/{}/statm", pid))
+        .ok()
+        .and_then(|line| parse_statm_rss_pages(&line))
+    else {
+        return DaemonStats::NotRunning;
+    };
+
+    let (ticks_per_sec, page_size) = unsafe {
+        (libc::sysconf(libc::_SC_CLK_TCK), libc::sysconf(libc::_SC_PAGESIZE))
+    };
+    DaemonStats::Running {
+        pid,
+        cpu_percent: cpu_percent(before, after, elapsed, ticks_per_sec.max(0) as u64),
+        rss_bytes: rss_pages * page_size.max(0) as u64,
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_cpu(pid: u32) -> Option<CpuSample> {
+    let line = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    parse_stat(&line)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub async fn sample(_pid: u32) -> DaemonStats {
+    DaemonStats::Unsupported
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_STAT: &str = "4242 (my daemon) (x)) S 1 4242 4242 0 -1 4194560 1234 0 0 0 150 50 0 0 20 0 3 0 987654 123456789 2048 18446744073709551615 1 1 0 0 0 0 0 0 0 0 0 0 17 2 0 0 0 0 0";
+
+    #[test]
+    fn parse_stat_sums_user_and_system_ticks() {
+        assert_eq!(parse_stat(SAMPLE_STAT), Some(CpuSample { ticks: 200 }));
+    }
+
+    #[test]
+    fn parse_cases() {
+        let cases = [
+            ("", None),
+            ("4242 (short) S 1 2 3", None),
+            ("4242 no-parens S 1 4242 4242 0 -1 0 0 0 0 0 150 50", None),
+            ("4242 (bad) S 1 4242 4242 0 -1 0 0 0 0 0 x 50", None),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(parse_stat(line), expected, "line: {:?}", line);
+        }
+    }
+
+    #[test]
+    fn parse_statm_cases() {
+        let cases = [
+            ("30141 2048 1500 300 0 2600 0\n", Some(2048)),
+            ("30141", None),
+            ("", None),
+        ];
+
+        for (line, expected) in cases {
+            assert_eq!(parse_statm_rss_pages(line), expected, "line: {:?}", line);
+        }
+    }
+
+    #[test]
+    fn cpu_percent_cases() {
+        let cases = [
+            (100, 150, Duration::from_secs(1), 100, 50.0),
+            (100, 300, Duration::from_secs(1), 100, 200.0),
+            (100, 100, Duration::from_millis(200), 100, 0.0),
+            (100, 120, Duration::from_millis(200), 100, 100.0),
+            (100, 150, Duration::ZERO, 100, 0.0),
+            (100, 150, Duration::from_secs(1), 0, 0.0),
+            (150, 100, Duration::from_secs(1), 100, 0.0),
+        ];
+
+        for (before, after, elapsed, hz, expected) in cases {
+            let actual = cpu_percent(CpuSample { ticks: before }, CpuSample { ticks: after }, elapsed, hz);
+            assert!((actual - expected).abs() < 1e-9, "{} -> {} over {:?}: {}", before, after, elapsed, actual);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn sample_reports_current_process() {
+        match sample(std::process::id()).await {
+            DaemonStats::Running { pid, rss_bytes, .. } => {
+                assert_eq!(pid, std::process::id());
+                assert!(rss_bytes > 0);
+            }
+            other => panic!("unexpected stats: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct ConfigSchema {
+    pub fields: BTreeMap<String, ConfigField>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ConfigField {
+    #[serde(rename = "type")]
+    pub field_type: FieldType,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    String,
+    Number,
+    Integer,
+    Boolean,
+    Array,
+    Object,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldType {
+    fn matches(self, value: &serde_json::Value) -> bool {
+        match self {
+            Self::String => value.is_string(),
+            Self::Number => value.is_number(),
+            Self::Integer => value.is_i64() || value.is_u64(),
+            Self::Boolean => value.is_boolean(),
+            Self::Array => value.is_array(),
+            Self::Object => value.is_object(),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Integer => "integer",
+            Self::Boolean => "boolean",
+            Self::Array => "array",
+            Self::Object => "object",
+        }
+    }
+}
+
+impl ConfigSchema {
+    pub fn validate(&self, config: &serde_json::Value) -> Result<(), Vec<FieldError>> {
+        let Some(object) = config.as_object() else {
+            return Err(vec![FieldError {
+                field: String::new(),
+                message: "Config must be a JSON object".to_string(),
+            }]);
+        };
+
+        let errors: Vec<FieldError> = self
+            .fields
+            .iter()
+            .filter_map(|(name, field)| {
+                let message = match object.get(name) {
+                    None if field.required => "Missing required field".to_string(),
+                    None => return None,
+                    Some(value) if !field.field_type.matches(value) => {
+                        format!("Expected {}", field.field_type.name())
+                    }
+                    Some(_) => return None,
+                };
+                Some(FieldError {
+                    field: name.clone(),
+                    message,
+                })
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SCHEMA: &str = r#"
+enabled = { type = "boolean", required = true }
+interval = { type = "integer" }
+name = { type = "string" }
+"#;
+
+    fn schema() -> ConfigSchema {
+        toml::from_str(SCHEMA).unwrap()
+    }
+
+    #[test]
+    fn valid_config_passes() {
+        let cases = [
+            json!({"enabled": true}),
+            json!({"enabled": false, "interval": 5, "name": "x"}),
+            json!({"enabled": true, "extra": [1, 2]}),
+        ];
+
+        for config in cases {
+            assert!(schema().validate(&config).is_ok(), "should be valid: {}", config);
+        }
+    }
+
+    #[test]
+    fn missing_required_field_is_reported() {
+        let errors = schema().validate(&json!({"interval": 5})).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![FieldError {
+                field: "enabled".to_string(),
+                message: "Missing required field".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn type_mismatch_is_reported() {
+        let cases = [
+            (json!({"enabled": "yes"}), vec!["enabled"]),
+            (json!({"enabled": true, "interval": 1.5}), vec!["interval"]),
+            (json!({"enabled": 1, "name": 2}), vec!["enabled", "name"]),
+        ];
+
+        for (config, expected) in cases {
+            let errors = schema().validate(&config).unwrap_err();
+            let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+            assert_eq!(fields, expected, "config: {}", config);
+            assert!(errors.iter().all(|e| e.message.starts_with("Expected ")));
+        }
+    }
+
+    #[test]
+    fn non_object_config_is_rejected() {
+        assert!(schema().validate(&json!([1, 2])).is_err());
+    }
+}
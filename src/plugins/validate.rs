@@ -0,0 +1,162 @@
+use serde::Serialize;
+use std::path::Path;
+
+use super::manifest::KNOWN_PLATFORMS;
+use super::{ActionType, MenuItem, PluginLoader};
+
+#[derive(Debug, Default, Serialize)]
+pub struct ValidationReport {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+pub fn validate_plugin(path: &Path) -> ValidationReport {
+    let mut report = ValidationReport::default();
+
+    let plugin = match PluginLoader::load_plugin(path) {
+        Ok(plugin) => plugin,
+        Err(e) => {
+            report.errors.push(e.to_string());
+            return report;
+        }
+    };
+
+    for platform in plugin.manifest.plugin.unknown_platforms() {
+        report.errors.push(format!(
+            "Unknown platform '{}' (expected one of: {})",
+            platform,
+            KNOWN_PLATFORMS.join(", ")
+        ));
+    }
+
+    for id in custom_actions_without_command(&plugin.manifest.menu.items) {
+        report.errors.push(format!("Custom action '{}' has no command", id));
+    }
+
+    let has_ui = path.join("ui").join("index.html").is_file();
+    if !has_ui && uses_settings_action(&plugin.manifest.menu.items) {
+        report
+            .warnings
+            .push("Menu items open settings but ui/index.html is missing".to_string());
+    }
+
+    report
+}
+
+fn custom_actions_without_command(items: &[MenuItem]) -> Vec<&str> {
+    items
+        .iter()
+        .flat_map(|item| match item {
+            MenuItem::Action { id, action: ActionType::Custom, command: None, .. } => vec![id.as_str()],
+            MenuItem::Submenu { items, .. } => custom_actions_without_command(items),
+            _ => vec![],
+        })
+        .collect()
+}
+
+fn uses_settings_action(items: &[MenuItem]) -> bool {
+    items.iter().any(|item| match item {
+        MenuItem::Action { action, .. } | MenuItem::Checkbox { action, .. } => {
+            *action == ActionType::Settings
+        }
+        MenuItem::Submenu { items, .. } => uses_settings_action(items),
+        MenuItem::Separator => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_manifest(platforms: &str, action: &str) -> TempDir {
+        let tmp = TempDir::new().unwrap();
+        let manifest = format!(
+            r#"[plugin]
+name = "Demo"
+description = "Demo plugin"
+version = "1.0.0"
+{}
+
+[menu]
+label = "Demo"
+
+[[menu.items]]
+type = "action"
+id = "open"
+label = "Open"
+action = "{}"
+"#,
+            platforms, action
+        );
+        std::fs::write(tmp.path().join("plugin.toml"), manifest).unwrap();
+        tmp
+    }
+
+    #[test]
+    fn validate_plugin_cases() {
+        let cases = [
+            ("", "run", true, 0),
+            (r#"platforms = ["linux", "macos"]"#, "run", true, 0),
+            ("", "settings", true, 1),
+            (r#"platforms = ["linux", "beos"]"#, "run", false, 0),
+            ("", "launch", false, 0),
+            ("", "custom", false, 0),
+        ];
+
+        for (platforms, action, valid, warnings) in cases {
+            let tmp = write_manifest(platforms, action);
+            let report = validate_plugin(tmp.path());
+            assert_eq!(report.is_valid(), valid, "{} {}: {:?}", platforms, action, report);
+            assert_eq!(report.warnings.len(), warnings, "{} {}: {:?}", platforms, action, report);
+        }
+    }
+
+    #[test]
+    fn bad_action_type_reports_line_context() {
+        let tmp = write_manifest("", "launch");
+
+        let report = validate_plugin(tmp.path());
+
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].contains("line 10"), "{}", report.errors[0]);
+        assert!(report.errors[0].contains("launch"), "{}", report.errors[0]);
+    }
+
+    #[test]
+    fn unknown_platform_is_named_in_error() {
+        let tmp = write_manifest(r#"platforms = ["beos"]"#, "run");
+
+        let report = validate_plugin(tmp.path());
+
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].contains("'beos'"), "{}", report.errors[0]);
+    }
+
+    #[test]
+    fn settings_warning_clears_when_ui_exists() {
+        let tmp = write_manifest("", "settings");
+        std::fs::create_dir_all(tmp.path().join("ui")).unwrap();
+        std::fs::write(tmp.path().join("ui").join("index.html"), "<html></html>").unwrap();
+
+        let report = validate_plugin(tmp.path());
+
+        assert!(report.is_valid());
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+    }
+
+    #[test]
+    fn missing_manifest_is_an_error() {
+        let tmp = TempDir::new().unwrap();
+
+        let report = validate_plugin(tmp.path());
+
+        assert!(!report.is_valid());
+    }
+}
@@ -0,0 +1,191 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSettings {
+    #[serde(default)]
+    pub prune_config_backups_after_days: Option<u64>,
+    #[serde(default = "default_notifications")]
+    pub notifications: bool,
+    #[serde(default)]
+    pub unix_socket: bool,
+    #[serde(default)]
+    pub plugin_cache_ttl_secs: Option<u64>,
+    #[serde(default = "default_clone_depth")]
+    pub clone_depth: u32,
+    #[serde(default)]
+    pub capture_plugin_output: bool,
+    #[serde(default)]
+    pub dev_tools_enabled: bool,
+    #[serde(default = "default_plugin_org")]
+    pub plugin_org: String,
+    #[serde(default = "default_github_api_base")]
+    pub github_api_base: String,
+    #[serde(default = "default_github_raw_base")]
+    pub github_raw_base: String,
+    #[serde(default = "default_github_web_base")]
+    pub github_web_base: String,
+    #[serde(default)]
+    pub cors_origins: Vec<String>,
+    #[serde(default)]
+    pub offline: bool,
+}
+
+fn default_notifications() -> bool {
+    true
+}
+
+fn default_clone_depth() -> u32 {
+    1
+}
+
+fn default_plugin_org() -> String {
+    "qol-tools".to_string()
+}
+
+fn default_github_api_base() -> String {
+    "https://api.github.com".to_string()
+}
+
+fn default_github_raw_base() -> String {
+    "https://raw.githubusercontent.com".to_string()
+}
+
+fn default_github_web_base() -> String {
+    "https://github.com".to_string()
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            prune_config_backups_after_days: None,
+            notifications: default_notifications(),
+            unix_socket: false,
+            plugin_cache_ttl_secs: None,
+            clone_depth: default_clone_depth(),
+            capture_plugin_output: false,
+            dev_tools_enabled: false,
+            plugin_org: default_plugin_org(),
+            github_api_base: default_github_api_base(),
+            github_raw_base: default_github_raw_base(),
+            github_web_base: default_github_web_base(),
+            cors_origins: Vec::new(),
+            offline: false,
+        }
+    }
+}
+
+impl AppSettings {
+    pub fn load() -> Result<Self> {
+        let path = crate::paths::settings_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let settings: AppSettings = serde_json::from_str(&content)?;
+        Ok(settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_settings_cases() {
+        let cases = [
+            ("{}", None),
+            (r#"{"prune_config_backups_after_days": 30}"#, Some(30)),
+            (r#"{"prune_config_backups_after_days": null}"#, None),
+        ];
+
+        for (json, expected) in cases {
+            let settings: AppSettings = serde_json::from_str(json).unwrap();
+            assert_eq!(settings.prune_config_backups_after_days, expected, "json: {}", json);
+        }
+    }
+
+    #[test]
+    fn notifications_default_to_enabled() {
+        let cases = [
+            ("{}", true),
+            (r#"{"notifications": false}"#, false),
+            (r#"{"notifications": true}"#, true),
+        ];
+
+        for (json, expected) in cases {
+            let settings: AppSettings = serde_json::from_str(json).unwrap();
+            assert_eq!(settings.notifications, expected, "json: {}", json);
+        }
+        assert!(AppSettings::default().notifications);
+    }
+
+    #[test]
+    fn clone_depth_defaults_to_shallow() {
+        let cases = [
+            ("{}", 1),
+            (r#"{"clone_depth": 10}"#, 10),
+            (r#"{"clone_depth": 0}"#, 0),
+        ];
+
+        for (json, expected) in cases {
+            let settings: AppSettings = serde_json::from_str(json).unwrap();
+            assert_eq!(settings.clone_depth, expected, "json: {}", json);
+        }
+        assert_eq!(AppSettings::default().clone_depth, 1);
+    }
+
+    #[test]
+    fn capture_plugin_output_defaults_to_disabled() {
+        let settings: AppSettings = serde_json::from_str("{}").unwrap();
+        assert!(!settings.capture_plugin_output);
+
+        let settings: AppSettings = serde_json::from_str(r#"{"capture_plugin_output": true}"#).unwrap();
+        assert!(settings.capture_plugin_output);
+    }
+
+    #[test]
+    fn plugin_org_defaults_to_qol_tools() {
+        let cases = [
+            ("{}", "qol-tools"),
+            (r#"{"plugin_org": "acme-internal"}"#, "acme-internal"),
+        ];
+
+        for (json, expected) in cases {
+            let settings: AppSettings = serde_json::from_str(json).unwrap();
+            assert_eq!(settings.plugin_org, expected, "json: {}", json);
+        }
+        assert_eq!(AppSettings::default().plugin_org, "qol-tools");
+    }
+
+    #[test]
+    fn github_bases_default_to_github_com() {
+        let settings: AppSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings.github_api_base, "https://api.github.com");
+        assert_eq!(settings.github_raw_base, "https://raw.githubusercontent.com");
+        assert_eq!(settings.github_web_base, "https://github.com");
+
+        let settings: AppSettings =
+            serde_json::from_str(r#"{"github_api_base": "https://ghe.example.com/api/v3"}"#).unwrap();
+        assert_eq!(settings.github_api_base, "https://ghe.example.com/api/v3");
+        assert_eq!(settings.github_raw_base, "https://raw.githubusercontent.com");
+    }
+
+    #[test]
+    fn dev_tools_default_to_disabled() {
+        let settings: AppSettings = serde_json::from_str("{}").unwrap();
+        assert!(!settings.dev_tools_enabled);
+
+        let settings: AppSettings = serde_json::from_str(r#"{"dev_tools_enabled": true}"#).unwrap();
+        assert!(settings.dev_tools_enabled);
+    }
+
+    #[test]
+    fn unix_socket_defaults_to_disabled() {
+        let settings: AppSettings = serde_json::from_str("{}").unwrap();
+        assert!(!settings.unix_socket);
+
+        let settings: AppSettings = serde_json::from_str(r#"{"unix_socket": true}"#).unwrap();
+        assert!(settings.unix_socket);
+    }
+}
@@ -1,8 +1,11 @@
+use anyhow::Result;
+use std::path::Path;
 use tray_icon::Icon;
 
 const ICON_SIZE: u32 = 64;
 const ICON_DATA: &[u8] = include_bytes!("../../assets/icon.rgba");
-const DOT_RADIUS: i32 = 8;
+const DOT_RADIUS_DIVISOR: f32 = 8.0;
+const DOT_INSET_DIVISOR: f32 = 16.0;
 const DOT_COLOR: [u8; 4] = [230, 150, 0, 255];
 
 const _: () = assert!(
@@ -22,23 +25,146 @@ pub fn create_icon_with_dot() -> Icon {
         .expect("embedded icon.rgba is valid")
 }
 
+pub fn load_menu_icon(path: &Path) -> Option<tray_icon::menu::Icon> {
+    let icon = std::fs::read(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|bytes| decode_png(&bytes))
+        .and_then(|(rgba, width, height)| Ok(tray_icon::menu::Icon::from_rgba(rgba, width, height)?));
+    match icon {
+        Ok(icon) => Some(icon),
+        Err(e) => {
+            log::warn!("Skipping menu icon {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+pub fn decode_png(bytes: &[u8]) -> Result<(Vec<u8>, u32, u32)> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf)?;
+    buf.truncate(info.buffer_size());
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => buf,
+        png::ColorType::Rgb => buf.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => buf.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => buf.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => anyhow::bail!("Unexpanded indexed PNG"),
+    };
+    Ok((rgba, info.width, info.height))
+}
+
 fn add_notification_dot(data: &mut [u8], size: u32) {
-    let center_x = (size as i32) - DOT_RADIUS - 2;
-    let center_y = DOT_RADIUS + 2;
-    let radius_sq = DOT_RADIUS * DOT_RADIUS;
+    let radius = size as f32 / DOT_RADIUS_DIVISOR;
+    let inset = size as f32 / DOT_INSET_DIVISOR;
+    let center_x = size as f32 - inset - radius;
+    let center_y = inset + radius;
 
-    let pixels = (0..size as i32).flat_map(|y| (0..size as i32).map(move |x| (x, y)));
-    pixels.filter(|&(x, y)| is_within_dot(x, y, center_x, center_y, radius_sq))
-        .for_each(|(x, y)| set_pixel(data, x, y, size, DOT_COLOR));
+    let pixels = (0..size).flat_map(|y| (0..size).map(move |x| (x, y)));
+    for (x, y) in pixels {
+        let coverage = dot_coverage(x, y, center_x, center_y, radius);
+        if coverage > 0.0 {
+            blend_pixel(data, x, y, size, DOT_COLOR, coverage);
+        }
+    }
 }
 
-fn is_within_dot(x: i32, y: i32, cx: i32, cy: i32, radius_sq: i32) -> bool {
-    let dx = x - cx;
-    let dy = y - cy;
-    dx * dx + dy * dy <= radius_sq
+fn dot_coverage(x: u32, y: u32, cx: f32, cy: f32, radius: f32) -> f32 {
+    let dx = x as f32 + 0.5 - cx;
+    let dy = y as f32 + 0.5 - cy;
+    let distance = (dx * dx + dy * dy).sqrt();
+    (radius + 0.5 - distance).clamp(0.0, 1.0)
 }
 
-fn set_pixel(data: &mut [u8], x: i32, y: i32, size: u32, color: [u8; 4]) {
-    let idx = ((y as u32 * size + x as u32) * 4) as usize;
-    data[idx..idx + 4].copy_from_slice(&color);
+fn blend_pixel(data: &mut [u8], x: u32, y: u32, size: u32, color: [u8; 4], coverage: f32) {
+    let idx = ((y * size + x) * 4) as usize;
+    for (channel, &target) in data[idx..idx + 4].iter_mut().zip(color.iter()) {
+        let blended = *channel as f32 + (target as f32 - *channel as f32) * coverage;
+        *channel = blended.round() as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_png(color: png::ColorType, width: u32, height: u32, data: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(color);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header().unwrap().write_image_data(data).unwrap();
+        out
+    }
+
+    #[test]
+    fn decode_png_expands_to_rgba() {
+        let cases = [
+            (png::ColorType::Rgba, vec![1, 2, 3, 4], vec![1, 2, 3, 4]),
+            (png::ColorType::Rgb, vec![1, 2, 3], vec![1, 2, 3, 255]),
+            (png::ColorType::GrayscaleAlpha, vec![9, 7], vec![9, 9, 9, 7]),
+            (png::ColorType::Grayscale, vec![9], vec![9, 9, 9, 255]),
+        ];
+
+        for (color, data, expected) in cases {
+            let bytes = encode_png(color, 1, 1, &data);
+
+            let (rgba, width, height) = decode_png(&bytes).unwrap();
+
+            assert_eq!((width, height), (1, 1), "color: {:?}", color);
+            assert_eq!(rgba, expected, "color: {:?}", color);
+        }
+    }
+
+    fn dot_pixels(size: u32) -> Vec<(u32, u32, u8)> {
+        let mut data = vec![0; (size * size * 4) as usize];
+        add_notification_dot(&mut data, size);
+        data.chunks_exact(4)
+            .enumerate()
+            .filter(|(_, pixel)| pixel[3] > 0)
+            .map(|(i, pixel)| (i as u32 % size, i as u32 / size, pixel[3]))
+            .collect()
+    }
+
+    #[test]
+    fn notification_dot_scales_into_top_right_quadrant() {
+        for size in [32, 64, 128] {
+            let pixels = dot_pixels(size);
+            let radius = size / 8;
+
+            assert!(!pixels.is_empty(), "size: {}", size);
+            assert!(
+                pixels.iter().all(|&(x, y, _)| x >= size / 2 && y < size / 2),
+                "size: {}",
+                size
+            );
+            assert!(pixels.iter().all(|&(x, y, _)| x < size && y < size), "size: {}", size);
+
+            let width = pixels.iter().map(|p| p.0).max().unwrap() - pixels.iter().map(|p| p.0).min().unwrap() + 1;
+            assert!(width.abs_diff(radius * 2) <= 2, "size: {}, width: {}", size, width);
+        }
+    }
+
+    #[test]
+    fn notification_dot_has_antialiased_edge() {
+        let pixels = dot_pixels(128);
+
+        assert!(pixels.iter().any(|&(_, _, alpha)| alpha == 255));
+        assert!(pixels.iter().any(|&(_, _, alpha)| alpha > 0 && alpha < 255));
+    }
+
+    #[test]
+    fn decode_png_rejects_invalid_data() {
+        assert!(decode_png(b"not a png").is_err());
+    }
+
+    #[test]
+    fn load_menu_icon_skips_missing_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+
+        assert!(load_menu_icon(&dir.path().join("missing.png")).is_none());
+    }
 }
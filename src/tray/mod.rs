@@ -16,19 +16,50 @@ impl TrayManager {
         shutdown_tx: broadcast::Sender<()>,
         shutdown_rx: broadcast::Receiver<()>,
         update_available: bool,
+        plugin_count: usize,
     ) -> Result<Self> {
         let icon = if update_available {
             icon::create_icon_with_dot()
         } else {
             icon::create_icon()
         };
+        let tooltip = tooltip_text(env!("CARGO_PKG_VERSION"), plugin_count, update_available);
         let tray = platform::create_tray(
             feature_registry,
             shutdown_tx,
             shutdown_rx,
             icon,
+            tooltip,
             update_available,
         )?;
         Ok(Self { _tray: tray })
     }
 }
+
+pub fn tooltip_text(version: &str, plugin_count: usize, update_available: bool) -> String {
+    let plugins = if plugin_count == 1 { "plugin" } else { "plugins" };
+    let text = format!("QoL Tray v{} — {} {}", version, plugin_count, plugins);
+    if update_available {
+        format!("{} (update available)", text)
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tooltip_text_cases() {
+        let cases = [
+            ("1.2.3", 5, false, "QoL Tray v1.2.3 — 5 plugins"),
+            ("1.2.3", 1, false, "QoL Tray v1.2.3 — 1 plugin"),
+            ("1.2.3", 0, true, "QoL Tray v1.2.3 — 0 plugins (update available)"),
+        ];
+
+        for (version, count, update_available, expected) in cases {
+            assert_eq!(tooltip_text(version, count, update_available), expected);
+        }
+    }
+}
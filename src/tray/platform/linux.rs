@@ -1,10 +1,10 @@
 use crate::features::FeatureRegistry;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use gtk::{self, glib};
 use once_cell::sync::OnceCell;
 use std::sync::Arc;
 use tokio::sync::broadcast;
-use tray_icon::{Icon, TrayIconBuilder};
+use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
 
 static SHUTDOWN_RX: OnceCell<std::sync::Mutex<Option<broadcast::Receiver<()>>>> = OnceCell::new();
 
@@ -24,52 +24,93 @@ pub fn create_tray(
     feature_registry: Arc<FeatureRegistry>,
     shutdown_tx: broadcast::Sender<()>,
     icon: Icon,
+    tooltip: String,
     update_available: bool,
 ) -> Result<()> {
-    std::thread::spawn(move || {
-        if gtk::init().is_err() {
-            log::error!("Failed to initialize GTK");
-            return;
-        }
+    let runtime = tokio::runtime::Handle::try_current().ok();
+    let setup = move || {
+        let _guard = runtime.as_ref().map(|handle| handle.enter());
+        gtk::init().context("Failed to initialize GTK (is a graphical session available?)")?;
 
-        let (menu, router) = match crate::menu::builder::build_menu(feature_registry, update_available) {
-            Ok(result) => result,
-            Err(e) => {
-                log::error!("Failed to build menu: {}", e);
-                return;
-            }
-        };
+        let (menu, router) = crate::menu::builder::build_menu(feature_registry.clone(), update_available)?;
 
         let tray_icon = TrayIconBuilder::new()
             .with_menu(Box::new(menu))
-            .with_tooltip("QoL Tray")
+            .with_tooltip(tooltip)
             .with_icon(icon)
-            .build();
+            .build()
+            .context("Failed to create tray icon")?;
+
+        setup_event_loop(
+            router,
+            shutdown_tx,
+            MenuSource {
+                tray_icon,
+                feature_registry,
+                update_available,
+                runtime,
+            },
+        );
+        Ok(())
+    };
+
+    spawn_ui_thread(setup, gtk::main)
+}
 
-        let tray_icon = match tray_icon {
-            Ok(icon) => icon,
-            Err(e) => {
-                log::error!("Failed to create tray icon: {}", e);
-                return;
-            }
-        };
+fn spawn_ui_thread<S, L>(setup: S, run_loop: L) -> Result<()>
+where
+    S: FnOnce() -> Result<()> + Send + 'static,
+    L: FnOnce() + Send + 'static,
+{
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
 
-        setup_event_loop(router, shutdown_tx);
-        std::mem::forget(tray_icon);
-        gtk::main();
+    std::thread::spawn(move || {
+        let result = setup();
+        let ready = result.is_ok();
+        let _ = ready_tx.send(result);
+        if ready {
+            run_loop();
+        }
     });
 
-    Ok(())
+    ready_rx.recv().context("Tray thread exited before initialization")?
+}
+
+struct MenuSource {
+    tray_icon: TrayIcon,
+    feature_registry: Arc<FeatureRegistry>,
+    update_available: bool,
+    runtime: Option<tokio::runtime::Handle>,
+}
+
+impl MenuSource {
+    fn rebuild(&self, router: &mut crate::menu::router::EventRouter) {
+        let _guard = self.runtime.as_ref().map(|handle| handle.enter());
+        match crate::menu::builder::build_menu(self.feature_registry.clone(), self.update_available) {
+            Ok((menu, new_router)) => {
+                self.tray_icon.set_menu(Some(Box::new(menu)));
+                *router = new_router;
+                log::info!("Tray menu rebuilt");
+            }
+            Err(e) => log::error!("Failed to rebuild tray menu: {}", e),
+        }
+    }
 }
 
-fn setup_event_loop(router: crate::menu::router::EventRouter, shutdown_tx: broadcast::Sender<()>) {
+fn setup_event_loop(
+    mut router: crate::menu::router::EventRouter,
+    shutdown_tx: broadcast::Sender<()>,
+    source: MenuSource,
+) {
     use tray_icon::menu::MenuEvent;
 
     let menu_receiver = MenuEvent::receiver();
-    let router = Arc::new(router);
 
     glib::timeout_add_local(std::time::Duration::from_millis(100), move || {
-        process_pending_events(&menu_receiver, &router, &shutdown_tx)
+        if crate::menu::take_rebuild_request() {
+            source.rebuild(&mut router);
+        }
+        process_pending_events(menu_receiver, &router, &shutdown_tx)
     });
 }
 
@@ -107,3 +148,32 @@ fn handle_menu_event(
     let _ = shutdown_tx.send(());
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawn_ui_thread_propagates_setup_failure() {
+        let result = spawn_ui_thread(|| anyhow::bail!("no display"), || panic!("loop must not run"));
+
+        assert_eq!(result.unwrap_err().to_string(), "no display");
+    }
+
+    #[test]
+    fn spawn_ui_thread_runs_loop_after_successful_setup() {
+        let (loop_tx, loop_rx) = std::sync::mpsc::channel();
+
+        let result = spawn_ui_thread(|| Ok(()), move || loop_tx.send(()).unwrap());
+
+        assert!(result.is_ok());
+        assert!(loop_rx.recv_timeout(std::time::Duration::from_secs(5)).is_ok());
+    }
+
+    #[test]
+    fn spawn_ui_thread_reports_crashed_setup() {
+        let result = spawn_ui_thread(|| panic!("setup crashed"), || {});
+
+        assert!(result.is_err());
+    }
+}
@@ -8,16 +8,19 @@ pub fn create_tray(
     feature_registry: Arc<FeatureRegistry>,
     shutdown_tx: broadcast::Sender<()>,
     icon: Icon,
+    tooltip: String,
     update_available: bool,
 ) -> Result<TrayIcon> {
     let (menu, router) = crate::menu::builder::build_menu(feature_registry, update_available)?;
 
     let tray_icon = TrayIconBuilder::new()
         .with_menu(Box::new(menu))
-        .with_tooltip("QoL Tray")
+        .with_tooltip(tooltip)
         .with_icon(icon)
+        .with_menu_on_left_click(false)
         .build()?;
 
+    super::spawn_tray_event_handler();
     super::spawn_menu_event_handler(shutdown_tx, router, stop_event_loop);
 
     Ok(tray_icon)
@@ -16,6 +16,10 @@ use tokio::sync::broadcast;
 #[cfg(any(target_os = "macos", target_os = "windows"))]
 use tray_icon::menu::MenuEvent;
 use tray_icon::Icon;
+#[cfg(any(target_os = "macos", target_os = "windows", test))]
+use tray_icon::{MouseButton, MouseButtonState};
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use tray_icon::TrayIconEvent;
 
 #[cfg(any(target_os = "macos", target_os = "windows"))]
 use tray_icon::TrayIcon;
@@ -36,12 +40,13 @@ pub fn create_tray(
     shutdown_tx: broadcast::Sender<()>,
     shutdown_rx: broadcast::Receiver<()>,
     icon: Icon,
+    tooltip: String,
     update_available: bool,
 ) -> Result<PlatformTray> {
     #[cfg(target_os = "linux")]
     {
         linux::store_shutdown_rx(shutdown_rx);
-        linux::create_tray(feature_registry, shutdown_tx, icon, update_available)?;
+        linux::create_tray(feature_registry, shutdown_tx, icon, tooltip, update_available)?;
         Ok(PlatformTray::Linux)
     }
 
@@ -49,7 +54,7 @@ pub fn create_tray(
     {
         let _ = shutdown_rx;
         let tray_icon =
-            macos::create_tray(feature_registry, shutdown_tx, icon, update_available)?;
+            macos::create_tray(feature_registry, shutdown_tx, icon, tooltip, update_available)?;
         Ok(PlatformTray::MacOS(tray_icon))
     }
 
@@ -57,7 +62,7 @@ pub fn create_tray(
     {
         let _ = shutdown_rx;
         let tray_icon =
-            windows::create_tray(feature_registry, shutdown_tx, icon, update_available)?;
+            windows::create_tray(feature_registry, shutdown_tx, icon, tooltip, update_available)?;
         Ok(PlatformTray::Windows(tray_icon))
     }
 }
@@ -67,7 +72,9 @@ pub fn run_app<F>(init: F) -> Result<()>
 where
     F: FnOnce() -> Result<(TrayManager, Arc<Mutex<PluginManager>>)>,
 {
-    let (_tray, plugin_manager) = init()?;
+    let (_tray, plugin_manager) = init().inspect_err(|e| {
+        log::error!("Failed to start tray, exiting: {:#}", e);
+    })?;
 
     #[cfg(target_os = "linux")]
     linux::run_event_loop();
@@ -113,3 +120,54 @@ pub(crate) fn spawn_menu_event_handler<F>(
         }
     });
 }
+
+#[cfg(any(target_os = "macos", target_os = "windows", test))]
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum TrayClickAction {
+    OpenStore,
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows", test))]
+pub(crate) fn click_action(button: MouseButton, state: MouseButtonState) -> Option<TrayClickAction> {
+    match (button, state) {
+        (MouseButton::Left, MouseButtonState::Up) => Some(TrayClickAction::OpenStore),
+        _ => None,
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+pub(crate) fn spawn_tray_event_handler() {
+    let tray_receiver = TrayIconEvent::receiver();
+
+    std::thread::spawn(move || {
+        while let Ok(event) = tray_receiver.recv() {
+            let TrayIconEvent::Click { button, button_state, .. } = event else {
+                continue;
+            };
+            if click_action(button, button_state) == Some(TrayClickAction::OpenStore) {
+                if let Err(e) = crate::features::plugin_store::open_store() {
+                    log::error!("Failed to open plugin store: {}", e);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn click_action_cases() {
+        let cases = [
+            (MouseButton::Left, MouseButtonState::Up, Some(TrayClickAction::OpenStore)),
+            (MouseButton::Left, MouseButtonState::Down, None),
+            (MouseButton::Right, MouseButtonState::Up, None),
+            (MouseButton::Middle, MouseButtonState::Up, None),
+        ];
+
+        for (button, state, expected) in cases {
+            assert_eq!(click_action(button, state), expected, "{:?} {:?}", button, state);
+        }
+    }
+}
@@ -12,6 +12,7 @@ pub fn create_tray(
     feature_registry: Arc<FeatureRegistry>,
     shutdown_tx: broadcast::Sender<()>,
     icon: Icon,
+    tooltip: String,
     update_available: bool,
 ) -> Result<TrayIcon> {
     QUIT_SIGNAL.get_or_init(std::sync::Condvar::new);
@@ -21,10 +22,12 @@ pub fn create_tray(
 
     let tray_icon = TrayIconBuilder::new()
         .with_menu(Box::new(menu))
-        .with_tooltip("QoL Tray")
+        .with_tooltip(tooltip)
         .with_icon(icon)
+        .with_menu_on_left_click(false)
         .build()?;
 
+    super::spawn_tray_event_handler();
     super::spawn_menu_event_handler(shutdown_tx, router, signal_quit);
 
     Ok(tray_icon)
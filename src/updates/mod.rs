@@ -1,19 +1,92 @@
 use anyhow::Result;
-use serde::Deserialize;
-use std::sync::OnceLock;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-static LATEST_VERSION: OnceLock<String> = OnceLock::new();
+static LATEST_VERSION: RwLock<Option<String>> = RwLock::new(None);
 
 const GITHUB_REPO: &str = "qol-tools/qol-tray";
 const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const NO_UPDATE_CHECK_ENV: &str = "QOL_NO_UPDATE_CHECK";
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct UpdateCache {
+    latest_version: String,
+    checked_at: u64,
+}
 
 #[derive(Debug, Deserialize)]
 struct GitHubRelease {
     tag_name: String,
 }
 
-pub fn latest_version() -> Option<&'static str> {
-    LATEST_VERSION.get().map(|s| s.as_str())
+pub fn checks_disabled() -> bool {
+    resolve_checks_disabled(
+        cfg!(debug_assertions),
+        std::env::var(NO_UPDATE_CHECK_ENV).ok().as_deref(),
+        crate::http::offline(),
+    )
+}
+
+fn resolve_checks_disabled(debug_build: bool, env_value: Option<&str>, offline: bool) -> bool {
+    if debug_build || offline {
+        return true;
+    }
+    match env_value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+        None | Some("" | "0" | "false" | "no" | "off") => false,
+        Some(_) => true,
+    }
+}
+
+pub fn latest_version() -> Option<String> {
+    LATEST_VERSION.read().ok().and_then(|latest| latest.clone())
+}
+
+fn set_latest_version(version: &str) {
+    if let Ok(mut latest) = LATEST_VERSION.write() {
+        *latest = Some(version.to_string());
+    }
+}
+
+pub fn cached_update() -> Option<String> {
+    let path = crate::paths::update_cache_path().ok()?;
+    let version = load_cached_update(&path, CURRENT_VERSION)?;
+    set_latest_version(&version);
+    log::info!("Update to {} known from last check", version);
+    Some(version)
+}
+
+fn load_cached_update(path: &Path, current: &str) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let cache: UpdateCache = serde_json::from_str(&content).ok()?;
+    if is_newer_version(&cache.latest_version, current) {
+        return Some(cache.latest_version);
+    }
+    let _ = std::fs::remove_file(path);
+    None
+}
+
+fn store_update_result(path: &Path, latest: Option<&str>) {
+    let Some(latest) = latest else {
+        let _ = std::fs::remove_file(path);
+        return;
+    };
+    let cache = UpdateCache {
+        latest_version: latest.to_string(),
+        checked_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+    };
+    match serde_json::to_string(&cache) {
+        Ok(json) => {
+            if let Err(e) = crate::paths::atomic_write(path, json) {
+                log::warn!("Failed to write update cache: {}", e);
+            }
+        }
+        Err(e) => log::warn!("Failed to serialize update cache: {}", e),
+    }
 }
 
 pub async fn check_for_updates() -> Result<bool> {
@@ -22,21 +95,22 @@ pub async fn check_for_updates() -> Result<bool> {
         GITHUB_REPO
     );
 
-    let client = reqwest::Client::builder()
-        .user_agent("qol-tray")
-        .build()?;
-
-    let response = client.get(&url).send().await?;
+    let response = crate::http::client().get(&url).send().await?;
 
     if !response.status().is_success() {
-        return Ok(false);
+        anyhow::bail!("Release lookup failed: {}", response.status());
     }
 
     let release: GitHubRelease = response.json().await?;
     let latest = release.tag_name.trim_start_matches('v');
 
-    if is_newer_version(latest, CURRENT_VERSION) {
-        let _ = LATEST_VERSION.set(latest.to_string());
+    let newer = is_newer_version(latest, CURRENT_VERSION);
+    if let Ok(path) = crate::paths::update_cache_path() {
+        store_update_result(&path, newer.then_some(latest));
+    }
+
+    if newer {
+        set_latest_version(latest);
         log::info!(
             "Update available: {} -> {}",
             CURRENT_VERSION,
@@ -57,7 +131,7 @@ fn is_newer_version(latest: &str, current: &str) -> bool {
 #[cfg(target_os = "linux")]
 pub async fn download_and_install() -> Result<()> {
     let version = latest_version().ok_or_else(|| anyhow::anyhow!("No update version available"))?;
-    let deb_path = download_deb(version).await?;
+    let deb_path = download_deb(&version).await?;
     install_deb(&deb_path)?;
     restart_with_cleanup();
 }
@@ -72,8 +146,11 @@ async fn download_deb(version: &str) -> Result<std::path::PathBuf> {
 
     log::info!("Downloading update from {}", url);
 
-    let client = reqwest::Client::builder().user_agent("qol-tray").build()?;
-    let response = client.get(&url).send().await?;
+    let response = crate::http::client()
+        .get(&url)
+        .timeout(crate::http::DOWNLOAD_TIMEOUT)
+        .send()
+        .await?;
 
     if !response.status().is_success() {
         anyhow::bail!("Failed to download update: {}", response.status());
@@ -121,3 +198,82 @@ pub async fn download_and_install() -> Result<()> {
     crate::paths::open_url(&url)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_cache(path: &Path, latest: &str) {
+        let cache = UpdateCache { latest_version: latest.to_string(), checked_at: 1 };
+        std::fs::write(path, serde_json::to_string(&cache).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn resolve_checks_disabled_cases() {
+        let cases = [
+            (false, None, false, false),
+            (false, Some(""), false, false),
+            (false, Some("0"), false, false),
+            (false, Some(" OFF "), false, false),
+            (false, Some("1"), false, true),
+            (false, Some("true"), false, true),
+            (false, Some("yes"), false, true),
+            (true, None, false, true),
+            (true, Some("0"), false, true),
+            (false, None, true, true),
+            (false, Some("0"), true, true),
+        ];
+
+        for (debug_build, env_value, offline, expected) in cases {
+            assert_eq!(
+                resolve_checks_disabled(debug_build, env_value, offline),
+                expected,
+                "debug={} env={:?} offline={}",
+                debug_build,
+                env_value,
+                offline
+            );
+        }
+    }
+
+    #[test]
+    fn cached_update_cases() {
+        let cases = [
+            ("1.3.0", "1.2.0", Some("1.3.0"), true),
+            ("2.0.0", "1.9.9", Some("2.0.0"), true),
+            ("1.2.0", "1.2.0", None, false),
+            ("1.1.0", "1.2.0", None, false),
+        ];
+
+        for (stored, current, expected, kept) in cases {
+            let tmp = tempfile::TempDir::new().unwrap();
+            let path = tmp.path().join(".update-cache.json");
+            write_cache(&path, stored);
+
+            assert_eq!(load_cached_update(&path, current).as_deref(), expected, "stored {} current {}", stored, current);
+            assert_eq!(path.exists(), kept, "stored {} current {}", stored, current);
+        }
+    }
+
+    #[test]
+    fn cached_update_ignores_missing_or_corrupt_cache() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(".update-cache.json");
+        assert_eq!(load_cached_update(&path, "1.0.0"), None);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(load_cached_update(&path, "1.0.0"), None);
+    }
+
+    #[test]
+    fn store_update_result_writes_or_clears_cache() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join(".update-cache.json");
+
+        store_update_result(&path, Some("1.5.0"));
+        assert_eq!(load_cached_update(&path, "1.4.0").as_deref(), Some("1.5.0"));
+
+        store_update_result(&path, None);
+        assert!(!path.exists());
+    }
+}
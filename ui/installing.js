@@ -1,8 +1,18 @@
+import { subscribe as subscribeEvents } from './events.js';
+
 const installing = new Map();
 const listeners = new Set();
 
+subscribeEvents((event) => {
+    if (event.type !== 'install_progress') return;
+    const entry = installing.get(event.id);
+    if (!entry) return;
+    entry.progress = { stage: event.stage, percent: event.percent };
+    notify();
+});
+
 export function add(id, name) {
-    installing.set(id, { id, name });
+    installing.set(id, { id, name, progress: null });
     notify();
 }
 
@@ -15,6 +25,11 @@ export function has(id) {
     return installing.has(id);
 }
 
+export function progressLabel(id) {
+    const progress = installing.get(id)?.progress;
+    return progress ? `${progress.stage} ${progress.percent}%` : '';
+}
+
 export function getAll() {
     return Array.from(installing.values());
 }
@@ -17,3 +17,29 @@ export function navigate(state, key, total, delta) {
     }
     return false;
 }
+
+export async function errorMessage(res) {
+    const text = await res.text();
+    try {
+        return JSON.parse(text).error?.message || text;
+    } catch {
+        return text;
+    }
+}
+
+export function escapeHtml(str) {
+    if (!str) return '';
+    return String(str).replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;').replace(/"/g, '&quot;');
+}
+
+export function formatBytes(bytes) {
+    if (bytes === null || bytes === undefined) return '';
+    const units = ['B', 'KB', 'MB', 'GB'];
+    let value = bytes;
+    let unit = 0;
+    while (value >= 1024 && unit < units.length - 1) {
+        value /= 1024;
+        unit++;
+    }
+    return unit === 0 ? `${value} ${units[unit]}` : `${value.toFixed(1)} ${units[unit]}`;
+}
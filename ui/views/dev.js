@@ -1,3 +1,4 @@
+import { errorMessage } from '../utils.js';
 import { subscribe } from '../events.js';
 
 export const id = 'dev';
@@ -8,6 +9,8 @@ const state = {
     error: null,
     plugins: [],
     discovered: [],
+    discoveryErrors: [],
+    searchPaths: [],
     discovering: false,
     selectedIndex: 0,
     showLinkInput: false,
@@ -35,11 +38,21 @@ function handleEvent(event) {
         state.discovering = true;
         updateView();
     } else if (event.type === 'discovery_complete') {
+        loadSearchPaths().then(() => { if (!state.linkingId) updateView(); });
         state.discovering = false;
         state.discovered = event.plugins || [];
+        state.discoveryErrors = event.errors || [];
         updateView();
     } else if (event.type === 'plugins_changed') {
         loadLinkedPlugins();
+    } else if (event.type === 'snapshot') {
+        state.discovering = event.discovery === 'discovering';
+        state.discovered = event.discovered || [];
+        state.discoveryErrors = event.discovery_errors || [];
+        updateView();
+    } else if (event.type === 'resync') {
+        fetchDiscoveryState();
+        loadLinkedPlugins();
     }
 }
 
@@ -79,7 +92,8 @@ function updateView() {
             id: d.id,
             name: d.name,
             path: d.path,
-            status: 'local'
+            status: 'local',
+            hasStoreInstall: d.installed_not_linked
         });
     }
 
@@ -131,6 +145,8 @@ function updateView() {
             actionBtn = `<button class="refresh-btn spinning" disabled>↻</button>`;
         } else if (p.status === 'linked') {
             actionBtn = `<button class="btn btn-sm btn-outline-danger" data-action="unlink" data-id="${p.id}">Unlink</button>`;
+        } else if (p.path && p.hasStoreInstall) {
+            actionBtn = `<button class="btn btn-sm btn-success" data-action="link-existing" data-id="${p.id}" data-path="${p.path}" title="Back up the installed copy and link this clone">Replace with Link</button>`;
         } else if (p.path) {
             actionBtn = `<button class="btn btn-sm btn-success" data-action="link" data-id="${p.id}" data-path="${p.path}">Link</button>`;
         } else {
@@ -178,6 +194,9 @@ function updateView() {
                     ` : '<p class="empty-state">No plugins found</p>'}
                 </div>
 
+                ${renderDiscoveryErrors()}
+                ${renderSearchPaths()}
+
                 ${state.showLinkInput ? `
                     <div class="link-input-row">
                         <input type="text" id="link-path" placeholder="/path/to/plugin" value="${state.linkPath}" autofocus>
@@ -218,6 +237,42 @@ function updateView() {
     }
 }
 
+function renderDiscoveryErrors() {
+    const errors = state.discoveryErrors;
+    if (!errors.length) return '';
+    const noun = errors.length === 1 ? 'directory' : 'directories';
+    return `
+        <details class="discovery-errors">
+            <summary>${errors.length} ${noun} skipped</summary>
+            <ul>
+                ${errors.map(e => `<li><span class="plugin-path">${e.path}</span> ${e.reason}</li>`).join('')}
+            </ul>
+        </details>
+    `;
+}
+
+function renderSearchPaths() {
+    const paths = state.searchPaths;
+    if (!paths.length) return '';
+    const noun = paths.length === 1 ? 'directory' : 'directories';
+    return `
+        <details class="search-paths">
+            <summary>Searched ${paths.length} ${noun}</summary>
+            <ul>
+                ${paths.map(p => `<li class="${p.exists ? '' : 'missing'}"><span class="plugin-path">${p.path}</span>${p.exists ? '' : ' (missing)'}</li>`).join('')}
+            </ul>
+        </details>
+    `;
+}
+
+async function loadSearchPaths() {
+    try {
+        const res = await fetch('/api/dev/search-paths');
+        if (!res.ok) return;
+        state.searchPaths = await res.json();
+    } catch (e) {}
+}
+
 function handleClick(e) {
     const action = e.target.closest('[data-action]')?.dataset.action;
     const id = e.target.closest('[data-id]')?.dataset.id;
@@ -230,6 +285,7 @@ function handleClick(e) {
     if (action === 'cancel-link') cancelLink();
     if (action === 'unlink' && id) deleteLink(id);
     if (action === 'link' && id && path) quickLink(path, id);
+    if (action === 'link-existing' && id && path) quickLink(path, id, true);
     if (action === 'link-manual' && id) {
         state.linkPath = '';
         showLinkInput();
@@ -249,25 +305,26 @@ function handleItemActivation() {
     if (item.status === 'linked') {
         deleteLink(item.id);
     } else if (item.path) {
-        quickLink(item.path, item.id);
+        quickLink(item.path, item.id, item.hasStoreInstall);
     } else {
         showLinkInput();
     }
 }
 
-async function quickLink(path, id) {
+async function quickLink(path, id, replaceInstalled = false) {
     if (state.linkingId) return;
     state.linkingId = id;
     updateView();
 
     try {
-        const res = await fetch('/api/dev/links', {
+        const endpoint = replaceInstalled ? '/api/dev/link-existing' : '/api/dev/links';
+        const res = await fetch(endpoint, {
             method: 'POST',
             headers: { 'Content-Type': 'application/json' },
             body: JSON.stringify({ path, id })
         });
         if (!res.ok) {
-            console.error('Failed to link:', await res.text());
+            console.error('Failed to link:', await errorMessage(res));
             return;
         }
         await triggerReload();
@@ -308,7 +365,7 @@ async function confirmLink() {
         });
 
         if (!res.ok) {
-            state.linkError = await res.text();
+            state.linkError = await errorMessage(res);
             updateView();
             return;
         }
@@ -332,7 +389,7 @@ async function deleteLink(id) {
     try {
         const res = await fetch(`/api/dev/links/${id}`, { method: 'DELETE' });
         if (!res.ok) {
-            console.error('Failed to delete link:', await res.text());
+            console.error('Failed to delete link:', await errorMessage(res));
             return;
         }
         await triggerReload();
@@ -349,6 +406,7 @@ async function deleteLink(id) {
 }
 
 async function refreshDiscoveryState() {
+    await loadSearchPaths();
     try {
         const res = await fetch('/api/dev/discovery-state');
         if (!res.ok) return;
@@ -356,6 +414,7 @@ async function refreshDiscoveryState() {
         state.discovering = data.status === 'discovering';
         if (data.status === 'complete') {
             state.discovered = data.plugins;
+            state.discoveryErrors = data.errors || [];
         }
     } catch (e) {}
 }
@@ -366,7 +425,7 @@ async function triggerReload() {
 
 async function triggerDiscovery() {
     if (state.discovering) return;
-    await fetch('/api/dev/discover', { method: 'POST' });
+    await fetch('/api/dev/discover?force=true', { method: 'POST' });
 }
 
 async function reloadPlugins() {
@@ -379,7 +438,7 @@ async function reloadPlugins() {
     try {
         const [reloadRes, discoverRes] = await Promise.all([
             fetch('/api/dev/reload', { method: 'POST' }),
-            fetch('/api/dev/discover', { method: 'POST' })
+            fetch('/api/dev/discover?force=true', { method: 'POST' })
         ]);
 
         if (reloadRes.ok && discoverRes.ok) {
@@ -1,10 +1,14 @@
 import { updateSelection as updateSel, navigate as nav } from '../utils.js';
+import { subscribe } from '../events.js';
 
 export const id = 'hotkeys';
 
 const state = {
     hotkeys: [],
     plugins: [],
+    keyNames: null,
+    status: {},
+    suspended: false,
     selectedIndex: -1,
     editModalOpen: false,
     recordingKey: false,
@@ -13,6 +17,7 @@ const state = {
 };
 
 let container = null;
+let unsubscribe = null;
 
 export function render(containerEl) {
     container = containerEl;
@@ -24,12 +29,34 @@ export function render(containerEl) {
             </header>
             <div id="hotkeys-list" class="hotkeys-list"></div>
             <footer class="help">
-                ↑↓ navigate • Enter edit • a add • d delete
+                ↑↓ navigate • Enter edit • a add • d delete • p pause/resume
             </footer>
         </div>
     `;
     
     loadData();
+    unsubscribe = subscribe(handleEvent);
+}
+
+function handleEvent(event) {
+    if ((event.type === 'hotkeys_changed' || event.type === 'resync') && !state.editModalOpen) {
+        reloadHotkeys();
+    }
+}
+
+async function reloadHotkeys() {
+    try {
+        const res = await fetch('/api/hotkeys');
+        if (!res.ok) return;
+        const config = await res.json();
+        state.hotkeys = config.hotkeys || [];
+        state.selectedIndex = Math.min(state.selectedIndex, state.hotkeys.length - 1);
+        renderList();
+        updateSelection();
+        setTimeout(refreshStatus, 300);
+    } catch (error) {
+        console.error('Failed to reload hotkeys:', error);
+    }
 }
 
 async function loadData() {
@@ -39,9 +66,11 @@ async function loadData() {
     listEl.addEventListener('click', handleClick);
     
     try {
-        const [hotkeysRes, pluginsRes] = await Promise.all([
+        const [hotkeysRes, pluginsRes, statusRes, keysRes] = await Promise.all([
             fetch('/api/hotkeys'),
-            fetch('/api/installed')
+            fetch('/api/installed'),
+            fetch('/api/hotkeys/status'),
+            fetch('/api/hotkeys/keys')
         ]);
         
         if (hotkeysRes.ok) {
@@ -52,6 +81,15 @@ async function loadData() {
         if (pluginsRes.ok) {
             state.plugins = await pluginsRes.json();
         }
+
+        if (statusRes.ok) {
+            setStatus(await statusRes.json());
+        }
+
+        if (keysRes.ok) {
+            const { keys } = await keysRes.json();
+            state.keyNames = new Set(keys);
+        }
         
         renderList();
         if (state.hotkeys.length > 0) {
@@ -63,6 +101,48 @@ async function loadData() {
     }
 }
 
+function setStatus(report) {
+    state.suspended = !!report.suspended;
+    state.status = Object.fromEntries((report.hotkeys || []).map(h => [h.id, h]));
+}
+
+async function toggleSuspended() {
+    const endpoint = state.suspended ? 'resume' : 'suspend';
+    try {
+        await fetch(`/api/hotkeys/${endpoint}`, { method: 'POST' });
+        setTimeout(refreshStatus, 300);
+    } catch (error) {
+        console.error(`Failed to ${endpoint} hotkeys:`, error);
+    }
+}
+
+async function refreshStatus() {
+    try {
+        const res = await fetch('/api/hotkeys/status');
+        if (!res.ok) return;
+        setStatus(await res.json());
+        renderList();
+        updateSelection();
+    } catch (error) {
+        console.error('Failed to load hotkey status:', error);
+    }
+}
+
+const STATUS_LABELS = {
+    registered: 'Active',
+    failed: 'Rejected',
+    invalid: 'Invalid',
+    disabled: 'Off'
+};
+
+function renderStatus(hotkeyId) {
+    if (state.suspended) return '<span class="col-status disabled">Paused</span>';
+    const entry = state.status[hotkeyId];
+    if (!entry) return '<span class="col-status"></span>';
+    const title = entry.error ? ` title="${entry.error}"` : '';
+    return `<span class="col-status ${entry.status}"${title}>${STATUS_LABELS[entry.status] || entry.status}</span>`;
+}
+
 function renderList() {
     const listEl = document.getElementById('hotkeys-list');
     if (!listEl) return;
@@ -76,11 +156,17 @@ function renderList() {
         return;
     }
     
+    const banner = state.suspended
+        ? '<div class="hotkeys-suspended">Hotkeys paused. Press <kbd>p</kbd> to resume.</div>'
+        : '';
+
     listEl.innerHTML = `
+        ${banner}
         <div class="hotkey-header">
             <span class="col-key">Shortcut</span>
             <span class="col-plugin">Plugin</span>
             <span class="col-action">Action</span>
+            <span class="col-status">Status</span>
         </div>
         ${state.hotkeys.map((hk, index) => {
             const plugin = state.plugins.find(p => p.id === hk.plugin_id);
@@ -92,6 +178,7 @@ function renderList() {
                     <span class="col-key"><kbd>${hk.key}</kbd></span>
                     <span class="col-plugin">${pluginName}</span>
                     <span class="col-action">${actionLabel}</span>
+                    ${renderStatus(hk.id)}
                 </div>
             `;
         }).join('')}
@@ -303,13 +390,17 @@ function formatKeyEvent(e) {
     }
     
     const key = getKeyName(e.code);
-    if (key) {
+    if (key && isAcceptedKey(key)) {
         parts.push(key);
     }
     
     return parts.join('+');
 }
 
+function isAcceptedKey(key) {
+    return !state.keyNames || state.keyNames.has(key.toLowerCase());
+}
+
 function getKeyName(code) {
     if (code.startsWith('Key')) return code.slice(3);
     if (code.startsWith('Digit')) return code.slice(5);
@@ -405,11 +496,16 @@ async function deleteSelected() {
 
 async function persistHotkeys() {
     try {
-        await fetch('/api/hotkeys', {
+        const res = await fetch('/api/hotkeys', {
             method: 'PUT',
             headers: { 'Content-Type': 'application/json' },
             body: JSON.stringify({ hotkeys: state.hotkeys })
         });
+        if (res.ok) {
+            const { warnings = [] } = await res.json();
+            warnings.forEach(w => console.warn(`Hotkey ${w.hotkey_id}: ${w.message}`));
+        }
+        setTimeout(refreshStatus, 300);
     } catch (error) {
         console.error('Failed to save hotkeys:', error);
     }
@@ -548,7 +644,9 @@ const keyHandlers = {
     a: () => openEditModal(),
     A: () => openEditModal(),
     d: deleteSelected,
-    D: deleteSelected
+    D: deleteSelected,
+    p: toggleSuspended,
+    P: toggleSuspended
 };
 
 function navigate(delta) {
@@ -563,6 +661,12 @@ export function isBlocking() {
 
 export function onFocus() {
     updateSelection();
+    if (!unsubscribe) {
+        unsubscribe = subscribe(handleEvent);
+    }
 }
 
-export function onBlur() {}
+export function onBlur() {
+    unsubscribe?.();
+    unsubscribe = null;
+}
@@ -1,4 +1,4 @@
-import { updateSelection as updateSel, navigate as nav } from '../utils.js';
+import { updateSelection as updateSel, navigate as nav, errorMessage, formatBytes } from '../utils.js';
 import { subscribe } from '../events.js';
 import * as installing from '../installing.js';
 
@@ -33,6 +33,7 @@ export function render(containerEl) {
             <header>
                 <h1>Plugins</h1>
             </header>
+            <div id="plugin-problems"></div>
             <div id="plugins-grid" class="plugin-grid"></div>
             <footer class="help">
                 ←↑↓→ navigate • Enter open • u update • d delete
@@ -42,7 +43,7 @@ export function render(containerEl) {
 
     loadPlugins();
     unsubscribe = subscribe((event) => {
-        if (event.type === 'plugins_changed') refreshPlugins();
+        if (event.type === 'plugins_changed' || event.type === 'resync') refreshPlugins();
     });
     unsubscribeInstalling = installing.subscribe(() => renderGrid());
 }
@@ -63,6 +64,7 @@ async function loadPlugins() {
         restoreSelection();
         renderGrid();
         updateSelection();
+        loadProblems();
         
         checkForUpdates();
     } catch (error) {
@@ -108,6 +110,7 @@ function renderGrid() {
         <div class="plugin-card ghost">
             <span class="ghost-spinner">↻</span>
             <div class="plugin-name">${plugin.name}</div>
+            <div class="install-progress">${installing.progressLabel(plugin.id)}</div>
         </div>
     `).join('');
 
@@ -118,7 +121,7 @@ function renderGrid() {
         const isUpdating = state.updating.has(plugin.id);
 
         return `
-            <div class="plugin-card ${noUiClass} ${updateClass}" data-index="${index}" data-plugin-id="${plugin.id}">
+            <div class="plugin-card ${noUiClass} ${updateClass}" data-index="${index}" data-plugin-id="${plugin.id}" title="${plugin.name} · ${formatBytes(plugin.size_bytes)} on disk${trackingLabel(plugin.git_ref)}">
                 <img src="${coverUrl}" alt="${plugin.name}" onerror="this.src='${PLACEHOLDER_SVG}'">
                 <div class="plugin-name">${plugin.name}</div>
                 ${plugin.update_available ? `
@@ -129,6 +132,7 @@ function renderGrid() {
                 <button class="plugin-cog" aria-label="Plugin options">⚙</button>
                 <div class="plugin-context-menu">
                     ${plugin.update_available ? '<button class="context-update">Update</button>' : ''}
+                    <button class="context-reveal">Show Files</button>
                     <button class="context-delete">Delete</button>
                 </div>
             </div>
@@ -138,6 +142,12 @@ function renderGrid() {
     gridEl.innerHTML = ghostCards + pluginCards;
 }
 
+function trackingLabel(gitRef) {
+    if (!gitRef) return '';
+    const commit = gitRef.commit.slice(0, 7);
+    return gitRef.branch ? ` · tracking ${gitRef.branch} @ ${commit}` : ` · detached @ ${commit}`;
+}
+
 function updateSelection() {
     updateSel('.plugin-card', state.selectedIndex);
 }
@@ -158,6 +168,13 @@ const clickHandlers = [
         selector: '.plugin-cog',
         handler: el => toggleContextMenu(el.closest('.plugin-card'))
     },
+    {
+        selector: '.context-reveal',
+        handler: el => {
+            closeAllContextMenus();
+            revealPlugin(el.closest('.plugin-card').dataset.pluginId);
+        }
+    },
     {
         selector: '.context-delete',
         handler: el => {
@@ -272,8 +289,7 @@ async function confirmUninstall() {
     try {
         const response = await fetch(`/api/uninstall/${pluginId}`, { method: 'POST' });
         const result = await response.json();
-        
-        if (!result.success) throw new Error(result.message);
+        if (!result.success) throw new Error(result.error?.message || result.message);
         
         state.plugins = state.plugins.filter(p => p.id !== pluginId);
         state.selectedIndex = Math.min(state.selectedIndex, Math.max(0, state.plugins.length - 1));
@@ -292,10 +308,12 @@ async function updatePlugin(pluginId) {
     updateSelection();
     
     try {
-        const response = await fetch(`/api/update/${pluginId}`, { method: 'POST' });
-        const result = await response.json();
-        
-        if (!result.success) throw new Error(result.message);
+        let result = await fetch(`/api/update/${pluginId}`, { method: 'POST' }).then(r => r.json());
+        if (result.error?.code === 'local_changes') {
+            if (!confirm(`${result.error.message}\n\nDiscard them and update anyway?`)) return;
+            result = await fetch(`/api/update/${pluginId}?force=true`, { method: 'POST' }).then(r => r.json());
+        }
+        if (!result.success) throw new Error(result.error?.message || result.message);
     } catch (error) {
         console.error(`Failed to update plugin: ${error.message}`);
     } finally {
@@ -304,6 +322,15 @@ async function updatePlugin(pluginId) {
     }
 }
 
+async function revealPlugin(pluginId) {
+    try {
+        const response = await fetch(`/api/plugins/${pluginId}/reveal`, { method: 'POST' });
+        if (!response.ok) throw new Error(await errorMessage(response));
+    } catch (error) {
+        console.error(`Failed to reveal plugin: ${error.message}`);
+    }
+}
+
 async function refreshPlugins() {
     try {
         const response = await fetch('/api/installed');
@@ -313,11 +340,38 @@ async function refreshPlugins() {
         state.plugins.sort((a, b) => a.name.localeCompare(b.name));
         renderGrid();
         updateSelection();
+        loadProblems();
     } catch (error) {
         console.error(`Failed to refresh plugins: ${error.message}`);
     }
 }
 
+async function loadProblems() {
+    try {
+        const response = await fetch('/api/plugins/problems');
+        if (!response.ok) throw new Error(await errorMessage(response));
+        renderProblems(await response.json());
+    } catch (error) {
+        console.error(`Failed to load plugin problems: ${error.message}`);
+    }
+}
+
+function renderProblems(problems) {
+    const el = document.getElementById('plugin-problems');
+    if (!el) return;
+
+    el.replaceChildren(...problems.map(problem => {
+        const row = document.createElement('div');
+        row.className = 'plugin-problem';
+        const name = document.createElement('strong');
+        name.textContent = problem.id;
+        const message = document.createElement('span');
+        message.textContent = problem.error;
+        row.append(name, message);
+        return row;
+    }));
+}
+
 function updateSelected() {
     const plugin = state.plugins[state.selectedIndex];
     if (plugin?.update_available) {
@@ -1,4 +1,4 @@
-import { updateSelection as updateSel, navigate as nav } from '../utils.js';
+import { updateSelection as updateSel, navigate as nav, errorMessage, escapeHtml } from '../utils.js';
 import { subscribe } from '../events.js';
 import * as installing from '../installing.js';
 
@@ -69,7 +69,8 @@ export function render(containerEl) {
     checkTokenStatus();
     loadPlugins();
     unsubscribe = subscribe((event) => {
-        if (event.type === 'plugins_changed') loadPlugins();
+        if (event.type === 'plugins_changed' || event.type === 'resync') loadPlugins();
+        if (event.type === 'install_progress') updateInstallProgress(event.id);
     });
 }
 
@@ -109,6 +110,17 @@ function renderTokenInput(banner) {
     });
 }
 
+function showOfflineBanner() {
+    const banner = document.getElementById('token-banner');
+    if (!banner) return;
+
+    banner.innerHTML = `
+        <div class="rate-limit-banner">
+            <span>Offline mode — showing cached plugins. Install and update are unavailable.</span>
+        </div>
+    `;
+}
+
 function renderRateLimitMessage(banner) {
     banner.innerHTML = `
         <div class="rate-limit-banner">
@@ -156,15 +168,20 @@ async function loadPlugins(forceRefresh = false) {
     updateRefreshButton();
     
     try {
-        const url = forceRefresh ? '/api/plugins?refresh=true' : '/api/plugins';
-        const response = await fetch(url);
+        if (forceRefresh) {
+            const refresh = await fetch('/api/cache/refresh', { method: 'POST' });
+            if (!refresh.ok) throw new Error('Failed to refresh plugin cache');
+        }
+        const response = await fetch('/api/plugins');
         if (!response.ok) throw new Error('Failed to fetch plugins');
         
         const data = await response.json();
         state.plugins = data.plugins;
         state.cacheAgeSecs = data.cache_age_secs;
         
-        if (state.plugins.length === 0 && !state.hasToken) {
+        if (data.offline) {
+            showOfflineBanner();
+        } else if (state.plugins.length === 0 && !state.hasToken) {
             showRateLimitBanner();
         }
         
@@ -202,6 +219,17 @@ function updateRefreshButton() {
     }
 }
 
+function renderCredit(plugin) {
+    const author = plugin.author ? ` · by ${escapeHtml(plugin.author)}` : '';
+    const homepage = /^https?:\/\//.test(plugin.homepage || '')
+        ? ` · <a class="homepage" href="${escapeHtml(plugin.homepage)}" target="_blank" rel="noopener">Homepage</a>`
+        : '';
+    const source = /^https?:\/\//.test(plugin.repo_url || '')
+        ? ` · <a class="homepage" href="${escapeHtml(plugin.repo_url)}" target="_blank" rel="noopener">View source</a>`
+        : '';
+    return author + homepage + source;
+}
+
 function renderPlugins(plugins) {
     const listEl = document.getElementById('store-list');
     if (!listEl) return;
@@ -215,14 +243,17 @@ function renderPlugins(plugins) {
         const isInstalling = installing.has(plugin.id);
         return `
             <div class="plugin-card ${plugin.installed ? 'installed' : ''} ${isInstalling ? 'installing' : ''}" data-index="${index}" data-plugin-id="${plugin.id}" data-installed="${plugin.installed}">
+                ${plugin.has_cover ? `<img class="store-cover" src="/api/cover/${plugin.id}" alt="${plugin.name}" onerror="this.remove()">` : ''}
                 <h3>${plugin.name}</h3>
-                <div class="version">v${plugin.version}</div>
+                <div class="version">v${plugin.version}${renderCredit(plugin)}</div>
                 <div class="description">${plugin.description}</div>
                 <div class="button-group">
                     ${plugin.installed ? `
                         <span class="installed-badge">Installed</span>
                     ` : isInstalling ? `
                         <button class="refresh-btn spinning" disabled></button>
+                        <span class="install-progress">${installing.progressLabel(plugin.id)}</span>
+                        <button class="btn btn-ghost btn-sm cancel-install">Cancel</button>
                     ` : `
                         <button class="btn btn-primary install" style="width: 100%">Install</button>
                     `}
@@ -232,6 +263,12 @@ function renderPlugins(plugins) {
     }).join('');
 }
 
+function updateInstallProgress(id) {
+    const card = document.querySelector(`.plugin-card[data-plugin-id="${CSS.escape(id)}"]`);
+    const label = card?.querySelector('.install-progress');
+    if (label) label.textContent = installing.progressLabel(id);
+}
+
 function handleListClick(e) {
     const card = e.target.closest('.plugin-card');
     if (!card) return;
@@ -241,6 +278,11 @@ function handleListClick(e) {
         installPlugin(pluginId);
         return;
     }
+
+    if (e.target.tagName === 'BUTTON' && e.target.classList.contains('cancel-install')) {
+        cancelInstall(card.dataset.pluginId);
+        return;
+    }
     
     const index = parseInt(card.dataset.index, 10);
     if (index !== state.selectedIndex) {
@@ -318,7 +360,7 @@ async function installPlugin(id) {
 
     try {
         const response = await fetch(`/api/install/${id}`, { method: 'POST' });
-        if (!response.ok) throw new Error('Installation failed');
+        if (!response.ok) throw new Error(await errorMessage(response));
 
         if (plugin) {
             plugin.installed = true;
@@ -332,6 +374,17 @@ async function installPlugin(id) {
     }
 }
 
+async function cancelInstall(id) {
+    if (!installing.has(id)) return;
+
+    try {
+        const response = await fetch(`/api/install/${id}/cancel`, { method: 'POST' });
+        if (!response.ok) throw new Error(await errorMessage(response));
+    } catch (error) {
+        console.error(`Failed to cancel install: ${error.message}`);
+    }
+}
+
 export function onFocus() {
     updateSelection();
     if (searchInput) {